@@ -0,0 +1,107 @@
+use crate::asf_object::{AsfObject, CONTENT_DESCRIPTION_OBJECT};
+use crate::{decode_utf16le_string, encode_utf16le_string};
+use anyhow::{anyhow, Result};
+
+/// Represents the ASF Content Description Object, which holds the basic metadata of a WMA file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AsfContentDescription {
+    /// The title of the content.
+    pub title: String,
+
+    /// The author (artist) of the content.
+    pub author: String,
+
+    /// The copyright of the content.
+    pub copyright: String,
+
+    /// The description (comment) of the content.
+    pub description: String,
+
+    /// The rating of the content.
+    pub rating: String,
+}
+
+impl AsfContentDescription {
+    /// Parses the content description from the data of its object.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The data of the Content Description Object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_content_description::*;
+    /// let bytes = [
+    ///     4, 0, 4, 0, 0, 0, 0, 0, 0, 0,
+    ///     b'A', 0, 0, 0, b'B', 0, 0, 0,
+    /// ];
+    ///
+    /// let description = AsfContentDescription::parse(&bytes)?;
+    ///
+    /// assert_eq!(description.title, "A");
+    /// assert_eq!(description.author, "B");
+    /// assert_eq!(description.copyright, "");
+    /// assert_eq!(description.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<AsfContentDescription> {
+        let lengths = bytes
+            .get(0..10)
+            .ok_or(anyhow!("ASF content description is truncated!"))?
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]) as usize);
+        let mut index = 10;
+        let mut strings = vec![];
+        for length in lengths {
+            let string_bytes = bytes
+                .get(index..index + length)
+                .ok_or(anyhow!("ASF content description is truncated!"))?;
+            strings.push(decode_utf16le_string(string_bytes));
+            index += length;
+        }
+        let [title, author, copyright, description, rating]: [String; 5] = strings
+            .try_into()
+            .map_err(|_| anyhow!("ASF content description is missing strings!"))?;
+
+        Ok(AsfContentDescription {
+            title,
+            author,
+            copyright,
+            description,
+            rating,
+        })
+    }
+
+    /// Returns this content description as an ASF object.
+    pub fn to_object(&self) -> AsfObject {
+        AsfObject {
+            guid: CONTENT_DESCRIPTION_OBJECT,
+            data: self.to_bytes(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let strings: Vec<Vec<u8>> = [
+            &self.title,
+            &self.author,
+            &self.copyright,
+            &self.description,
+            &self.rating,
+        ]
+        .iter()
+        .map(|s| match s.is_empty() {
+            true => vec![],
+            false => encode_utf16le_string(s),
+        })
+        .collect();
+        let mut description_bytes: Vec<u8> = vec![];
+        for string in &strings {
+            description_bytes.extend((string.len() as u16).to_le_bytes());
+        }
+        for string in strings {
+            description_bytes.extend(string);
+        }
+        description_bytes
+    }
+}
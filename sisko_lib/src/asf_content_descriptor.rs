@@ -0,0 +1,113 @@
+use crate::asf_descriptor_value::AsfDescriptorValue;
+use crate::asf_picture::AsfPicture;
+use crate::flac_picture::FlacPicture;
+use crate::{decode_utf16le_string, encode_utf16le_string};
+use anyhow::{anyhow, Result};
+
+/// The name of the descriptor that holds an attached picture.
+pub const WM_PICTURE: &str = "WM/Picture";
+
+/// Represents a descriptor in an ASF Extended Content Description Object (e.g. `WM/AlbumTitle`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AsfContentDescriptor {
+    /// The name of the descriptor.
+    pub name: String,
+
+    /// The value of the descriptor.
+    pub value: AsfDescriptorValue,
+}
+
+impl AsfContentDescriptor {
+    /// Returns a new descriptor with the given name and string value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the descriptor.
+    /// * `value` - The string value of the descriptor.
+    pub fn new_text(name: &str, value: &str) -> AsfContentDescriptor {
+        AsfContentDescriptor {
+            name: String::from(name),
+            value: AsfDescriptorValue::Unicode(String::from(value)),
+        }
+    }
+
+    /// Parses a descriptor from the start of the given bytes.
+    /// Returns the descriptor and the number of bytes it took up.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the descriptor from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_content_descriptor::*;
+    /// let bytes = [
+    ///     4, 0, b'W', 0, 0, 0,
+    ///     0, 0,
+    ///     6, 0, b'N', 0, b'I', 0, 0, 0,
+    /// ];
+    ///
+    /// let (descriptor, size) = AsfContentDescriptor::parse(&bytes)?;
+    ///
+    /// assert_eq!(descriptor, AsfContentDescriptor::new_text("W", "NI"));
+    /// assert_eq!(size, 16);
+    /// assert_eq!(descriptor.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(AsfContentDescriptor, usize)> {
+        let mut index = 0;
+        let name_length = read_u16(bytes, &mut index)? as usize;
+        let name = decode_utf16le_string(read_bytes(bytes, &mut index, name_length)?);
+        let value_type = read_u16(bytes, &mut index)?;
+        let value_length = read_u16(bytes, &mut index)? as usize;
+        let value =
+            AsfDescriptorValue::parse(value_type, read_bytes(bytes, &mut index, value_length)?)?;
+        Ok((AsfContentDescriptor { name, value }, index))
+    }
+
+    /// Returns a `WM/Picture` descriptor holding the given picture.
+    ///
+    /// # Arguments
+    ///
+    /// * `picture` - The picture to hold.
+    pub fn from_picture(picture: &FlacPicture) -> AsfContentDescriptor {
+        AsfContentDescriptor {
+            name: String::from(WM_PICTURE),
+            value: AsfDescriptorValue::Bytes(AsfPicture::from(picture).to_bytes()),
+        }
+    }
+
+    /// Returns the picture held by this `WM/Picture` descriptor.
+    pub fn picture(&self) -> Result<FlacPicture> {
+        match &self.value {
+            AsfDescriptorValue::Bytes(bytes) => Ok(FlacPicture::from(&AsfPicture::parse(bytes)?)),
+            _ => Err(anyhow!("ASF descriptor {} is not a picture!", self.name)),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let name = encode_utf16le_string(&self.name);
+        let value = self.value.to_bytes();
+        let mut descriptor_bytes: Vec<u8> = vec![];
+        descriptor_bytes.extend((name.len() as u16).to_le_bytes());
+        descriptor_bytes.extend(name);
+        descriptor_bytes.extend(self.value.value_type().to_le_bytes());
+        descriptor_bytes.extend((value.len() as u16).to_le_bytes());
+        descriptor_bytes.extend(value);
+        descriptor_bytes
+    }
+}
+
+fn read_u16(bytes: &[u8], index: &mut usize) -> Result<u16> {
+    let value = read_bytes(bytes, index, 2)?;
+    Ok(u16::from_le_bytes(value.try_into()?))
+}
+
+fn read_bytes<'a>(bytes: &'a [u8], index: &mut usize, length: usize) -> Result<&'a [u8]> {
+    let value = bytes
+        .get(*index..*index + length)
+        .ok_or(anyhow!("ASF content descriptor is truncated!"))?;
+    *index += length;
+    Ok(value)
+}
@@ -0,0 +1,102 @@
+use crate::{decode_utf16le_string, encode_utf16le_string};
+use anyhow::{anyhow, Result};
+
+/// Represents the typed value of a descriptor in an ASF Extended Content Description Object.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AsfDescriptorValue {
+    /// A UTF-16LE string.
+    Unicode(String),
+
+    /// An array of bytes.
+    Bytes(Vec<u8>),
+
+    /// A boolean, stored as a 32-bit integer.
+    Bool(bool),
+
+    /// A 32-bit unsigned integer.
+    Dword(u32),
+
+    /// A 64-bit unsigned integer.
+    Qword(u64),
+
+    /// A 16-bit unsigned integer.
+    Word(u16),
+}
+
+impl AsfDescriptorValue {
+    /// Parses a descriptor value of the given type.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_type` - The value data type of the descriptor.
+    /// * `bytes` - The bytes of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_descriptor_value::*;
+    /// let value = AsfDescriptorValue::parse(0, &[b'1', 0, 0, 0])?;
+    /// assert_eq!(value, AsfDescriptorValue::Unicode(String::from("1")));
+    /// assert_eq!(value.value_type(), 0);
+    /// assert_eq!(value.to_bytes(), vec![b'1', 0, 0, 0]);
+    ///
+    /// let value = AsfDescriptorValue::parse(3, &[7, 0, 0, 0])?;
+    /// assert_eq!(value, AsfDescriptorValue::Dword(7));
+    /// assert_eq!(value.text(), Some(String::from("7")));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(value_type: u16, bytes: &[u8]) -> Result<AsfDescriptorValue> {
+        match value_type {
+            0 => Ok(AsfDescriptorValue::Unicode(decode_utf16le_string(bytes))),
+            1 => Ok(AsfDescriptorValue::Bytes(bytes.to_vec())),
+            2 => Ok(AsfDescriptorValue::Bool(
+                bytes.iter().any(|byte| *byte != 0),
+            )),
+            3 => Ok(AsfDescriptorValue::Dword(u32::from_le_bytes(
+                bytes.try_into()?,
+            ))),
+            4 => Ok(AsfDescriptorValue::Qword(u64::from_le_bytes(
+                bytes.try_into()?,
+            ))),
+            5 => Ok(AsfDescriptorValue::Word(u16::from_le_bytes(
+                bytes.try_into()?,
+            ))),
+            _ => Err(anyhow!("Unknown ASF descriptor value type: {}", value_type)),
+        }
+    }
+
+    /// Returns the value data type of this value.
+    pub fn value_type(&self) -> u16 {
+        match self {
+            AsfDescriptorValue::Unicode(_) => 0,
+            AsfDescriptorValue::Bytes(_) => 1,
+            AsfDescriptorValue::Bool(_) => 2,
+            AsfDescriptorValue::Dword(_) => 3,
+            AsfDescriptorValue::Qword(_) => 4,
+            AsfDescriptorValue::Word(_) => 5,
+        }
+    }
+
+    /// Returns this value as text, or `None` if it is an array of bytes.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            AsfDescriptorValue::Unicode(value) => Some(value.clone()),
+            AsfDescriptorValue::Bytes(_) => None,
+            AsfDescriptorValue::Bool(value) => Some(value.to_string()),
+            AsfDescriptorValue::Dword(value) => Some(value.to_string()),
+            AsfDescriptorValue::Qword(value) => Some(value.to_string()),
+            AsfDescriptorValue::Word(value) => Some(value.to_string()),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            AsfDescriptorValue::Unicode(value) => encode_utf16le_string(value),
+            AsfDescriptorValue::Bytes(value) => value.clone(),
+            AsfDescriptorValue::Bool(value) => (*value as u32).to_le_bytes().to_vec(),
+            AsfDescriptorValue::Dword(value) => value.to_le_bytes().to_vec(),
+            AsfDescriptorValue::Qword(value) => value.to_le_bytes().to_vec(),
+            AsfDescriptorValue::Word(value) => value.to_le_bytes().to_vec(),
+        }
+    }
+}
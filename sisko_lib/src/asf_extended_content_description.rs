@@ -0,0 +1,77 @@
+use crate::asf_content_descriptor::AsfContentDescriptor;
+use crate::asf_object::{AsfObject, EXTENDED_CONTENT_DESCRIPTION_OBJECT};
+use anyhow::{anyhow, Result};
+
+/// Represents the ASF Extended Content Description Object, which holds named metadata descriptors.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AsfExtendedContentDescription {
+    /// The descriptors of the object.
+    pub descriptors: Vec<AsfContentDescriptor>,
+}
+
+impl AsfExtendedContentDescription {
+    /// Parses the extended content description from the data of its object.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The data of the Extended Content Description Object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_extended_content_description::*;
+    /// # use sisko_lib::asf_descriptor_value::*;
+    /// let bytes = [
+    ///     1, 0,
+    ///     4, 0, b'W', 0, 0, 0, 3, 0, 4, 0, 7, 0, 0, 0,
+    /// ];
+    ///
+    /// let description = AsfExtendedContentDescription::parse(&bytes)?;
+    ///
+    /// assert_eq!(description.get("W").map(|d| &d.value), Some(&AsfDescriptorValue::Dword(7)));
+    /// assert_eq!(description.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<AsfExtendedContentDescription> {
+        let count = bytes
+            .get(0..2)
+            .map(|count| u16::from_le_bytes([count[0], count[1]]))
+            .ok_or(anyhow!("ASF extended content description is truncated!"))?;
+        let mut index = 2;
+        let mut descriptors = vec![];
+        for _ in 0..count {
+            let (descriptor, size) = AsfContentDescriptor::parse(&bytes[index..])?;
+            descriptors.push(descriptor);
+            index += size;
+        }
+        Ok(AsfExtendedContentDescription { descriptors })
+    }
+
+    /// Returns the first descriptor with the given name, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the descriptor to get.
+    pub fn get(&self, name: &str) -> Option<&AsfContentDescriptor> {
+        self.descriptors
+            .iter()
+            .find(|descriptor| descriptor.name == name)
+    }
+
+    /// Returns this extended content description as an ASF object.
+    pub fn to_object(&self) -> AsfObject {
+        AsfObject {
+            guid: EXTENDED_CONTENT_DESCRIPTION_OBJECT,
+            data: self.to_bytes(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut description_bytes: Vec<u8> = vec![];
+        description_bytes.extend((self.descriptors.len() as u16).to_le_bytes());
+        for descriptor in &self.descriptors {
+            description_bytes.extend(descriptor.to_bytes());
+        }
+        description_bytes
+    }
+}
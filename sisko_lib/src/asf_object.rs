@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Result};
+
+/// The GUID of the ASF Header Object (75B22630-668E-11CF-A6D9-00AA0062CE6C).
+pub const HEADER_OBJECT: [u8; 16] = [
+    0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11, 0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C,
+];
+
+/// The GUID of the ASF File Properties Object (8CABDCA1-A947-11CF-8EE4-00C00C205365).
+pub const FILE_PROPERTIES_OBJECT: [u8; 16] = [
+    0xA1, 0xDC, 0xAB, 0x8C, 0x47, 0xA9, 0xCF, 0x11, 0x8E, 0xE4, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65,
+];
+
+/// The GUID of the ASF Content Description Object (75B22633-668E-11CF-A6D9-00AA0062CE6C).
+pub const CONTENT_DESCRIPTION_OBJECT: [u8; 16] = [
+    0x33, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11, 0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C,
+];
+
+/// The GUID of the ASF Extended Content Description Object (D2D0A440-E307-11D2-97F0-00A0C95EA850).
+pub const EXTENDED_CONTENT_DESCRIPTION_OBJECT: [u8; 16] = [
+    0x40, 0xA4, 0xD0, 0xD2, 0x07, 0xE3, 0xD2, 0x11, 0x97, 0xF0, 0x00, 0xA0, 0xC9, 0x5E, 0xA8, 0x50,
+];
+
+/// The size of the GUID and size fields at the start of every ASF object.
+pub const OBJECT_HEADER_SIZE: usize = 24;
+
+/// Represents an object in an Advanced Systems Format (WMA/WMV) file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AsfObject {
+    /// The GUID of the object, as stored in the file.
+    pub guid: [u8; 16],
+
+    /// The data of the object after its GUID and size.
+    pub data: Vec<u8>,
+}
+
+impl AsfObject {
+    /// Parses an object from the start of the given bytes.
+    /// Returns the object and the number of bytes it took up.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the object from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_object::*;
+    /// let mut bytes = CONTENT_DESCRIPTION_OBJECT.to_vec();
+    /// bytes.extend(26u64.to_le_bytes());
+    /// bytes.extend([1, 2]);
+    ///
+    /// let (object, size) = AsfObject::parse(&bytes)?;
+    ///
+    /// assert_eq!(object.guid, CONTENT_DESCRIPTION_OBJECT);
+    /// assert_eq!(object.data, vec![1, 2]);
+    /// assert_eq!(size, 26);
+    /// assert_eq!(object.to_bytes(), bytes);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(AsfObject, usize)> {
+        let header = bytes
+            .get(0..OBJECT_HEADER_SIZE)
+            .ok_or(anyhow!("ASF object header is truncated!"))?;
+        let guid: [u8; 16] = header[0..16].try_into()?;
+        let size = u64::from_le_bytes(header[16..24].try_into()?) as usize;
+        if size < OBJECT_HEADER_SIZE {
+            return Err(anyhow!("ASF object has an invalid size!"));
+        }
+        let data = bytes
+            .get(OBJECT_HEADER_SIZE..size)
+            .ok_or(anyhow!("ASF object is truncated!"))?
+            .to_vec();
+        Ok((AsfObject { guid, data }, size))
+    }
+
+    /// Parses all of the consecutive objects in the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the objects from.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<AsfObject>> {
+        let mut objects = vec![];
+        let mut index = 0;
+        while index < bytes.len() {
+            let (object, size) = Self::parse(&bytes[index..])?;
+            objects.push(object);
+            index += size;
+        }
+        Ok(objects)
+    }
+
+    /// Returns the total size of this object in bytes, including its GUID and size.
+    pub fn total_size(&self) -> usize {
+        OBJECT_HEADER_SIZE + self.data.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut object_bytes: Vec<u8> = vec![];
+        object_bytes.extend(self.guid);
+        object_bytes.extend((self.total_size() as u64).to_le_bytes());
+        object_bytes.extend(&self.data);
+        object_bytes
+    }
+}
@@ -0,0 +1,121 @@
+use crate::flac_picture::FlacPicture;
+use crate::picture_type::PictureType;
+use crate::{decode_utf16le_string, encode_utf16le_string};
+use anyhow::{anyhow, Result};
+
+/// Represents the value of a `WM/Picture` descriptor in an ASF file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AsfPicture {
+    /// The type of this picture.
+    pub picture_type: PictureType,
+
+    /// The MIME type for the picture.
+    pub mime_type: String,
+
+    /// The description of the picture.
+    pub description: String,
+
+    /// The binary data of the picture.
+    pub picture_data: Vec<u8>,
+}
+
+impl AsfPicture {
+    /// Parses a `WM/Picture` value from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the descriptor value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_picture::*;
+    /// # use sisko_lib::picture_type::*;
+    /// let bytes = [
+    ///     3, 2, 0, 0, 0,
+    ///     b'i', 0, b'/', 0, b'p', 0, 0, 0,
+    ///     0, 0,
+    ///     0xab, 0xcd,
+    /// ];
+    ///
+    /// let picture = AsfPicture::parse(&bytes)?;
+    ///
+    /// assert_eq!(picture.picture_type, PictureType::CoverFront);
+    /// assert_eq!(picture.mime_type, "i/p");
+    /// assert_eq!(picture.description, "");
+    /// assert_eq!(picture.picture_data, vec![0xab, 0xcd]);
+    /// assert_eq!(picture.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<AsfPicture> {
+        let header = bytes
+            .get(0..5)
+            .ok_or(anyhow!("ASF picture is truncated!"))?;
+        let picture_type = PictureType::parse(header[0])?;
+        let data_length = u32::from_le_bytes(header[1..5].try_into()?) as usize;
+        let mut index = 5;
+        let mime_type = read_utf16le_string(bytes, &mut index)?;
+        let description = read_utf16le_string(bytes, &mut index)?;
+        let picture_data = bytes
+            .get(index..index + data_length)
+            .ok_or(anyhow!("ASF picture is truncated!"))?
+            .to_vec();
+
+        Ok(AsfPicture {
+            picture_type,
+            mime_type,
+            description,
+            picture_data,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut picture_bytes: Vec<u8> = vec![];
+        picture_bytes.extend(self.picture_type.to_bytes());
+        picture_bytes.extend((self.picture_data.len() as u32).to_le_bytes());
+        picture_bytes.extend(encode_utf16le_string(&self.mime_type));
+        picture_bytes.extend(encode_utf16le_string(&self.description));
+        picture_bytes.extend(&self.picture_data);
+        picture_bytes
+    }
+}
+
+impl From<&FlacPicture> for AsfPicture {
+    fn from(picture: &FlacPicture) -> Self {
+        AsfPicture {
+            picture_type: picture.picture_type.clone(),
+            mime_type: picture.mime_type.clone(),
+            description: picture.description.clone(),
+            picture_data: picture.picture_data.clone(),
+        }
+    }
+}
+
+impl From<&AsfPicture> for FlacPicture {
+    fn from(picture: &AsfPicture) -> Self {
+        FlacPicture {
+            picture_type: picture.picture_type.clone(),
+            mime_type: picture.mime_type.clone(),
+            description: picture.description.clone(),
+            width: 0,
+            height: 0,
+            color_depth: 0,
+            indexed_colors: 0,
+            picture_data: picture.picture_data.clone(),
+        }
+    }
+}
+
+/// Reads a null-terminated UTF-16LE string, moving the index past its terminator.
+fn read_utf16le_string(bytes: &[u8], index: &mut usize) -> Result<String> {
+    let length = bytes
+        .get(*index..)
+        .unwrap_or_default()
+        .chunks_exact(2)
+        .position(|pair| pair == [0, 0])
+        .ok_or(anyhow!("ASF picture string is not terminated!"))?
+        * 2;
+    let string = decode_utf16le_string(&bytes[*index..*index + length]);
+    *index += length + 2;
+    Ok(string)
+}
@@ -0,0 +1,184 @@
+use crate::asf_content_description::AsfContentDescription;
+use crate::asf_extended_content_description::AsfExtendedContentDescription;
+use crate::asf_object::{
+    AsfObject, CONTENT_DESCRIPTION_OBJECT, EXTENDED_CONTENT_DESCRIPTION_OBJECT,
+    FILE_PROPERTIES_OBJECT, HEADER_OBJECT, OBJECT_HEADER_SIZE,
+};
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// The size of the object count and reserved bytes at the start of the Header Object's data.
+const HEADER_DATA_PREFIX_SIZE: usize = 6;
+
+/// Represents the metadata of an ASF (WMA/WMV) file: its Content Description and
+/// Extended Content Description Objects.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AsfTag {
+    /// The Content Description Object, if there is one.
+    pub content_description: Option<AsfContentDescription>,
+
+    /// The Extended Content Description Object, if there is one.
+    pub extended_content_description: Option<AsfExtendedContentDescription>,
+}
+
+impl AsfTag {
+    /// Parses the tag from the data of the given Header Object.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The Header Object of an ASF file.
+    pub fn parse(header: &AsfObject) -> Result<AsfTag> {
+        let mut tag = AsfTag::default();
+        for object in Self::header_objects(header)? {
+            if object.guid == CONTENT_DESCRIPTION_OBJECT {
+                tag.content_description = Some(AsfContentDescription::parse(&object.data)?);
+            } else if object.guid == EXTENDED_CONTENT_DESCRIPTION_OBJECT {
+                tag.extended_content_description =
+                    Some(AsfExtendedContentDescription::parse(&object.data)?);
+            }
+        }
+        Ok(tag)
+    }
+
+    /// Reads the tag from the ASF file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read the tag from.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<AsfTag> {
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(file);
+        Self::read_from_reader(&mut reader)
+    }
+
+    /// Reads the tag from the given ASF stream.
+    /// Only the Header Object at the start of the stream is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to read the tag from.
+    pub fn read_from_reader<R: Read>(reader: &mut R) -> Result<AsfTag> {
+        let mut object_header = [0u8; OBJECT_HEADER_SIZE];
+        reader.read_exact(&mut object_header)?;
+        if object_header[0..16] != HEADER_OBJECT {
+            return Err(anyhow!("No ASF header object found!"));
+        }
+        let size = u64::from_le_bytes(object_header[16..24].try_into()?) as usize;
+        let mut header_bytes = object_header.to_vec();
+        header_bytes.resize(size.max(OBJECT_HEADER_SIZE), 0);
+        reader.read_exact(&mut header_bytes[OBJECT_HEADER_SIZE..])?;
+        let (header, _) = AsfObject::parse(&header_bytes)?;
+        Self::parse(&header)
+    }
+
+    /// Returns the given ASF file's bytes with its description objects replaced by this tag's.
+    /// Missing objects are added to the end of the Header Object, and the file size in the
+    /// File Properties Object is updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_bytes` - The bytes of the ASF file to write the tag to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::asf_content_description::*;
+    /// # use sisko_lib::asf_object::*;
+    /// # use sisko_lib::asf_tag::*;
+    /// let mut properties_data = vec![0u8; 16];
+    /// properties_data.extend(86u64.to_le_bytes());
+    /// let properties = AsfObject { guid: FILE_PROPERTIES_OBJECT, data: properties_data };
+    /// let mut header_data = vec![1, 0, 0, 0, 1, 2];
+    /// header_data.extend(properties.to_bytes());
+    /// let header = AsfObject { guid: HEADER_OBJECT, data: header_data };
+    /// let file_bytes = header.to_bytes();
+    /// assert_eq!(file_bytes.len(), 78);
+    ///
+    /// let tag = AsfTag {
+    ///     content_description: Some(AsfContentDescription {
+    ///         title: String::from("Hurt"),
+    ///         ..Default::default()
+    ///     }),
+    ///     extended_content_description: None,
+    /// };
+    /// let new_bytes = tag.write_to_bytes(&file_bytes)?;
+    ///
+    /// let (new_header, size) = AsfObject::parse(&new_bytes)?;
+    /// assert_eq!(size, new_bytes.len());
+    /// assert_eq!(&new_header.data[0..4], &[2, 0, 0, 0]);
+    /// assert_eq!(&new_header.data[46..54], &(new_bytes.len() as u64).to_le_bytes());
+    /// assert_eq!(AsfTag::parse(&new_header)?, tag);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_to_bytes(&self, file_bytes: &[u8]) -> Result<Vec<u8>> {
+        let (header, header_size) = AsfObject::parse(file_bytes)?;
+        if header.guid != HEADER_OBJECT {
+            return Err(anyhow!("No ASF header object found!"));
+        }
+        let mut objects = Self::header_objects(&header)?;
+        Self::set_object(
+            &mut objects,
+            CONTENT_DESCRIPTION_OBJECT,
+            self.content_description
+                .as_ref()
+                .map(|description| description.to_object()),
+        );
+        Self::set_object(
+            &mut objects,
+            EXTENDED_CONTENT_DESCRIPTION_OBJECT,
+            self.extended_content_description
+                .as_ref()
+                .map(|description| description.to_object()),
+        );
+
+        let objects_size: usize = objects.iter().map(|object| object.total_size()).sum();
+        let new_header_size = OBJECT_HEADER_SIZE + HEADER_DATA_PREFIX_SIZE + objects_size;
+        let file_size = (file_bytes.len() - header_size + new_header_size) as u64;
+        for object in objects.iter_mut() {
+            if object.guid == FILE_PROPERTIES_OBJECT {
+                object
+                    .data
+                    .get_mut(16..24)
+                    .ok_or(anyhow!("ASF file properties object is truncated!"))?
+                    .copy_from_slice(&file_size.to_le_bytes());
+            }
+        }
+
+        let mut header_data: Vec<u8> = vec![];
+        header_data.extend((objects.len() as u32).to_le_bytes());
+        header_data.extend(&header.data[4..HEADER_DATA_PREFIX_SIZE]);
+        for object in &objects {
+            header_data.extend(object.to_bytes());
+        }
+        let mut new_bytes = AsfObject {
+            guid: HEADER_OBJECT,
+            data: header_data,
+        }
+        .to_bytes();
+        new_bytes.extend(&file_bytes[header_size..]);
+        Ok(new_bytes)
+    }
+
+    /// Returns the objects inside the given Header Object.
+    fn header_objects(header: &AsfObject) -> Result<Vec<AsfObject>> {
+        let objects = header
+            .data
+            .get(HEADER_DATA_PREFIX_SIZE..)
+            .ok_or(anyhow!("ASF header object is truncated!"))?;
+        AsfObject::parse_all(objects)
+    }
+
+    /// Replaces the object with the given GUID, appending it if it doesn't exist yet.
+    /// If there is no new object, the existing one is left as it is.
+    fn set_object(objects: &mut Vec<AsfObject>, guid: [u8; 16], new_object: Option<AsfObject>) {
+        let Some(new_object) = new_object else {
+            return;
+        };
+        match objects.iter().position(|object| object.guid == guid) {
+            Some(index) => objects[index] = new_object,
+            None => objects.push(new_object),
+        }
+    }
+}
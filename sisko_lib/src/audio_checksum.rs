@@ -0,0 +1,301 @@
+use crate::asf_object::{AsfObject, HEADER_OBJECT};
+use crate::ebml_element::{read_id, read_size};
+use crate::file_format::FileFormat;
+use crate::id3v2_tag_size;
+use crate::iff_file::IffFile;
+use crate::iff_format::IffFormat;
+use crate::matroska_element_id::{
+    ATTACHMENTS, BLOCK_GROUP, CHAPTERS, CLUSTER, CUES, INFO, SEEK_HEAD, SEGMENT, SIMPLE_BLOCK,
+    TAGS, TRACKS,
+};
+use crate::mp4_atom::Mp4Atom;
+use crate::ogg_codec::OggCodec;
+use crate::ogg_page::OggPage;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::path::Path;
+
+/// The size of an ID3v1 tag at the end of a file.
+const ID3V1_TAG_SIZE: usize = 128;
+
+/// The size of an APEv2 tag's header or footer.
+const APE_FOOTER_SIZE: usize = 32;
+
+/// The size of a Lyrics3v2 tag's size field and end marker.
+const LYRICS3_FOOTER_SIZE: usize = 15;
+
+/// The IDs of the segment's top-level elements, which end a cluster of unknown size.
+const LEVEL_1_IDS: [u32; 8] = [
+    ATTACHMENTS,
+    CHAPTERS,
+    CLUSTER,
+    CUES,
+    INFO,
+    SEEK_HEAD,
+    TAGS,
+    TRACKS,
+];
+
+/// Represents a SHA-256 checksum of only the audio payload of a file.
+/// Tags, padding and other metadata aren't included, so the checksum stays the same when a file
+/// is retagged and is the same for copies of the same audio with different tags.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AudioChecksum {
+    /// The format of the file the checksum is of.
+    pub format: FileFormat,
+
+    /// The SHA-256 digest of the audio payload.
+    pub digest: [u8; 32],
+}
+
+impl AudioChecksum {
+    /// Computes the checksum of the audio payload in the given file bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the whole file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::audio_checksum::*;
+    /// let mut audio = vec![0xFF, 0xFB, 0x90, 0x64];
+    /// audio.resize(417, 0);
+    /// let mut tagged = vec![b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 2, 0, 0];
+    /// tagged.extend(&audio);
+    /// tagged.extend(b"TAG");
+    /// tagged.resize(tagged.len() + 125, 0);
+    ///
+    /// let checksum = AudioChecksum::compute(&audio)?;
+    ///
+    /// assert_eq!(AudioChecksum::compute(&tagged)?, checksum);
+    /// assert_ne!(AudioChecksum::compute(&audio[..416])?, checksum);
+    /// assert_eq!(checksum.to_string().len(), 64);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn compute(bytes: &[u8]) -> Result<AudioChecksum> {
+        let range = untagged_range(bytes);
+        let bytes = &bytes[range];
+        let format = FileFormat::identify(bytes)?;
+        let mut hasher = Sha256::new();
+        match format {
+            FileFormat::Aac | FileFormat::Mpeg => hasher.update(bytes),
+            FileFormat::Aiff | FileFormat::Wav => hash_iff(bytes, &mut hasher)?,
+            FileFormat::Asf => hash_asf(bytes, &mut hasher)?,
+            FileFormat::Flac => hash_flac(bytes, &mut hasher)?,
+            FileFormat::Matroska => hash_matroska(bytes, &mut hasher)?,
+            FileFormat::Mp4 => hash_mp4(bytes, &mut hasher)?,
+            FileFormat::Ogg => hash_ogg(bytes, &mut hasher)?,
+        }
+        Ok(AudioChecksum {
+            format,
+            digest: hasher.finalize().into(),
+        })
+    }
+
+    /// Computes the checksum of the audio payload in the file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<AudioChecksum> {
+        let bytes = std::fs::read(path)?;
+        Self::compute(&bytes)
+    }
+}
+
+impl Display for AudioChecksum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.digest {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the range of the given bytes without the ID3v2 tags at the start and the ID3v1,
+/// APEv2, Lyrics3v2 and appended ID3v2 tags at the end.
+fn untagged_range(bytes: &[u8]) -> Range<usize> {
+    let mut start = 0;
+    while let Some(size) = bytes.get(start..).and_then(id3v2_tag_size) {
+        start = (start + size as usize).min(bytes.len());
+    }
+    let mut end = bytes.len();
+    loop {
+        let tail = &bytes[start..end];
+        let size = if tail.len() >= ID3V1_TAG_SIZE
+            && tail[tail.len() - ID3V1_TAG_SIZE..].starts_with(b"TAG")
+        {
+            ID3V1_TAG_SIZE
+        } else if tail.ends_with(b"LYRICS200") && tail.len() >= LYRICS3_FOOTER_SIZE {
+            let size = &tail[tail.len() - LYRICS3_FOOTER_SIZE..tail.len() - 9];
+            match String::from_utf8_lossy(size).parse::<usize>() {
+                Ok(size) => size + LYRICS3_FOOTER_SIZE,
+                Err(_) => break,
+            }
+        } else if tail.len() >= APE_FOOTER_SIZE
+            && tail[tail.len() - APE_FOOTER_SIZE..].starts_with(b"APETAGEX")
+        {
+            let footer = &tail[tail.len() - APE_FOOTER_SIZE..];
+            let size = u32::from_le_bytes([footer[12], footer[13], footer[14], footer[15]]);
+            let has_header = footer[23] & 0x80 != 0;
+            size as usize + has_header as usize * APE_FOOTER_SIZE
+        } else if tail.len() >= 10 && tail[tail.len() - 10..].starts_with(b"3DI") {
+            let mut footer = tail[tail.len() - 10..].to_vec();
+            footer[0..3].copy_from_slice(b"ID3");
+            match id3v2_tag_size(&footer) {
+                Some(size) => size as usize,
+                None => break,
+            }
+        } else {
+            break;
+        };
+        if size == 0 || size > tail.len() {
+            break;
+        }
+        end -= size;
+    }
+    start..end
+}
+
+/// Hashes the frames after the metadata blocks of a FLAC stream.
+fn hash_flac(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    let mut index = 4;
+    loop {
+        let header = bytes
+            .get(index..index + 4)
+            .ok_or(anyhow!("FLAC metadata block is truncated!"))?;
+        let size = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        index += 4 + size;
+        if header[0] & 0x80 != 0 {
+            break;
+        }
+    }
+    hasher.update(
+        bytes
+            .get(index..)
+            .ok_or(anyhow!("FLAC metadata block is truncated!"))?,
+    );
+    Ok(())
+}
+
+/// Hashes the data of the pages after the header packets of every logical bitstream.
+/// Only the packet data is hashed, since retagging renumbers the pages.
+fn hash_ogg(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    let mut remaining_headers: HashMap<u32, usize> = HashMap::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let page = OggPage::parse(&bytes[index..])?;
+        index += page.total_size();
+        if page.flags.is_beginning_of_stream {
+            // NOTE: sisko only rewrites Opus and Vorbis headers, so other streams are hashed whole
+            let header_count =
+                OggCodec::identify(&page.data).map_or(0, |codec| codec.header_packet_count());
+            remaining_headers.insert(page.serial_number, header_count);
+        }
+        let remaining = remaining_headers.entry(page.serial_number).or_default();
+        if *remaining > 0 {
+            *remaining = remaining.saturating_sub(page.finished_packet_count());
+        } else {
+            hasher.update(&page.data);
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the `mdat` atoms of an MPEG-4 file.
+fn hash_mp4(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    for atom in Mp4Atom::parse_all(bytes)? {
+        if atom.atom_type == "mdat" {
+            hasher.update(&atom.data);
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the sound data chunk of a RIFF or AIFF file.
+fn hash_iff(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    let iff_file = IffFile::parse(bytes)?;
+    let chunk_id = match iff_file.format {
+        IffFormat::Aiff => "SSND",
+        IffFormat::Riff => "data",
+    };
+    let chunk = iff_file
+        .chunks
+        .iter()
+        .find(|chunk| chunk.chunk_id == chunk_id)
+        .ok_or(anyhow!("No {} chunk found!", chunk_id))?;
+    hasher.update(&chunk.data);
+    Ok(())
+}
+
+/// Hashes the objects after the Header Object of an ASF file (the Data and Index Objects).
+fn hash_asf(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    for object in AsfObject::parse_all(bytes)? {
+        if object.guid != HEADER_OBJECT {
+            hasher.update(object.to_bytes());
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the blocks in the clusters of a Matroska file's segments.
+fn hash_matroska(bytes: &[u8], hasher: &mut Sha256) -> Result<()> {
+    for segment in scan(bytes, 0..bytes.len(), &[])? {
+        if segment.id != SEGMENT {
+            continue;
+        }
+        for cluster in scan(bytes, segment.data, &[])? {
+            if cluster.id != CLUSTER {
+                continue;
+            }
+            for block in scan(bytes, cluster.data, &LEVEL_1_IDS)? {
+                if block.id == SIMPLE_BLOCK || block.id == BLOCK_GROUP {
+                    hasher.update(&bytes[block.element]);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Represents the position of an EBML element.
+struct ElementSpan {
+    id: u32,
+    element: Range<usize>,
+    data: Range<usize>,
+}
+
+/// Returns the positions of the consecutive elements in the given range, stopping at any of the
+/// given IDs. An element of unknown size runs until the next element of the same or an outer
+/// level, which for a cluster is the next top-level element.
+fn scan(bytes: &[u8], range: Range<usize>, end_ids: &[u32]) -> Result<Vec<ElementSpan>> {
+    let mut spans: Vec<ElementSpan> = vec![];
+    let mut index = range.start;
+    while index < range.end {
+        let (id, id_length) = read_id(&bytes[index..range.end])?;
+        if end_ids.contains(&id) {
+            break;
+        }
+        let (size, size_length) = read_size(&bytes[index + id_length..range.end])?;
+        let data_start = index + id_length + size_length;
+        let end = match size {
+            Some(size) => (data_start + size as usize).min(range.end),
+            None if id == CLUSTER => {
+                let blocks = scan(bytes, data_start..range.end, &LEVEL_1_IDS)?;
+                blocks.last().map_or(data_start, |block| block.element.end)
+            }
+            None => range.end,
+        };
+        spans.push(ElementSpan {
+            id,
+            element: index..end,
+            data: data_start..end,
+        });
+        index = end;
+    }
+    Ok(spans)
+}
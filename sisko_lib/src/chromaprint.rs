@@ -0,0 +1,298 @@
+use crate::chromaprint_classifier::CLASSIFIERS;
+use crate::chromaprint_image::{ChromaprintImage, BAND_COUNT};
+use crate::chromaprint_resampler::ChromaprintResampler;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::f64::consts::PI;
+
+/// The sample rate Chromaprint analyses audio at.
+pub const SAMPLE_RATE: u32 = 11025;
+
+/// The ID of Chromaprint's default algorithm, which is the one AcoustID uses.
+const ALGORITHM: u8 = 1;
+
+/// The number of samples in each analysed frame.
+const FRAME_SIZE: usize = 4096;
+
+/// The number of samples between the starts of consecutive frames.
+const FRAME_INCREMENT: usize = FRAME_SIZE / 3;
+
+/// The lowest frequency included in the chroma features.
+const MIN_FREQUENCY: f64 = 28.0;
+
+/// The highest frequency included in the chroma features.
+const MAX_FREQUENCY: f64 = 3520.0;
+
+/// The frequency that octaves are counted from: four octaves below A4.
+const BASE_FREQUENCY: f64 = 440.0 / 16.0;
+
+/// The weights of the chroma features of consecutive frames that are summed to smooth them.
+const CHROMA_FILTER_COEFFICIENTS: [f64; 5] = [0.25, 0.75, 1.0, 0.75, 0.25];
+
+/// The norm below which chroma features are taken to be silence.
+const NORMALIZATION_THRESHOLD: f64 = 0.01;
+
+/// The largest gap between set bits that fits in a normal (3-bit) value of a compressed
+/// fingerprint.
+const MAX_NORMAL_VALUE: u32 = 7;
+
+/// Represents a Chromaprint fingerprinter: audio samples go in and a fingerprint comes out.
+/// See [the Chromaprint project](https://acoustid.org/chromaprint) for more detail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Chromaprint {
+    /// The sample rate of the audio.
+    pub sample_rate: u32,
+
+    /// The number of interleaved channels of the audio.
+    pub channel_count: usize,
+
+    /// The audio consumed so far, mixed down to mono.
+    samples: Vec<i16>,
+}
+
+impl Chromaprint {
+    /// Returns a new fingerprinter for audio of the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_rate` - The sample rate of the audio.
+    /// * `channel_count` - The number of interleaved channels of the audio.
+    pub fn new(sample_rate: u32, channel_count: usize) -> Chromaprint {
+        Chromaprint {
+            sample_rate,
+            channel_count: channel_count.max(1),
+            samples: vec![],
+        }
+    }
+
+    /// Consumes the given interleaved samples, mixing them down to mono.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The samples to consume.
+    pub fn consume(&mut self, samples: &[i16]) {
+        let channel_count = self.channel_count;
+        self.samples
+            .extend(samples.chunks_exact(channel_count).map(|frame| {
+                let sum: i32 = frame.iter().map(|sample| *sample as i32).sum();
+                (sum / channel_count as i32) as i16
+            }));
+    }
+
+    /// Returns the number of samples per channel consumed so far.
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns the raw fingerprint of the audio consumed so far: one 32-bit subfingerprint for
+    /// about every eighth of a second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::chromaprint::*;
+    /// let samples: Vec<i16> = (0..SAMPLE_RATE * 10)
+    ///     .map(|i| {
+    ///         let frequency = if i < SAMPLE_RATE * 5 { 440.0 } else { 660.0 };
+    ///         let t = i as f64 / SAMPLE_RATE as f64;
+    ///         (10000.0 * (2.0 * std::f64::consts::PI * frequency * t).sin()) as i16
+    ///     })
+    ///     .collect();
+    /// let mut chromaprint = Chromaprint::new(SAMPLE_RATE, 1);
+    /// chromaprint.consume(&samples);
+    ///
+    /// let fingerprint = chromaprint.fingerprint();
+    ///
+    /// assert_eq!(fingerprint.len(), 59);
+    /// assert_ne!(fingerprint[0], fingerprint[58]);
+    /// assert!(similarity(&fingerprint, &fingerprint) == 1.0);
+    /// ```
+    pub fn fingerprint(&self) -> Vec<u32> {
+        let samples = match self.sample_rate {
+            SAMPLE_RATE => self.samples.clone(),
+            sample_rate => {
+                ChromaprintResampler::new(sample_rate, SAMPLE_RATE).resample(&self.samples)
+            }
+        };
+        let mut image = ChromaprintImage::new();
+        let mut chroma_filter = ChromaFilter::default();
+        for features in chroma_features(&samples) {
+            if let Some(features) = chroma_filter.consume(features) {
+                image.add_row(&normalize(features));
+            }
+        }
+        let max_width = CLASSIFIERS
+            .iter()
+            .map(|classifier| classifier.width)
+            .max()
+            .unwrap_or_default();
+        (0..(image.row_count() + 1).saturating_sub(max_width))
+            .map(|offset| {
+                CLASSIFIERS.iter().fold(0, |bits, classifier| {
+                    (bits << 2) | classifier.classify(&image, offset)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Compresses and encodes the given raw fingerprint the way AcoustID expects it.
+///
+/// # Arguments
+///
+/// * `fingerprint` - The raw fingerprint.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::chromaprint::*;
+/// assert_eq!(encode_fingerprint(&[1]), "AQAAAQE");
+/// assert_eq!(encode_fingerprint(&[7, 7]), "AQAAAkkA");
+/// ```
+pub fn encode_fingerprint(fingerprint: &[u32]) -> String {
+    let mut normal_values = vec![];
+    let mut exceptional_values = vec![];
+    let mut last_subfingerprint = 0;
+    for subfingerprint in fingerprint {
+        let mut x = subfingerprint ^ last_subfingerprint;
+        last_subfingerprint = *subfingerprint;
+        let mut bit = 1;
+        let mut last_bit = 0;
+        while x != 0 {
+            if x & 1 != 0 {
+                let value = bit - last_bit;
+                normal_values.push(value.min(MAX_NORMAL_VALUE));
+                if value >= MAX_NORMAL_VALUE {
+                    exceptional_values.push(value - MAX_NORMAL_VALUE);
+                }
+                last_bit = bit;
+            }
+            x >>= 1;
+            bit += 1;
+        }
+        normal_values.push(0);
+    }
+    let length = fingerprint.len() as u32;
+    let mut bytes = vec![ALGORITHM];
+    bytes.extend(&length.to_be_bytes()[1..]);
+    bytes.extend(pack(&normal_values, 3));
+    bytes.extend(pack(&exceptional_values, 5));
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Returns the fraction of matching bits between the given raw fingerprints, over their common
+/// length. Fingerprints of the same audio from different decoders are usually above 0.9.
+///
+/// # Arguments
+///
+/// * `fingerprint1` - The first raw fingerprint.
+/// * `fingerprint2` - The second raw fingerprint.
+pub fn similarity(fingerprint1: &[u32], fingerprint2: &[u32]) -> f64 {
+    let length = fingerprint1.len().min(fingerprint2.len());
+    if length == 0 {
+        return 0.0;
+    }
+    let differing_bits: u32 = fingerprint1
+        .iter()
+        .zip(fingerprint2)
+        .map(|(subfingerprint1, subfingerprint2)| (subfingerprint1 ^ subfingerprint2).count_ones())
+        .sum();
+    1.0 - differing_bits as f64 / (length * 32) as f64
+}
+
+/// Packs the given values into bytes, least significant bits first.
+fn pack(values: &[u32], bits_per_value: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; (values.len() * bits_per_value).div_ceil(8)];
+    for (index, value) in values.iter().enumerate() {
+        for bit in 0..bits_per_value {
+            if value & (1 << bit) != 0 {
+                let position = index * bits_per_value + bit;
+                bytes[position / 8] |= 1 << (position % 8);
+            }
+        }
+    }
+    bytes
+}
+
+/// Returns the chroma features of every frame of the given samples: the energy of each of the
+/// twelve semitones, summed over all octaves.
+fn chroma_features(samples: &[i16]) -> Vec<[f64; BAND_COUNT]> {
+    let window: Vec<f64> = (0..FRAME_SIZE)
+        .map(|i| {
+            let hamming = 0.54 - 0.46 * (2.0 * PI * i as f64 / (FRAME_SIZE - 1) as f64).cos();
+            hamming / i16::MAX as f64
+        })
+        .collect();
+    let min_index = frequency_to_index(MIN_FREQUENCY).max(1);
+    let max_index = frequency_to_index(MAX_FREQUENCY).min(FRAME_SIZE / 2);
+    let notes: Vec<usize> = (min_index..max_index)
+        .map(|index| {
+            let frequency = index as f64 * SAMPLE_RATE as f64 / FRAME_SIZE as f64;
+            let octave = (frequency / BASE_FREQUENCY).log2();
+            (BAND_COUNT as f64 * (octave - octave.floor())) as usize
+        })
+        .collect();
+    let fft = FftPlanner::<f64>::new().plan_fft_forward(FRAME_SIZE);
+    let mut buffer = vec![Complex::default(); FRAME_SIZE];
+    let mut features = vec![];
+    let mut start = 0;
+    while start + FRAME_SIZE <= samples.len() {
+        for (i, value) in buffer.iter_mut().enumerate() {
+            *value = Complex::new(samples[start + i] as f64 * window[i], 0.0);
+        }
+        fft.process(&mut buffer);
+        let mut frame_features = [0.0; BAND_COUNT];
+        for (index, note) in (min_index..max_index).zip(&notes) {
+            frame_features[*note] += buffer[index].norm_sqr();
+        }
+        features.push(frame_features);
+        start += FRAME_INCREMENT;
+    }
+    features
+}
+
+/// Returns the index of the FFT bin of the given frequency.
+fn frequency_to_index(frequency: f64) -> usize {
+    (FRAME_SIZE as f64 * frequency / SAMPLE_RATE as f64).round() as usize
+}
+
+/// Returns the given features scaled to a unit Euclidean norm, or zeros if they're too quiet.
+fn normalize(features: [f64; BAND_COUNT]) -> [f64; BAND_COUNT] {
+    let norm = features
+        .iter()
+        .map(|feature| feature * feature)
+        .sum::<f64>()
+        .sqrt();
+    match norm < NORMALIZATION_THRESHOLD {
+        true => [0.0; BAND_COUNT],
+        false => features.map(|feature| feature / norm),
+    }
+}
+
+/// Smooths chroma features over time with a weighted sum of consecutive frames.
+#[derive(Default)]
+struct ChromaFilter {
+    buffer: Vec<[f64; BAND_COUNT]>,
+}
+
+impl ChromaFilter {
+    /// Consumes the features of the next frame and returns the smoothed features, once enough
+    /// frames have been consumed.
+    fn consume(&mut self, features: [f64; BAND_COUNT]) -> Option<[f64; BAND_COUNT]> {
+        self.buffer.push(features);
+        if self.buffer.len() > CHROMA_FILTER_COEFFICIENTS.len() {
+            self.buffer.remove(0);
+        } else if self.buffer.len() < CHROMA_FILTER_COEFFICIENTS.len() {
+            return None;
+        }
+        let mut result = [0.0; BAND_COUNT];
+        for (frame, coefficient) in self.buffer.iter().zip(CHROMA_FILTER_COEFFICIENTS) {
+            for (band, feature) in frame.iter().enumerate() {
+                result[band] += feature * coefficient;
+            }
+        }
+        Some(result)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::chromaprint_image::ChromaprintImage;
+
+/// The classifiers of Chromaprint's default algorithm, one for each pair of bits of a
+/// subfingerprint.
+pub const CLASSIFIERS: [ChromaprintClassifier; 16] = [
+    ChromaprintClassifier::new(0, 4, 3, 15, [1.98215, 2.35817, 2.63523]),
+    ChromaprintClassifier::new(4, 4, 6, 15, [-1.03809, -0.651211, -0.282167]),
+    ChromaprintClassifier::new(1, 0, 4, 16, [-0.298702, 0.119262, 0.558497]),
+    ChromaprintClassifier::new(3, 8, 2, 12, [-0.105439, 0.0153946, 0.135898]),
+    ChromaprintClassifier::new(3, 4, 4, 8, [-0.142891, 0.0258736, 0.200632]),
+    ChromaprintClassifier::new(4, 0, 3, 5, [-0.826319, -0.590612, -0.368214]),
+    ChromaprintClassifier::new(1, 2, 2, 9, [-0.557409, -0.233035, 0.0534525]),
+    ChromaprintClassifier::new(2, 7, 3, 4, [-0.0646826, 0.00620476, 0.0784847]),
+    ChromaprintClassifier::new(2, 6, 2, 16, [-0.192387, -0.029699, 0.215855]),
+    ChromaprintClassifier::new(2, 1, 3, 2, [-0.0397818, -0.00568076, 0.0292026]),
+    ChromaprintClassifier::new(5, 10, 1, 15, [-0.53823, -0.369934, -0.190235]),
+    ChromaprintClassifier::new(3, 6, 2, 10, [-0.124877, 0.0296483, 0.139239]),
+    ChromaprintClassifier::new(2, 1, 1, 14, [-0.101475, 0.0225617, 0.231971]),
+    ChromaprintClassifier::new(3, 5, 6, 4, [-0.0799915, -0.00729616, 0.063262]),
+    ChromaprintClassifier::new(1, 9, 2, 12, [-0.272556, 0.019424, 0.302559]),
+    ChromaprintClassifier::new(3, 4, 2, 14, [-0.164292, -0.0321188, 0.0846339]),
+];
+
+/// Represents a filter over a rectangle of the chroma image and the thresholds that quantize its
+/// response into two bits of a subfingerprint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChromaprintClassifier {
+    /// The shape of the filter, from 0 to 5.
+    pub filter_type: u8,
+
+    /// The first chroma band of the filter.
+    pub y: usize,
+
+    /// The number of chroma bands of the filter.
+    pub height: usize,
+
+    /// The number of rows (frames) of the filter.
+    pub width: usize,
+
+    /// The thresholds between the four quantized values.
+    pub thresholds: [f64; 3],
+}
+
+impl ChromaprintClassifier {
+    /// Returns a new classifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter_type` - The shape of the filter, from 0 to 5.
+    /// * `y` - The first chroma band of the filter.
+    /// * `height` - The number of chroma bands of the filter.
+    /// * `width` - The number of rows (frames) of the filter.
+    /// * `thresholds` - The thresholds between the four quantized values.
+    pub const fn new(
+        filter_type: u8,
+        y: usize,
+        height: usize,
+        width: usize,
+        thresholds: [f64; 3],
+    ) -> ChromaprintClassifier {
+        ChromaprintClassifier {
+            filter_type,
+            y,
+            height,
+            width,
+            thresholds,
+        }
+    }
+
+    /// Classifies the rectangle of the image starting at the given row as a Gray-coded value
+    /// from 0 to 3.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The chroma image.
+    /// * `x` - The first row of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::chromaprint_classifier::*;
+    /// # use sisko_lib::chromaprint_image::*;
+    /// let mut image = ChromaprintImage::new();
+    /// image.add_row(&[0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    /// let classifier = ChromaprintClassifier::new(0, 4, 3, 1, [0.5, 1.0, 1.5]);
+    ///
+    /// // log(1 + 3) is about 1.39, which is between the second and third thresholds
+    /// assert_eq!(classifier.classify(&image, 0), 3);
+    /// ```
+    pub fn classify(&self, image: &ChromaprintImage, x: usize) -> u32 {
+        let value = self.apply(image, x);
+        let quantized = match value < self.thresholds[1] {
+            true => match value < self.thresholds[0] {
+                true => 0,
+                false => 1,
+            },
+            false => match value < self.thresholds[2] {
+                true => 2,
+                false => 3,
+            },
+        };
+        // NOTE: Gray code, so that neighbouring values differ by one bit
+        [0, 1, 3, 2][quantized]
+    }
+
+    /// Returns the response of the filter to the rectangle of the image starting at the given
+    /// row: the log ratio of the sums of its light and dark areas.
+    fn apply(&self, image: &ChromaprintImage, x: usize) -> f64 {
+        let (y, w, h) = (self.y, self.width, self.height);
+        let area = |x1: usize, y1: usize, x2: usize, y2: usize| image.area(x1, y1, x2, y2);
+        let (a, b) = match self.filter_type {
+            0 => (area(x, y, x + w, y + h), 0.0),
+            1 => {
+                let h_2 = h / 2;
+                (area(x, y + h_2, x + w, y + h), area(x, y, x + w, y + h_2))
+            }
+            2 => {
+                let w_2 = w / 2;
+                (area(x + w_2, y, x + w, y + h), area(x, y, x + w_2, y + h))
+            }
+            3 => {
+                let (w_2, h_2) = (w / 2, h / 2);
+                (
+                    area(x, y + h_2, x + w_2, y + h) + area(x + w_2, y, x + w, y + h_2),
+                    area(x, y, x + w_2, y + h_2) + area(x + w_2, y + h_2, x + w, y + h),
+                )
+            }
+            4 => {
+                let h_3 = h / 3;
+                (
+                    area(x, y + h_3, x + w, y + 2 * h_3),
+                    area(x, y, x + w, y + h_3) + area(x, y + 2 * h_3, x + w, y + h),
+                )
+            }
+            _ => {
+                let w_3 = w / 3;
+                (
+                    area(x + w_3, y, x + 2 * w_3, y + h),
+                    area(x, y, x + w_3, y + h) + area(x + 2 * w_3, y, x + w, y + h),
+                )
+            }
+        };
+        (1.0 + a).ln() - (1.0 + b).ln()
+    }
+}
@@ -0,0 +1,68 @@
+/// The number of chroma bands in each row of the image.
+pub const BAND_COUNT: usize = 12;
+
+/// Represents the chroma features of a stream over time as an integral image, so that the sum of
+/// any rectangle of it can be found in constant time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChromaprintImage {
+    /// The running sums of the rows, with a leading row and column of zeros.
+    sums: Vec<[f64; BAND_COUNT + 1]>,
+}
+
+impl ChromaprintImage {
+    /// Returns a new image with no rows.
+    pub fn new() -> ChromaprintImage {
+        ChromaprintImage {
+            sums: vec![[0.0; BAND_COUNT + 1]],
+        }
+    }
+
+    /// Adds a row of chroma features to the bottom of the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - The chroma features of the row.
+    pub fn add_row(&mut self, features: &[f64; BAND_COUNT]) {
+        let last = self.sums[self.sums.len() - 1];
+        let mut row = [0.0; BAND_COUNT + 1];
+        let mut row_sum = 0.0;
+        for (band, feature) in features.iter().enumerate() {
+            row_sum += feature;
+            row[band + 1] = last[band + 1] + row_sum;
+        }
+        self.sums.push(row);
+    }
+
+    /// Returns the number of rows in the image.
+    pub fn row_count(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    /// Returns the sum of the rectangle from the first row and column up to, but not including,
+    /// the second row and column.
+    ///
+    /// # Arguments
+    ///
+    /// * `row1` - The first row of the rectangle.
+    /// * `column1` - The first column of the rectangle.
+    /// * `row2` - The row after the last row of the rectangle.
+    /// * `column2` - The column after the last column of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::chromaprint_image::*;
+    /// let mut image = ChromaprintImage::new();
+    /// image.add_row(&[1.0; BAND_COUNT]);
+    /// image.add_row(&[2.0; BAND_COUNT]);
+    ///
+    /// assert_eq!(image.row_count(), 2);
+    /// assert_eq!(image.area(0, 0, 2, 12), 36.0);
+    /// assert_eq!(image.area(1, 3, 2, 5), 4.0);
+    /// assert_eq!(image.area(1, 3, 1, 5), 0.0);
+    /// ```
+    pub fn area(&self, row1: usize, column1: usize, row2: usize, column2: usize) -> f64 {
+        self.sums[row2][column2] - self.sums[row1][column2] - self.sums[row2][column1]
+            + self.sums[row1][column1]
+    }
+}
@@ -0,0 +1,156 @@
+use std::f64::consts::PI;
+
+/// The number of fractional bits of the filter coefficients.
+const FILTER_SHIFT: u32 = 15;
+
+/// The length of the filter at the output sample rate.
+const FILTER_SIZE: f64 = 16.0;
+
+/// The number of bits of the filter phase, i.e. 1024 phases between input samples.
+const PHASE_SHIFT: u32 = 10;
+
+/// The cutoff frequency of the filter, relative to the output's Nyquist frequency.
+const CUTOFF: f64 = 0.8;
+
+/// The beta of the Kaiser window applied to the filter.
+const KAISER_BETA: f64 = 9.0;
+
+/// Represents the polyphase resampler Chromaprint uses to convert audio to its sample rate.
+/// This is a port of the `av_resample` filter that Chromaprint bundles from FFmpeg.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChromaprintResampler {
+    /// The filter coefficients of every phase, one after another.
+    filter_bank: Vec<i16>,
+
+    /// The number of coefficients in each phase of the filter.
+    filter_length: usize,
+
+    /// The output sample rate.
+    src_incr: i64,
+
+    /// The input sample rate, in phases.
+    dst_incr: i64,
+}
+
+impl ChromaprintResampler {
+    /// Returns a new resampler between the given sample rates.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_rate` - The sample rate of the audio to resample.
+    /// * `output_rate` - The sample rate to resample the audio to.
+    pub fn new(input_rate: u32, output_rate: u32) -> ChromaprintResampler {
+        let phase_count = 1usize << PHASE_SHIFT;
+        let factor = (output_rate as f64 * CUTOFF / input_rate as f64).min(1.0);
+        let filter_length = ((FILTER_SIZE / factor).ceil() as usize).max(1);
+        let center = (filter_length - 1) / 2;
+        let scale = (1 << FILTER_SHIFT) as f64;
+        let mut filter_bank = Vec::with_capacity(phase_count * filter_length);
+        for phase in 0..phase_count {
+            let taps: Vec<f64> = (0..filter_length)
+                .map(|i| {
+                    let x = PI
+                        * ((i as f64 - center as f64) - phase as f64 / phase_count as f64)
+                        * factor;
+                    let sinc = match x == 0.0 {
+                        true => 1.0,
+                        false => x.sin() / x,
+                    };
+                    let w = 2.0 * x / (factor * filter_length as f64 * PI);
+                    sinc * bessel(KAISER_BETA * (1.0 - w * w).max(0.0).sqrt())
+                })
+                .collect();
+            let norm: f64 = taps.iter().sum();
+            filter_bank.extend(taps.iter().map(|tap| {
+                (tap * scale / norm)
+                    .round()
+                    .clamp(i16::MIN as f64, i16::MAX as f64) as i16
+            }));
+        }
+        ChromaprintResampler {
+            filter_bank,
+            filter_length,
+            src_incr: output_rate as i64,
+            dst_incr: input_rate as i64 * phase_count as i64,
+        }
+    }
+
+    /// Resamples the given mono samples.
+    /// The samples at the end that don't fill the filter are dropped, as Chromaprint does.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The samples to resample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::chromaprint_resampler::*;
+    /// let resampler = ChromaprintResampler::new(44100, 11025);
+    ///
+    /// let resampled = resampler.resample(&vec![1000; 44100]);
+    ///
+    /// assert!(resampled.len() > 11000 && resampled.len() <= 11025);
+    /// assert!(resampled.iter().all(|sample| (*sample - 1000).abs() <= 1));
+    /// ```
+    pub fn resample(&self, samples: &[i16]) -> Vec<i16> {
+        let phase_mask = (1i64 << PHASE_SHIFT) - 1;
+        let dst_incr_frac = self.dst_incr % self.src_incr;
+        let dst_incr = self.dst_incr / self.src_incr;
+        let mut index = -(1i64 << PHASE_SHIFT) * ((self.filter_length as i64 - 1) / 2);
+        let mut frac = 0;
+        let mut resampled = vec![];
+        if samples.is_empty() {
+            return resampled;
+        }
+        loop {
+            let phase = (index & phase_mask) as usize;
+            let filter =
+                &self.filter_bank[phase * self.filter_length..(phase + 1) * self.filter_length];
+            let sample_index = index >> PHASE_SHIFT;
+            let value: i64 = if sample_index < 0 {
+                filter
+                    .iter()
+                    .enumerate()
+                    .map(|(i, coefficient)| {
+                        let i = (sample_index + i as i64).unsigned_abs() as usize % samples.len();
+                        samples[i] as i64 * *coefficient as i64
+                    })
+                    .sum()
+            } else if sample_index as usize + self.filter_length > samples.len() {
+                break;
+            } else {
+                samples[sample_index as usize..]
+                    .iter()
+                    .zip(filter)
+                    .map(|(sample, coefficient)| *sample as i64 * *coefficient as i64)
+                    .sum()
+            };
+            let value = (value + (1 << (FILTER_SHIFT - 1))) >> FILTER_SHIFT;
+            resampled.push(value.clamp(i16::MIN as i64, i16::MAX as i64) as i16);
+            frac += dst_incr_frac;
+            index += dst_incr;
+            if frac >= self.src_incr {
+                frac -= self.src_incr;
+                index += 1;
+            }
+        }
+        resampled
+    }
+}
+
+/// Returns the zeroth-order modified Bessel function of the first kind at the given value.
+fn bessel(x: f64) -> f64 {
+    let x = x * x / 4.0;
+    let mut value = 1.0;
+    let mut last_value = 0.0;
+    let mut term = 1.0;
+    let mut i = 1.0;
+    while value != last_value {
+        last_value = value;
+        term *= x / (i * i);
+        value += term;
+        i += 1.0;
+    }
+    value
+}
@@ -0,0 +1,361 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use encoding_rs::WINDOWS_1252;
+use sha1::{Digest, Sha1};
+
+/// The sectors before the first track of every disc, which offsets include.
+pub const PREGAP_SECTORS: u32 = 150;
+
+/// The number of sectors in a second of CD audio.
+pub const SECTORS_PER_SECOND: u32 = 75;
+
+/// The sectors between the last audio track and a data track of an enhanced CD.
+const DATA_TRACK_GAP_SECTORS: u32 = 11400;
+
+/// The most tracks a disc can have.
+const MAX_TRACKS: usize = 99;
+
+/// Represents the table of contents of an audio CD, as used to compute its MusicBrainz disc ID.
+/// All offsets are in sectors and include the pregap of the disc.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscToc {
+    /// The number of the first track.
+    pub first_track: u32,
+
+    /// The number of the last audio track.
+    pub last_track: u32,
+
+    /// The offset of the end of the last audio track.
+    pub lead_out: u32,
+
+    /// The offset of each track, from the first to the last.
+    pub offsets: Vec<u32>,
+}
+
+impl DiscToc {
+    /// Creates a table of contents, checking that the offsets are in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_track` - The number of the first track.
+    /// * `lead_out` - The offset of the end of the last audio track.
+    /// * `offsets` - The offset of each track.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let toc = DiscToc::new(1, 242457, vec![150, 44942, 61305, 72755, 96360, 130485])?;
+    ///
+    /// assert_eq!(toc.last_track, 6);
+    /// assert!(DiscToc::new(1, 100, vec![150]).is_err());
+    /// assert!(DiscToc::new(1, 242457, vec![]).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn new(first_track: u32, lead_out: u32, offsets: Vec<u32>) -> Result<DiscToc> {
+        if offsets.is_empty() || offsets.len() > MAX_TRACKS {
+            return Err(anyhow!("A disc has {} tracks!", offsets.len()));
+        }
+        if !offsets.windows(2).all(|pair| pair[0] < pair[1])
+            || offsets.last().is_some_and(|last| *last >= lead_out)
+        {
+            return Err(anyhow!("The track offsets of the disc are out of order!"));
+        }
+        Ok(DiscToc {
+            first_track,
+            last_track: first_track + offsets.len() as u32 - 1,
+            lead_out,
+            offsets,
+        })
+    }
+
+    /// Parses the table of contents of a cue sheet.
+    /// Cue sheets don't give the lead-out, so the lengths of the files they refer to are needed,
+    /// in the order of [`DiscToc::cue_sheet_files`].
+    /// Only the audio tracks are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `cue_sheet` - The text of the cue sheet.
+    /// * `file_sectors` - The length in sectors of each file the cue sheet refers to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let cue_sheet = "FILE \"disc.flac\" WAVE\n\
+    ///     \x20 TRACK 01 AUDIO\n\
+    ///     \x20   INDEX 01 00:00:00\n\
+    ///     \x20 TRACK 02 AUDIO\n\
+    ///     \x20   INDEX 00 09:55:00\n\
+    ///     \x20   INDEX 01 09:57:17\n";
+    ///
+    /// let toc = DiscToc::from_cue_sheet(cue_sheet, &[90000])?;
+    ///
+    /// assert_eq!(toc.offsets, vec![150, 44942]);
+    /// assert_eq!(toc.lead_out, 90150);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_cue_sheet(cue_sheet: &str, file_sectors: &[u32]) -> Result<DiscToc> {
+        let mut file_count = 0;
+        let mut file_start = None;
+        let mut track: Option<(u32, bool)> = None;
+        let mut tracks: Vec<(u32, u32)> = vec![];
+        for line in cue_sheet.lines() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("FILE") => {
+                    file_start = Some(cue_file_start(file_sectors, file_count)?);
+                    file_count += 1;
+                }
+                Some("TRACK") => {
+                    let number = words
+                        .next()
+                        .and_then(|number| number.parse::<u32>().ok())
+                        .ok_or_else(|| anyhow!("Invalid track in cue sheet: {}", line.trim()))?;
+                    track = Some((number, words.next() == Some("AUDIO")));
+                }
+                Some("INDEX") if words.next() == Some("01") => {
+                    let (number, is_audio) =
+                        track.ok_or_else(|| anyhow!("Index outside a track in cue sheet!"))?;
+                    let start = file_start.ok_or_else(|| {
+                        anyhow!("Track {} is outside a file in cue sheet!", number)
+                    })?;
+                    let time = words
+                        .next()
+                        .and_then(parse_cue_time)
+                        .ok_or_else(|| anyhow!("Invalid index in cue sheet: {}", line.trim()))?;
+                    if is_audio {
+                        tracks.push((number, start + time));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let total_sectors = cue_file_start(file_sectors, file_count)?;
+        let first_track = tracks
+            .first()
+            .map(|(number, _)| *number)
+            .ok_or_else(|| anyhow!("No audio tracks found in cue sheet!"))?;
+        DiscToc::new(
+            first_track,
+            total_sectors + PREGAP_SECTORS,
+            tracks
+                .into_iter()
+                .map(|(_, offset)| offset + PREGAP_SECTORS)
+                .collect(),
+        )
+    }
+
+    /// Returns the names of the files a cue sheet refers to, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `cue_sheet` - The text of the cue sheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let cue_sheet = "FILE \"01 One.flac\" WAVE\n  TRACK 01 AUDIO\nFILE two.flac WAVE\n";
+    ///
+    /// assert_eq!(DiscToc::cue_sheet_files(cue_sheet), vec!["01 One.flac", "two.flac"]);
+    /// ```
+    pub fn cue_sheet_files(cue_sheet: &str) -> Vec<String> {
+        cue_sheet
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("FILE "))
+            .map(|rest| match rest.strip_prefix('"') {
+                Some(quoted) => quoted.split('"').next().unwrap_or_default().to_owned(),
+                None => rest
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned(),
+            })
+            .collect()
+    }
+
+    /// Parses the table of contents of an EAC or XLD rip log.
+    /// A data track at the end of an enhanced CD is left out, as MusicBrainz does.
+    ///
+    /// # Arguments
+    ///
+    /// * `log` - The text of the rip log.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let log = "TOC of the extracted CD\n\n\
+    ///     \x20    Track |   Start  |  Length  | Start sector | End sector \n\
+    ///     \x20   ---------------------------------------------------------\n\
+    ///     \x20      1  |  0:00.00 |  9:57.17 |         0    |    44791   \n\
+    ///     \x20      2  |  9:57.17 |  3:38.13 |     44792    |    61154   \n\
+    ///     \x20      3  | 16:12.32 |  5:00.00 |     72555    |    95054   \n";
+    ///
+    /// let toc = DiscToc::from_rip_log(log)?;
+    ///
+    /// assert_eq!(toc.offsets, vec![150, 44942]);
+    /// assert_eq!(toc.lead_out, 61305);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_rip_log(log: &str) -> Result<DiscToc> {
+        let mut tracks: Vec<(u32, u32, u32)> = vec![];
+        for line in log.lines() {
+            match parse_log_track(line) {
+                Some(track) => tracks.push(track),
+                None if !tracks.is_empty() => break,
+                None => {}
+            }
+        }
+        if tracks.len() > 1 {
+            let (_, _, previous_end) = tracks[tracks.len() - 2];
+            let (_, start, _) = tracks[tracks.len() - 1];
+            if start == previous_end + 1 + DATA_TRACK_GAP_SECTORS {
+                tracks.pop();
+            }
+        }
+        let (first_track, _, _) = tracks
+            .first()
+            .ok_or_else(|| anyhow!("No table of contents found in rip log!"))?;
+        let (_, _, last_end) = tracks[tracks.len() - 1];
+        DiscToc::new(
+            *first_track,
+            last_end + 1 + PREGAP_SECTORS,
+            tracks
+                .iter()
+                .map(|(_, start, _)| start + PREGAP_SECTORS)
+                .collect(),
+        )
+    }
+
+    /// Returns the MusicBrainz disc ID of the table of contents: the SHA-1 digest of the first
+    /// and last track numbers, the lead-out and the offsets of 99 tracks, as uppercase hex,
+    /// base64 encoded with the characters that aren't safe in URLs replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let toc = DiscToc::new(1, 242457, vec![150, 44942, 61305, 72755, 96360, 130485])?;
+    ///
+    /// assert_eq!(toc.disc_id(), "7lAd6g1Tu2xcmnh41d6rlq1cTAs-");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn disc_id(&self) -> String {
+        let mut text = format!(
+            "{:02X}{:02X}{:08X}",
+            self.first_track, self.last_track, self.lead_out
+        );
+        for index in 0..MAX_TRACKS {
+            text.push_str(&format!("{:08X}", self.offsets.get(index).unwrap_or(&0)));
+        }
+        STANDARD
+            .encode(Sha1::digest(text.as_bytes()))
+            .replace('+', ".")
+            .replace('/', "_")
+            .replace('=', "-")
+    }
+
+    /// Returns the table of contents as MusicBrainz takes it to look up discs: the first and
+    /// last track numbers, the lead-out and the track offsets, separated by spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::disc_toc::*;
+    /// let toc = DiscToc::new(1, 61305, vec![150, 44942])?;
+    ///
+    /// assert_eq!(toc.toc(), "1 2 61305 150 44942");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn toc(&self) -> String {
+        let mut values = vec![self.first_track, self.last_track, self.lead_out];
+        values.extend(&self.offsets);
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+/// Decodes the text of a cue sheet or rip log. EAC writes its logs in UTF-16 and older tools
+/// write cue sheets in Windows-1252, so those are decoded too.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes of the file.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::disc_toc::*;
+/// assert_eq!(decode_rip_text(&[0xFF, 0xFE, b'E', 0, b'A', 0, b'C', 0]), "EAC");
+/// assert_eq!(decode_rip_text(b"\xEF\xBB\xBFXLD"), "XLD");
+/// assert_eq!(decode_rip_text(b"Caf\xE9"), "Café");
+/// ```
+pub fn decode_rip_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_owned(),
+        Err(_) => WINDOWS_1252.decode(bytes).0.into_owned(),
+    }
+}
+
+/// Returns the sector the given file of a cue sheet starts at, which is the sum of the lengths
+/// of the files before it.
+///
+/// # Arguments
+///
+/// * `file_sectors` - The length in sectors of each file the cue sheet refers to.
+/// * `file_index` - The index of the file to find the start of.
+fn cue_file_start(file_sectors: &[u32], file_index: usize) -> Result<u32> {
+    match file_sectors.get(..file_index) {
+        Some(previous) => Ok(previous.iter().sum()),
+        None => Err(anyhow!(
+            "No length given for file {} of the cue sheet!",
+            file_index
+        )),
+    }
+}
+
+/// Parses a cue sheet time of minutes, seconds and frames into sectors.
+///
+/// # Arguments
+///
+/// * `time` - The time to parse, as "mm:ss:ff".
+fn parse_cue_time(time: &str) -> Option<u32> {
+    let parts: Vec<u32> = time
+        .split(':')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    match parts[..] {
+        [minutes, seconds, frames] => Some((minutes * 60 + seconds) * SECTORS_PER_SECOND + frames),
+        _ => None,
+    }
+}
+
+/// Parses a row of the table of contents of a rip log into the track number and its start and
+/// end sectors.
+///
+/// # Arguments
+///
+/// * `line` - The line of the log to parse.
+fn parse_log_track(line: &str) -> Option<(u32, u32, u32)> {
+    let fields: Vec<&str> = line.split('|').map(|field| field.trim()).collect();
+    match fields[..] {
+        [number, _, _, start, end] => {
+            Some((number.parse().ok()?, start.parse().ok()?, end.parse().ok()?))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::{anyhow, Result};
+
+/// Represents an element in an EBML (Matroska/WebM) file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EbmlElement {
+    /// The ID of the element, including its length marker (e.g. `0x1254C367` for `Tags`).
+    pub id: u32,
+
+    /// The data of the element.
+    pub data: Vec<u8>,
+}
+
+impl EbmlElement {
+    /// Returns a new element with the given ID and data.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the element.
+    /// * `data` - The data of the element.
+    pub fn new(id: u32, data: Vec<u8>) -> EbmlElement {
+        EbmlElement { id, data }
+    }
+
+    /// Returns a new master element with the given ID and child elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the element.
+    /// * `children` - The child elements.
+    pub fn new_master(id: u32, children: &[EbmlElement]) -> EbmlElement {
+        let data = children.iter().flat_map(|child| child.to_bytes()).collect();
+        EbmlElement { id, data }
+    }
+
+    /// Returns a new element holding the given unsigned integer in as few bytes as possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the element.
+    /// * `value` - The unsigned integer to hold.
+    pub fn new_uint(id: u32, value: u64) -> EbmlElement {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(7);
+        EbmlElement::new(id, bytes[start..].to_vec())
+    }
+
+    /// Returns a new element holding the given UTF-8 string.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the element.
+    /// * `value` - The string to hold.
+    pub fn new_string(id: u32, value: &str) -> EbmlElement {
+        EbmlElement::new(id, value.as_bytes().to_vec())
+    }
+
+    /// Parses an element from the start of the given bytes.
+    /// Returns the element and the number of bytes it took up.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the element from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// let bytes = [0x45, 0xA3, 0x84, b'N', b'A', b'M', b'E'];
+    ///
+    /// let (element, size) = EbmlElement::parse(&bytes)?;
+    ///
+    /// assert_eq!(element.id, 0x45A3);
+    /// assert_eq!(element.string(), "NAME");
+    /// assert_eq!(size, 7);
+    /// assert_eq!(element.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(EbmlElement, usize)> {
+        let (id, id_length) = read_id(bytes)?;
+        let (size, size_length) = read_size(&bytes[id_length..])?;
+        let size = size.ok_or(anyhow!("EBML element {:X} has an unknown size!", id))? as usize;
+        let data_start = id_length + size_length;
+        let data = bytes
+            .get(data_start..data_start + size)
+            .ok_or(anyhow!("EBML element {:X} is truncated!", id))?
+            .to_vec();
+        Ok((EbmlElement { id, data }, data_start + size))
+    }
+
+    /// Parses all of the consecutive elements in the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the elements from.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<EbmlElement>> {
+        let mut elements = vec![];
+        let mut index = 0;
+        while index < bytes.len() {
+            let (element, size) = Self::parse(&bytes[index..])?;
+            elements.push(element);
+            index += size;
+        }
+        Ok(elements)
+    }
+
+    /// Parses the data of this master element as its child elements.
+    pub fn children(&self) -> Result<Vec<EbmlElement>> {
+        Self::parse_all(&self.data)
+    }
+
+    /// Returns the data of this element as a big-endian unsigned integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// let element = EbmlElement::new_uint(0x68CA, 300);
+    ///
+    /// assert_eq!(element.data, vec![1, 44]);
+    /// assert_eq!(element.uint(), 300);
+    /// ```
+    pub fn uint(&self) -> u64 {
+        self.data
+            .iter()
+            .fold(0, |value, byte| (value << 8) | *byte as u64)
+    }
+
+    /// Returns the data of this element as a UTF-8 string, dropping any null padding.
+    pub fn string(&self) -> String {
+        String::from_utf8_lossy(&self.data)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+
+    /// Returns the total size of this element in bytes, including its ID and size.
+    pub fn total_size(&self) -> usize {
+        encode_id(self.id).len() + encode_size(self.data.len() as u64).len() + self.data.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut element_bytes: Vec<u8> = vec![];
+        element_bytes.extend(encode_id(self.id));
+        element_bytes.extend(encode_size(self.data.len() as u64));
+        element_bytes.extend(&self.data);
+        element_bytes
+    }
+}
+
+/// Reads an element ID from the start of the given bytes, keeping its length marker.
+/// Returns the ID and the number of bytes it took up.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to read the ID from.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::ebml_element::*;
+/// assert_eq!(read_id(&[0x1A, 0x45, 0xDF, 0xA3])?, (0x1A45DFA3, 4));
+/// assert_eq!(read_id(&[0xEC])?, (0xEC, 1));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn read_id(bytes: &[u8]) -> Result<(u32, usize)> {
+    let length = vint_length(bytes)?;
+    if length > 4 {
+        return Err(anyhow!("EBML element ID is too long!"));
+    }
+    let id = bytes[0..length]
+        .iter()
+        .fold(0, |id, byte| (id << 8) | *byte as u32);
+    Ok((id, length))
+}
+
+/// Reads an element size from the start of the given bytes.
+/// Returns the size, or `None` if it is unknown, and the number of bytes it took up.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to read the size from.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::ebml_element::*;
+/// assert_eq!(read_size(&[0x81])?, (Some(1), 1));
+/// assert_eq!(read_size(&[0x40, 0x02])?, (Some(2), 2));
+/// assert_eq!(read_size(&[0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])?, (None, 8));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn read_size(bytes: &[u8]) -> Result<(Option<u64>, usize)> {
+    let length = vint_length(bytes)?;
+    let value = bytes[1..length].iter().fold(
+        bytes[0] as u64 & ((1 << (8 - length)) - 1),
+        |value, byte| (value << 8) | *byte as u64,
+    );
+    let unknown = (1u64 << (7 * length)) - 1;
+    Ok(((value != unknown).then_some(value), length))
+}
+
+/// Encodes an element ID, which already includes its length marker.
+///
+/// # Arguments
+///
+/// * `id` - The ID to encode.
+pub fn encode_id(id: u32) -> Vec<u8> {
+    let bytes = id.to_be_bytes();
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(3);
+    bytes[start..].to_vec()
+}
+
+/// Encodes an element size in as few bytes as possible.
+///
+/// # Arguments
+///
+/// * `size` - The size to encode.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::ebml_element::*;
+/// assert_eq!(encode_size(1), vec![0x81]);
+/// assert_eq!(encode_size(127), vec![0x40, 0x7F]);
+/// ```
+pub fn encode_size(size: u64) -> Vec<u8> {
+    let length = (1..=8)
+        .find(|length| size < (1u64 << (7 * length)) - 1)
+        .unwrap_or(8);
+    encode_size_with_length(size, length).unwrap_or_default()
+}
+
+/// Encodes an element size in exactly the given number of bytes.
+///
+/// # Arguments
+///
+/// * `size` - The size to encode.
+/// * `length` - The number of bytes to encode the size in, from 1 to 8.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::ebml_element::*;
+/// assert_eq!(encode_size_with_length(1, 2)?, vec![0x40, 0x01]);
+/// assert!(encode_size_with_length(127, 1).is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn encode_size_with_length(size: u64, length: usize) -> Result<Vec<u8>> {
+    if !(1..=8).contains(&length) || size >= (1u64 << (7 * length)) - 1 {
+        return Err(anyhow!(
+            "EBML size {} doesn't fit in {} bytes!",
+            size,
+            length
+        ));
+    }
+    let marked = size | (1u64 << (7 * length));
+    Ok(marked.to_be_bytes()[8 - length..].to_vec())
+}
+
+/// Returns the number of bytes taken up by the variable-length integer at the start of the given bytes.
+fn vint_length(bytes: &[u8]) -> Result<usize> {
+    let first = bytes
+        .first()
+        .ok_or(anyhow!("EBML variable-length integer is truncated!"))?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > 8 {
+        return Err(anyhow!("EBML variable-length integer is invalid!"));
+    }
+    if bytes.len() < length {
+        return Err(anyhow!("EBML variable-length integer is truncated!"));
+    }
+    Ok(length)
+}
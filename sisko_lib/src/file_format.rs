@@ -0,0 +1,127 @@
+use crate::asf_object::HEADER_OBJECT;
+use crate::id3v2_tag_size;
+use crate::matroska_element_id::EBML;
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// The number of bytes needed to identify every format.
+const SIGNATURE_SIZE: usize = 16;
+
+/// Represents the audio file formats that can be identified from their content.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FileFormat {
+    /// An ADTS stream of AAC audio.
+    Aac,
+
+    /// An AIFF or AIFF-C file.
+    Aiff,
+
+    /// An ASF (WMA/WMV) file.
+    Asf,
+
+    /// A FLAC file.
+    Flac,
+
+    /// A Matroska (MKA/MKV/WebM) file.
+    Matroska,
+
+    /// An MPEG audio (e.g. MP3) stream.
+    Mpeg,
+
+    /// An MPEG-4 (e.g. M4A) file.
+    Mp4,
+
+    /// An Ogg file.
+    Ogg,
+
+    /// A RIFF WAVE file.
+    Wav,
+}
+
+impl FileFormat {
+    /// Identifies the format of a file from its first bytes, after any ID3v2 tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The first 16 bytes of the file's content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::file_format::*;
+    /// assert_eq!(FileFormat::identify(b"fLaC\0\0\0\x22")?, FileFormat::Flac);
+    /// assert_eq!(FileFormat::identify(b"\0\0\0\x20ftypM4A ")?, FileFormat::Mp4);
+    /// assert_eq!(FileFormat::identify(b"RIFF\x24\0\0\0WAVE")?, FileFormat::Wav);
+    /// assert_eq!(FileFormat::identify(&[0xFF, 0xFB, 0x90, 0x64])?, FileFormat::Mpeg);
+    /// assert_eq!(FileFormat::identify(&[0xFF, 0xF1, 0x50, 0x80])?, FileFormat::Aac);
+    /// assert!(FileFormat::identify(b"RIFF\x24\0\0\0AVI ").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn identify(bytes: &[u8]) -> Result<FileFormat> {
+        let form_type = bytes.get(8..12);
+        match bytes {
+            [b'f', b'L', b'a', b'C', ..] => Ok(FileFormat::Flac),
+            [b'O', b'g', b'g', b'S', ..] => Ok(FileFormat::Ogg),
+            [_, _, _, _, b'f', b't', b'y', b'p', ..] => Ok(FileFormat::Mp4),
+            [b'R', b'I', b'F', b'F', ..] if form_type == Some(b"WAVE") => Ok(FileFormat::Wav),
+            [b'F', b'O', b'R', b'M', ..]
+                if form_type == Some(b"AIFF") || form_type == Some(b"AIFC") =>
+            {
+                Ok(FileFormat::Aiff)
+            }
+            _ if bytes.starts_with(&HEADER_OBJECT) => Ok(FileFormat::Asf),
+            _ if bytes.starts_with(&EBML.to_be_bytes()) => Ok(FileFormat::Matroska),
+            // NOTE: the layer bits are 00 for AAC in ADTS, which shares the MPEG sync word
+            [0xFF, second, ..] if second & 0xF6 == 0xF0 => Ok(FileFormat::Aac),
+            [0xFF, second, ..] if second & 0xE0 == 0xE0 && second & 0x06 != 0 => {
+                Ok(FileFormat::Mpeg)
+            }
+            _ => Err(anyhow!("Unknown file format!")),
+        }
+    }
+
+    /// Identifies the format of the file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to identify.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<FileFormat> {
+        let mut file = File::open(path)?;
+        Self::read_from_reader(&mut file)
+    }
+
+    /// Identifies the format of the given stream, skipping over any ID3v2 tags at its start.
+    /// A stream with only an ID3v2 tag and MPEG audio is identified as MPEG audio.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to identify.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::file_format::*;
+    /// # use std::io::Cursor;
+    /// let mut bytes = vec![b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 1, 0];
+    /// bytes.extend(b"fLaC");
+    ///
+    /// assert_eq!(FileFormat::read_from_reader(&mut Cursor::new(bytes))?, FileFormat::Flac);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn read_from_reader<R: Read + Seek>(reader: &mut R) -> Result<FileFormat> {
+        loop {
+            let mut bytes = vec![];
+            reader
+                .by_ref()
+                .take(SIGNATURE_SIZE as u64)
+                .read_to_end(&mut bytes)?;
+            let offset = match id3v2_tag_size(&bytes) {
+                Some(offset) => offset,
+                None => return Self::identify(&bytes),
+            };
+            reader.seek(SeekFrom::Current(offset as i64 - bytes.len() as i64))?;
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::picture_type::PictureType;
+use anyhow::{anyhow, Result};
+
+/// Represents a FLAC picture block.
+/// This is also the format of the base64-encoded `METADATA_BLOCK_PICTURE`
+/// field in Vorbis comments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlacPicture {
+    /// The type of this picture.
+    pub picture_type: PictureType,
+
+    /// The MIME type for the picture.
+    pub mime_type: String,
+
+    /// The description of the picture.
+    pub description: String,
+
+    /// The width of the picture in pixels.
+    pub width: u32,
+
+    /// The height of the picture in pixels.
+    pub height: u32,
+
+    /// The colour depth of the picture in bits per pixel.
+    pub color_depth: u32,
+
+    /// The number of colours used for indexed pictures, or 0 otherwise.
+    pub indexed_colors: u32,
+
+    /// The binary data of the picture.
+    pub picture_data: Vec<u8>,
+}
+
+impl FlacPicture {
+    /// Parses a FLAC picture block from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the picture block, without a metadata block header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::flac_picture::*;
+    /// # use sisko_lib::picture_type::*;
+    /// let bytes = [
+    ///     0, 0, 0, 3,
+    ///     0, 0, 0, 9, b'i', b'm', b'a', b'g', b'e', b'/', b'p', b'n', b'g',
+    ///     0, 0, 0, 0,
+    ///     0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 24, 0, 0, 0, 0,
+    ///     0, 0, 0, 2, 0xab, 0xcd,
+    /// ];
+    ///
+    /// let picture = FlacPicture::parse(&bytes)?;
+    ///
+    /// assert_eq!(picture.picture_type, PictureType::CoverFront);
+    /// assert_eq!(picture.mime_type, "image/png");
+    /// assert_eq!(picture.description, "");
+    /// assert_eq!(picture.color_depth, 24);
+    /// assert_eq!(picture.picture_data, vec![0xab, 0xcd]);
+    /// assert_eq!(picture.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<FlacPicture> {
+        let mut index = 0;
+        let picture_type = PictureType::parse(read_u32(bytes, &mut index)? as u8)?;
+        let mime_type =
+            String::from_utf8_lossy(read_length_prefixed(bytes, &mut index)?).to_string();
+        let description =
+            String::from_utf8_lossy(read_length_prefixed(bytes, &mut index)?).to_string();
+        let width = read_u32(bytes, &mut index)?;
+        let height = read_u32(bytes, &mut index)?;
+        let color_depth = read_u32(bytes, &mut index)?;
+        let indexed_colors = read_u32(bytes, &mut index)?;
+        let picture_data = read_length_prefixed(bytes, &mut index)?.to_vec();
+
+        Ok(FlacPicture {
+            picture_type,
+            mime_type,
+            description,
+            width,
+            height,
+            color_depth,
+            indexed_colors,
+            picture_data,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut picture_bytes: Vec<u8> = vec![];
+        picture_bytes.extend([0, 0, 0]);
+        picture_bytes.extend(self.picture_type.to_bytes());
+        picture_bytes.extend((self.mime_type.len() as u32).to_be_bytes());
+        picture_bytes.extend(self.mime_type.as_bytes());
+        picture_bytes.extend((self.description.len() as u32).to_be_bytes());
+        picture_bytes.extend(self.description.as_bytes());
+        picture_bytes.extend(self.width.to_be_bytes());
+        picture_bytes.extend(self.height.to_be_bytes());
+        picture_bytes.extend(self.color_depth.to_be_bytes());
+        picture_bytes.extend(self.indexed_colors.to_be_bytes());
+        picture_bytes.extend((self.picture_data.len() as u32).to_be_bytes());
+        picture_bytes.extend(&self.picture_data);
+        picture_bytes
+    }
+}
+
+fn read_u32(bytes: &[u8], index: &mut usize) -> Result<u32> {
+    let value = bytes
+        .get(*index..*index + 4)
+        .ok_or(anyhow!("FLAC picture block is truncated!"))?;
+    *index += 4;
+    Ok(u32::from_be_bytes(value.try_into()?))
+}
+
+fn read_length_prefixed<'a>(bytes: &'a [u8], index: &mut usize) -> Result<&'a [u8]> {
+    let length = read_u32(bytes, index)? as usize;
+    let value = bytes
+        .get(*index..*index + length)
+        .ok_or(anyhow!("FLAC picture block is truncated!"))?;
+    *index += length;
+    Ok(value)
+}
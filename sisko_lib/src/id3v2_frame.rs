@@ -99,8 +99,13 @@ impl ID3v2Frame {
         Ok(frames)
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let header_bytes = self.header.to_bytes();
+    /// Returns the bytes of this frame for the given ID3v2 version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of ID3v2 to write the frame for.
+    pub fn to_bytes(&self, version: &ID3v2VersionNumber) -> Vec<u8> {
+        let header_bytes = self.header.to_bytes(version);
         let field_bytes = self.fields.to_bytes();
 
         let mut frame_bytes: Vec<u8> = vec![];
@@ -138,9 +138,9 @@ pub enum ID3v2FrameFields {
         id: Vec<u8>,
     },
 
-    /// An unrecognized frame's data.
+    /// The data of an unrecognized frame, or of one whose fields aren't parsed.
     UnknownFrameFields {
-        /// The bytes of the unrecognized frame's fields.
+        /// The bytes of the frame's fields.
         bytes: Vec<u8>,
     },
 
@@ -204,11 +204,8 @@ impl ID3v2FrameFields {
                 let encoding = TextEncoding::parse(bytes[0])?;
                 let text = encoding.decode(&bytes[1..])?;
                 ID3v2FrameFields::TextFields { encoding, text }
-            } else if frame_id.starts_with('W') && frame_id != "WXXX" {
-                ID3v2FrameFields::UrlFields {}
             } else {
                 match frame_id {
-                    "AENC" => ID3v2FrameFields::AudioEncryptionFields {},
                     "APIC" => {
                         let encoding = TextEncoding::parse(bytes[0])?;
                         let index = 1 + TextEncoding::Iso88591.next_terminator(&bytes[1..]).ok_or(
@@ -220,7 +217,7 @@ impl ID3v2FrameFields {
                         let mime_type = TextEncoding::Iso88591.decode(&bytes[1..index])?.remove(0);
                         let picture_type = PictureType::parse(bytes[index + 1])?;
                         let next_index = index
-                            + 3
+                            + 2
                             + encoding
                                 .next_terminator(&bytes[index + 2..])
                                 .ok_or(anyhow!(
@@ -240,7 +237,6 @@ impl ID3v2FrameFields {
                             picture_data,
                         }
                     }
-                    "ASPI" => ID3v2FrameFields::AudioPointSeekIndexFields {},
                     "COMM" => {
                         let encoding = TextEncoding::parse(bytes[0])?;
                         let language = TextEncoding::Iso88591.decode(&bytes[1..4])?.remove(0);
@@ -254,27 +250,6 @@ impl ID3v2FrameFields {
                             text,
                         }
                     }
-                    "COMR" => ID3v2FrameFields::CommercialFields {},
-                    "ENCR" => ID3v2FrameFields::EncryptionFields {},
-                    "EQU2" => ID3v2FrameFields::EqualisationFields {},
-                    "ETCO" => ID3v2FrameFields::EventTimingCodesFields {},
-                    "GEOB" => ID3v2FrameFields::GeneralObjectFields {},
-                    "GRID" => ID3v2FrameFields::GroupRegistrationFields {},
-                    "LINK" => ID3v2FrameFields::LinkedInfoFields {},
-                    "MCDI" => ID3v2FrameFields::MusicCdIdentifierFields {},
-                    "MLLT" => ID3v2FrameFields::MpegLocationLookupFields {},
-                    "OWNE" => ID3v2FrameFields::OwnershipFields {},
-                    "PCNT" => ID3v2FrameFields::PlayCounterFields {},
-                    "POPM" => ID3v2FrameFields::PopularimeterFields {},
-                    "POSS" => ID3v2FrameFields::PositionSyncFields {},
-                    "PRIV" => ID3v2FrameFields::PrivateFields {},
-                    "RBUF" => ID3v2FrameFields::RecommendedBufferFields {},
-                    "RVA2" => ID3v2FrameFields::RelativeVolumeAdjustmentFields {},
-                    "RVRB" => ID3v2FrameFields::ReverbFields {},
-                    "SEEK" => ID3v2FrameFields::SeekFields {},
-                    "SIGN" => ID3v2FrameFields::SignatureFields {},
-                    "SYLT" => ID3v2FrameFields::SynchronisedLyricsFields {},
-                    "SYTC" => ID3v2FrameFields::SynchronisedTempCodesFields {},
                     "TXXX" => {
                         let encoding = TextEncoding::parse(bytes[0])?;
                         let mut value = encoding.decode(&bytes[1..])?;
@@ -297,9 +272,8 @@ impl ID3v2FrameFields {
                             id: bytes[(index + 1)..].to_vec(),
                         }
                     }
-                    "USER" => ID3v2FrameFields::TermsOfUseFields {},
-                    "USLT" => ID3v2FrameFields::UnsynchronisedLyricsFields {},
-                    "WXXX" => ID3v2FrameFields::UserDefinedUrlFields {},
+                    // NOTE: the frames whose fields aren't parsed keep their bytes, so they can be
+                    // written back unchanged
                     _ => ID3v2FrameFields::UnknownFrameFields {
                         bytes: bytes.to_vec(),
                     },
@@ -308,15 +282,63 @@ impl ID3v2FrameFields {
         Ok(fields)
     }
 
+    /// Returns the bytes of these fields, as they're written after the frame header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::id3v2_frame_fields::*;
+    /// # use sisko_lib::id3v2_frame_flags::*;
+    /// # use sisko_lib::id3v2_frame_header::*;
+    /// # use sisko_lib::picture_type::*;
+    /// # use sisko_lib::text_encoding::*;
+    /// let fields = ID3v2FrameFields::AttachedPictureFields {
+    ///     encoding: TextEncoding::Iso88591,
+    ///     mime_type: String::from("image/png"),
+    ///     picture_type: PictureType::CoverFront,
+    ///     description: String::from("Cover"),
+    ///     picture_data: vec![1, 2, 3],
+    /// };
+    ///
+    /// let bytes = fields.to_bytes();
+    /// let header = ID3v2FrameHeader {
+    ///     frame_id: String::from("APIC"),
+    ///     size: bytes.len() as u32,
+    ///     flags: ID3v2FrameFlags::parse(&[0, 0]),
+    /// };
+    ///
+    /// assert_eq!(bytes, b"\x00image/png\x00\x03Cover\x00\x01\x02\x03");
+    /// if let ID3v2FrameFields::AttachedPictureFields { description, picture_data, .. } =
+    ///     ID3v2FrameFields::parse(&header, &bytes)?
+    /// {
+    ///     assert_eq!(description, "Cover");
+    ///     assert_eq!(picture_data, [1, 2, 3]);
+    /// } else { panic!(); }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
     pub fn to_bytes(&self) -> Vec<u8> {
         match &self {
             ID3v2FrameFields::AttachedPictureFields {
-                encoding: _,
-                mime_type: _,
-                picture_type: _,
-                description: _,
-                picture_data: _,
-            } => todo!(),
+                encoding,
+                mime_type,
+                picture_type,
+                description,
+                picture_data,
+            } => {
+                let encoding_bytes = encoding.to_bytes();
+                let mime_type_bytes =
+                    TextEncoding::Iso88591.encode(std::slice::from_ref(mime_type));
+                let picture_type_bytes = picture_type.to_bytes();
+                let desc_bytes = encoding.encode(std::slice::from_ref(description));
+
+                let mut field_bytes: Vec<u8> = vec![];
+                field_bytes.extend(encoding_bytes);
+                field_bytes.extend(mime_type_bytes);
+                field_bytes.extend(picture_type_bytes);
+                field_bytes.extend(desc_bytes);
+                field_bytes.extend(picture_data);
+                field_bytes
+            }
             ID3v2FrameFields::AudioEncryptionFields {} => todo!(),
             ID3v2FrameFields::AudioPointSeekIndexFields {} => todo!(),
             ID3v2FrameFields::CommentsFields {
@@ -65,9 +65,35 @@ impl ID3v2FrameHeader {
         })
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
+    /// Returns the bytes of this frame header for the given ID3v2 version.
+    /// ID3v2.4 writes the size as a synchsafe integer, while earlier versions write it plainly.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of ID3v2 to write the header for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::id3v2_frame_header::*;
+    /// # use sisko_lib::id3v2_version_number::*;
+    /// let bytes = [b'\x54', b'\x50', b'\x45', b'\x31', b'\x00', b'\x00', b'\x01', b'\x00', b'\x00', b'\x00'];
+    /// let v3 = ID3v2VersionNumber { major_number: 3, revision_number: 0 };
+    /// let v4 = ID3v2VersionNumber { major_number: 4, revision_number: 0 };
+    ///
+    /// let header = ID3v2FrameHeader::parse(&bytes, &v3)?;
+    ///
+    /// assert_eq!(header.size, 256);
+    /// assert_eq!(header.to_bytes(&v3), bytes);
+    /// assert_eq!(header.to_bytes(&v4)[4..8], [0, 0, 2, 0]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn to_bytes(&self, version: &ID3v2VersionNumber) -> Vec<u8> {
         let id_bytes = self.frame_id.as_bytes().to_vec();
-        let size_bytes = SynchSafeInteger::from(self.size).bytes;
+        let size_bytes = match version.major_number {
+            4 => SynchSafeInteger::from(self.size).bytes,
+            _ => self.size.to_be_bytes().to_vec(),
+        };
         let flag_bytes = self.flags.to_bytes();
 
         let mut header_bytes: Vec<u8> = vec![];
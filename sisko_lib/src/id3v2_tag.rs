@@ -139,7 +139,7 @@ impl ID3v2Tag {
         let frame_bytes: Vec<u8> = self
             .frames
             .iter()
-            .flat_map(|frame| frame.to_bytes())
+            .flat_map(|frame| frame.to_bytes(&self.header.version))
             .collect();
         let padding_bytes: Vec<u8> = vec![0u8; self.padding as usize];
         let footer_bytes: Vec<u8> = match &self.footer {
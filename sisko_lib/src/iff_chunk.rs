@@ -0,0 +1,98 @@
+use crate::iff_format::IffFormat;
+use anyhow::{anyhow, Result};
+
+/// Represents a chunk in a RIFF (WAV) or AIFF file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IffChunk {
+    /// The four character ID of the chunk.
+    pub chunk_id: String,
+
+    /// The data of the chunk, without its pad byte.
+    pub data: Vec<u8>,
+}
+
+impl IffChunk {
+    /// Returns a new chunk with the given ID and data.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk_id` - The four character ID of the chunk.
+    /// * `data` - The data of the chunk.
+    pub fn new(chunk_id: &str, data: Vec<u8>) -> IffChunk {
+        IffChunk {
+            chunk_id: String::from(chunk_id),
+            data,
+        }
+    }
+
+    /// Parses a chunk from the start of the given bytes.
+    /// Returns the chunk and the number of bytes it took up, including any pad byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the chunk from.
+    /// * `format` - The format of the file the chunk is in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::iff_chunk::*;
+    /// # use sisko_lib::iff_format::*;
+    /// let bytes = [b'a', b'b', b'c', b'd', 3, 0, 0, 0, 1, 2, 3, 0];
+    ///
+    /// let (chunk, size) = IffChunk::parse(&bytes, IffFormat::Riff)?;
+    ///
+    /// assert_eq!(chunk.chunk_id, "abcd");
+    /// assert_eq!(chunk.data, vec![1, 2, 3]);
+    /// assert_eq!(size, 12);
+    /// assert_eq!(chunk.to_bytes(IffFormat::Riff), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8], format: IffFormat) -> Result<(IffChunk, usize)> {
+        let header = bytes
+            .get(0..8)
+            .ok_or(anyhow!("Chunk header is truncated!"))?;
+        let chunk_id = String::from_utf8_lossy(&header[0..4]).to_string();
+        let size = format.read_size(header[4..8].try_into()?) as usize;
+        let data = bytes
+            .get(8..8 + size)
+            .ok_or(anyhow!("Chunk {} is truncated!", chunk_id))?
+            .to_vec();
+        // NOTE: the pad byte may be missing from the last chunk of a sloppily written file
+        let total_size = (8 + size + size % 2).min(bytes.len());
+        Ok((IffChunk { chunk_id, data }, total_size))
+    }
+
+    /// Parses all of the consecutive chunks in the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the chunks from.
+    /// * `format` - The format of the file the chunks are in.
+    pub fn parse_all(bytes: &[u8], format: IffFormat) -> Result<Vec<IffChunk>> {
+        let mut chunks = vec![];
+        let mut index = 0;
+        while index + 8 <= bytes.len() {
+            let (chunk, size) = Self::parse(&bytes[index..], format)?;
+            chunks.push(chunk);
+            index += size;
+        }
+        Ok(chunks)
+    }
+
+    /// Returns the total size of this chunk, including its header and any pad byte.
+    pub fn total_size(&self) -> usize {
+        8 + self.data.len() + self.data.len() % 2
+    }
+
+    pub fn to_bytes(&self, format: IffFormat) -> Vec<u8> {
+        let mut chunk_bytes: Vec<u8> = vec![];
+        chunk_bytes.extend(self.chunk_id.as_bytes());
+        chunk_bytes.extend(format.write_size(self.data.len() as u32));
+        chunk_bytes.extend(&self.data);
+        if self.data.len() % 2 == 1 {
+            chunk_bytes.push(0);
+        }
+        chunk_bytes
+    }
+}
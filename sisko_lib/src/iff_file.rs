@@ -0,0 +1,184 @@
+use crate::id3v2_tag::ID3v2Tag;
+use crate::iff_chunk::IffChunk;
+use crate::iff_format::IffFormat;
+use crate::riff_info::RiffInfo;
+use anyhow::{anyhow, Result};
+use std::io::Cursor;
+use std::path::Path;
+
+/// Represents a RIFF (WAV) or AIFF file as the chunks in its outermost chunk.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IffFile {
+    /// The format of the file.
+    pub format: IffFormat,
+
+    /// The form type of the file (e.g. `WAVE`, `AIFF`, or `AIFC`).
+    pub form_type: String,
+
+    /// The chunks in the file.
+    pub chunks: Vec<IffChunk>,
+}
+
+impl IffFile {
+    /// Parses a RIFF or AIFF file from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::iff_file::*;
+    /// # use sisko_lib::iff_format::*;
+    /// let bytes = [
+    ///     b'R', b'I', b'F', b'F', 16, 0, 0, 0, b'W', b'A', b'V', b'E',
+    ///     b'd', b'a', b't', b'a', 3, 0, 0, 0, 1, 2, 3, 0,
+    /// ];
+    ///
+    /// let file = IffFile::parse(&bytes)?;
+    ///
+    /// assert_eq!(file.format, IffFormat::Riff);
+    /// assert_eq!(file.form_type, "WAVE");
+    /// assert_eq!(file.chunks.len(), 1);
+    /// assert_eq!(file.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<IffFile> {
+        let format = IffFormat::identify(bytes)?;
+        let (container, _) = IffChunk::parse(bytes, format)?;
+        let form_type = container
+            .data
+            .get(0..4)
+            .map(|form_type| String::from_utf8_lossy(form_type).to_string())
+            .ok_or(anyhow!("{} chunk is truncated!", format.container_id()))?;
+        let chunks = IffChunk::parse_all(&container.data[4..], format)?;
+        Ok(IffFile {
+            format,
+            form_type,
+            chunks,
+        })
+    }
+
+    /// Reads the RIFF or AIFF file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<IffFile> {
+        let bytes = std::fs::read(path)?;
+        Self::parse(&bytes)
+    }
+
+    /// Returns the ID3v2 tag in the file's `id3 ` or `ID3 ` chunk, if there is one.
+    pub fn id3v2_tag(&self) -> Result<Option<ID3v2Tag>> {
+        match self.id3_chunk_index() {
+            Some(index) => {
+                let mut reader = Cursor::new(&self.chunks[index].data);
+                Ok(Some(ID3v2Tag::read_from_reader(&mut reader)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the info in the file's `LIST`/`INFO` chunk, if there is one.
+    pub fn info(&self) -> Result<Option<RiffInfo>> {
+        self.info_chunk_index()
+            .map(|index| RiffInfo::parse(&self.chunks[index].data))
+            .transpose()
+    }
+
+    /// Sets the ID3v2 tag of the file, replacing any existing `id3 ` or `ID3 ` chunk.
+    /// A new chunk is added to the end of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::id3v2_header::*;
+    /// # use sisko_lib::id3v2_header_flags::*;
+    /// # use sisko_lib::id3v2_tag::*;
+    /// # use sisko_lib::id3v2_version_number::*;
+    /// # use sisko_lib::iff_file::*;
+    /// let bytes = [
+    ///     b'F', b'O', b'R', b'M', 0, 0, 0, 16, b'A', b'I', b'F', b'F',
+    ///     b'S', b'S', b'N', b'D', 0, 0, 0, 3, 1, 2, 3, 0,
+    /// ];
+    /// let mut file = IffFile::parse(&bytes)?;
+    /// let tag = ID3v2Tag {
+    ///     header: ID3v2Header {
+    ///         file_identifier: String::from("ID3"),
+    ///         version: ID3v2VersionNumber::new(4, 0),
+    ///         flags: ID3v2HeaderFlags {
+    ///             unsynchronisation: false,
+    ///             has_extended_header: false,
+    ///             is_experimental: false,
+    ///             has_footer: false,
+    ///         },
+    ///         size: 0,
+    ///     },
+    ///     extended_header: None,
+    ///     frames: vec![],
+    ///     padding: 0,
+    ///     footer: None,
+    /// };
+    ///
+    /// file.set_id3v2_tag(&tag);
+    /// let new_bytes = file.to_bytes();
+    ///
+    /// assert_eq!(&new_bytes[4..8], &[0, 0, 0, 34]);
+    /// assert_eq!(&new_bytes[24..28], b"ID3 ");
+    /// assert_eq!(new_bytes.len(), 42);
+    /// assert!(IffFile::parse(&new_bytes)?.id3v2_tag()?.is_some());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn set_id3v2_tag(&mut self, tag: &ID3v2Tag) {
+        let chunk = IffChunk::new(self.format.id3_chunk_id(), tag.to_bytes());
+        match self.id3_chunk_index() {
+            Some(index) => self.chunks[index] = chunk,
+            None => self.chunks.push(chunk),
+        }
+    }
+
+    /// Sets the info of the file, replacing any existing `LIST`/`INFO` chunk.
+    /// Only RIFF files have info chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - The info to set.
+    pub fn set_info(&mut self, info: &RiffInfo) -> Result<()> {
+        if self.format != IffFormat::Riff {
+            return Err(anyhow!("Only RIFF files have LIST/INFO chunks!"));
+        }
+        let chunk = info.to_chunk();
+        match self.info_chunk_index() {
+            Some(index) => self.chunks[index] = chunk,
+            None => self.chunks.push(chunk),
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![];
+        data.extend(self.form_type.as_bytes());
+        for chunk in &self.chunks {
+            data.extend(chunk.to_bytes(self.format));
+        }
+        IffChunk::new(self.format.container_id(), data).to_bytes(self.format)
+    }
+
+    fn id3_chunk_index(&self) -> Option<usize> {
+        self.chunks
+            .iter()
+            .position(|chunk| chunk.chunk_id.eq_ignore_ascii_case("id3 "))
+    }
+
+    fn info_chunk_index(&self) -> Option<usize> {
+        self.chunks
+            .iter()
+            .position(|chunk| chunk.chunk_id == "LIST" && chunk.data.starts_with(b"INFO"))
+    }
+}
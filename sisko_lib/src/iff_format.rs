@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Result};
+
+/// Represents the formats of chunked (IFF-style) audio files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IffFormat {
+    /// An AIFF or AIFF-C file: a big-endian `FORM` chunk.
+    Aiff,
+
+    /// A WAV file: a little-endian `RIFF` chunk.
+    Riff,
+}
+
+impl IffFormat {
+    /// Identifies the format of a file from its first four bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The ID of the file's outermost chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::iff_format::*;
+    /// assert_eq!(IffFormat::identify(b"RIFF")?, IffFormat::Riff);
+    /// assert_eq!(IffFormat::identify(b"FORM")?, IffFormat::Aiff);
+    /// assert!(IffFormat::identify(b"fLaC").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn identify(bytes: &[u8]) -> Result<IffFormat> {
+        match bytes.get(0..4) {
+            Some(b"FORM") => Ok(IffFormat::Aiff),
+            Some(b"RIFF") => Ok(IffFormat::Riff),
+            _ => Err(anyhow!("Not a RIFF or AIFF file!")),
+        }
+    }
+
+    /// Returns the ID of the outermost chunk of a file in this format.
+    pub fn container_id(&self) -> &'static str {
+        match self {
+            IffFormat::Aiff => "FORM",
+            IffFormat::Riff => "RIFF",
+        }
+    }
+
+    /// Returns the ID of the chunk that holds an ID3v2 tag in this format.
+    pub fn id3_chunk_id(&self) -> &'static str {
+        match self {
+            IffFormat::Aiff => "ID3 ",
+            IffFormat::Riff => "id3 ",
+        }
+    }
+
+    /// Reads a chunk size in this format's byte order.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The four bytes of the size.
+    pub fn read_size(&self, bytes: [u8; 4]) -> u32 {
+        match self {
+            IffFormat::Aiff => u32::from_be_bytes(bytes),
+            IffFormat::Riff => u32::from_le_bytes(bytes),
+        }
+    }
+
+    /// Writes a chunk size in this format's byte order.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size to write.
+    pub fn write_size(&self, size: u32) -> [u8; 4] {
+        match self {
+            IffFormat::Aiff => size.to_be_bytes(),
+            IffFormat::Riff => size.to_le_bytes(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Result};
+
+/// The size of a LAME header.
+pub const LAME_HEADER_SIZE: usize = 36;
+
+/// Represents the LAME extension that follows a Xing or Info header.
+/// Besides LAME, it's also written by other encoders such as FFmpeg's (`Lavc`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LameHeader {
+    /// The encoder and its version (e.g. `LAME3.100`).
+    pub encoder: String,
+
+    /// The revision of the LAME header.
+    pub revision: u8,
+
+    /// The VBR method (e.g. 1 for CBR, 4 for VBR).
+    pub vbr_method: u8,
+
+    /// The lowpass filter frequency in Hz.
+    pub lowpass: u32,
+
+    /// The number of samples the encoder added to the start of the stream.
+    pub encoder_delay: u16,
+
+    /// The number of samples the encoder added to the end of the stream.
+    pub encoder_padding: u16,
+
+    /// The size of the stream in bytes, from the first frame to the last.
+    pub music_length: u32,
+}
+
+impl LameHeader {
+    /// Parses the LAME header from the start of the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the LAME header from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::lame_header::*;
+    /// let mut bytes = b"LAME3.100".to_vec();
+    /// bytes.extend([0x24, 0xC8]);
+    /// bytes.extend([0; 10]);
+    /// bytes.extend([0x24, 0x03, 0x3C]);
+    /// bytes.extend([0; 4]);
+    /// bytes.extend(417000u32.to_be_bytes());
+    /// bytes.extend([0; 4]);
+    ///
+    /// let header = LameHeader::parse(&bytes)?;
+    ///
+    /// assert_eq!(header.encoder, "LAME3.100");
+    /// assert_eq!(header.vbr_method, 4);
+    /// assert_eq!(header.lowpass, 20000);
+    /// assert_eq!(header.encoder_delay, 576);
+    /// assert_eq!(header.encoder_padding, 828);
+    /// assert_eq!(header.music_length, 417000);
+    /// assert!(LameHeader::parse(b"\0\0\0\0").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<LameHeader> {
+        let bytes = bytes
+            .get(0..LAME_HEADER_SIZE)
+            .ok_or(anyhow!("LAME header is truncated!"))?;
+        let encoder = &bytes[0..9];
+        if !encoder[0].is_ascii_alphabetic() || !encoder.iter().all(|b| b.is_ascii()) {
+            return Err(anyhow!("LAME header has no encoder version!"));
+        }
+        let encoder = String::from_utf8_lossy(encoder)
+            .trim_end_matches(['\0', ' '])
+            .to_string();
+        Ok(LameHeader {
+            encoder,
+            revision: bytes[9] >> 4,
+            vbr_method: bytes[9] & 0x0F,
+            lowpass: bytes[10] as u32 * 100,
+            encoder_delay: ((bytes[21] as u16) << 4) | (bytes[22] as u16 >> 4),
+            encoder_padding: ((bytes[22] as u16 & 0x0F) << 8) | bytes[23] as u16,
+            music_length: u32::from_be_bytes(bytes[28..32].try_into()?),
+        })
+    }
+}
@@ -3,6 +3,22 @@
 use anyhow::{anyhow, Result};
 use encoding_rs::{mem::encode_latin1_lossy, UTF_16BE};
 
+pub mod asf_content_description;
+pub mod asf_content_descriptor;
+pub mod asf_descriptor_value;
+pub mod asf_extended_content_description;
+pub mod asf_object;
+pub mod asf_picture;
+pub mod asf_tag;
+pub mod audio_checksum;
+pub mod chromaprint;
+pub mod chromaprint_classifier;
+pub mod chromaprint_image;
+pub mod chromaprint_resampler;
+pub mod disc_toc;
+pub mod ebml_element;
+pub mod file_format;
+pub mod flac_picture;
 pub mod id3v2_extended_flag_data;
 pub mod id3v2_extended_flags;
 pub mod id3v2_extended_header;
@@ -18,9 +34,38 @@ pub mod id3v2_header_flags;
 pub mod id3v2_tag;
 pub mod id3v2_tag_restrictions;
 pub mod id3v2_version_number;
+pub mod iff_chunk;
+pub mod iff_file;
+pub mod iff_format;
+pub mod lame_header;
+pub mod matroska_element_id;
+pub mod matroska_simple_tag;
+pub mod matroska_tag;
+pub mod matroska_tags;
+pub mod mp4_atom;
+pub mod mp4_data;
+pub mod mp4_data_type;
+pub mod mp4_tag;
+pub mod mp4_tag_item;
+pub mod mpeg_audio_info;
+pub mod mpeg_channel_mode;
+pub mod mpeg_frame_header;
+pub mod mpeg_layer;
+pub mod mpeg_version;
+pub mod ogg_codec;
+pub mod ogg_comment_header;
+pub mod ogg_packet;
+pub mod ogg_page;
+pub mod ogg_page_flags;
 pub mod picture_type;
+pub mod riff_info;
+pub mod riff_info_field;
 pub mod synch_safe_integer;
 pub mod text_encoding;
+pub mod vbri_header;
+pub mod vorbis_comment;
+pub mod vorbis_comment_field;
+pub mod xing_header;
 
 /// Returns whether or not the given byte has the given bit set.
 /// Bits are numbers as follows: 0b7654_3210.
@@ -147,12 +192,22 @@ pub fn encode_utf8_strings(strings: &[String]) -> Vec<u8> {
 /// # Ok::<(), anyhow::Error>(())
 /// ```
 pub fn encode_utf16bom_strings(strings: &[String]) -> Vec<u8> {
-    let mut bytes: Vec<u8> = encode_utf16_strings(strings);
+    // NOTE: the FF FE byte order mark is of little-endian UTF-16
+    let mut bytes: Vec<u8> = strings
+        .iter()
+        .flat_map(|s| encode_utf16le_string(s))
+        .collect();
     bytes.insert(0, b'\xfe');
     bytes.insert(0, b'\xff');
     bytes
 }
 
+/// Encodes the given strings as big-endian UTF-16, each terminated by a null.
+///
+/// # Arguments
+///
+/// * `strings` - The strings to encode.
+///
 /// # Examples
 ///
 /// ```
@@ -160,15 +215,19 @@ pub fn encode_utf16bom_strings(strings: &[String]) -> Vec<u8> {
 /// let s: Vec<String> = vec!["2008".to_string()];
 ///
 /// let bytes = encode_utf16_strings(&s);
+/// let accented = encode_utf16_strings(&["é".to_string()]);
 ///
-/// assert_eq!(bytes[0], b'\x32');
-/// assert_eq!(bytes[1], 0);
-/// assert_eq!(bytes[2], b'\x30');
-/// assert_eq!(bytes[3], 0);
-/// assert_eq!(bytes[4], b'\x30');
-/// assert_eq!(bytes[5], 0);
-/// assert_eq!(bytes[6], b'\x38');
-/// assert_eq!(bytes[7], 0);
+/// assert_eq!(accented, [0, b'\xe9', 0, 0]);
+/// assert_eq!(decode_utf16_strings(&accented)?, ["é"]);
+///
+/// assert_eq!(bytes[0], 0);
+/// assert_eq!(bytes[1], b'\x32');
+/// assert_eq!(bytes[2], 0);
+/// assert_eq!(bytes[3], b'\x30');
+/// assert_eq!(bytes[4], 0);
+/// assert_eq!(bytes[5], b'\x30');
+/// assert_eq!(bytes[6], 0);
+/// assert_eq!(bytes[7], b'\x38');
 /// assert_eq!(bytes[8], 0);
 /// assert_eq!(bytes[9], 0);
 /// # Ok::<(), anyhow::Error>(())
@@ -176,12 +235,80 @@ pub fn encode_utf16bom_strings(strings: &[String]) -> Vec<u8> {
 pub fn encode_utf16_strings(strings: &[String]) -> Vec<u8> {
     strings
         .iter()
-        .flat_map(|s| {
-            let (b, _, _) = UTF_16BE.encode(s.as_str());
-            let mut b = b.to_vec();
-            b.push(0);
-            b
-        })
-        .flat_map(|b| [b, 0])
+        .flat_map(|s| s.encode_utf16().chain([0]).collect::<Vec<u16>>())
+        .flat_map(u16::to_be_bytes)
+        .collect()
+}
+
+/// Decodes a little-endian UTF-16 string, dropping any null terminator.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to decode the string from.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::*;
+/// let bytes = [b'W', 0, b'M', 0, 0, 0];
+///
+/// assert_eq!(decode_utf16le_string(&bytes), "WM");
+/// ```
+pub fn decode_utf16le_string(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+        .trim_end_matches('\x00')
+        .to_string()
+}
+
+/// Encodes a null-terminated little-endian UTF-16 string.
+///
+/// # Arguments
+///
+/// * `s` - The string to encode.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::*;
+/// assert_eq!(encode_utf16le_string("WM"), vec![b'W', 0, b'M', 0, 0, 0]);
+/// ```
+pub fn encode_utf16le_string(s: &str) -> Vec<u8> {
+    s.encode_utf16()
+        .chain([0])
+        .flat_map(|unit| unit.to_le_bytes())
         .collect()
 }
+
+/// Returns the total size of the ID3v2 tag that the given bytes start with, including its header
+/// and any footer, or `None` if they don't start with an ID3v2 tag.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to check, at least the 10 bytes of an ID3v2 header.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::*;
+/// assert_eq!(id3v2_tag_size(&[b'I', b'D', b'3', 4, 0, 0, 0, 0, 1, 0]), Some(138));
+/// assert_eq!(id3v2_tag_size(&[b'I', b'D', b'3', 4, 0, 0x10, 0, 0, 0, 1]), Some(21));
+/// assert_eq!(id3v2_tag_size(b"fLaC\0\0\0\x22\0\0"), None);
+/// ```
+pub fn id3v2_tag_size(bytes: &[u8]) -> Option<u64> {
+    const HEADER_SIZE: u64 = 10;
+    if !bytes.starts_with(b"ID3") || bytes.len() < HEADER_SIZE as usize {
+        return None;
+    }
+    let size = bytes[6..10]
+        .iter()
+        .fold(0u64, |size, byte| (size << 7) | (*byte & 0x7F) as u64);
+    let footer_size = match bytes[5] & 0x10 {
+        0 => 0,
+        _ => HEADER_SIZE,
+    };
+    Some(HEADER_SIZE + size + footer_size)
+}
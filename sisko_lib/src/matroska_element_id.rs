@@ -0,0 +1,76 @@
+//! The IDs of the Matroska elements sisko reads and writes.
+
+/// The EBML header at the start of the file.
+pub const EBML: u32 = 0x1A45DFA3;
+
+/// The segment holding the rest of the file.
+pub const SEGMENT: u32 = 0x18538067;
+
+/// The index of the segment's top-level elements.
+pub const SEEK_HEAD: u32 = 0x114D9B74;
+
+/// An entry in the seek head.
+pub const SEEK: u32 = 0x4DBB;
+
+/// The ID of the element a seek entry points to.
+pub const SEEK_ID: u32 = 0x53AB;
+
+/// The position of the element a seek entry points to, relative to the segment data.
+pub const SEEK_POSITION: u32 = 0x53AC;
+
+/// A cluster of media blocks.
+pub const CLUSTER: u32 = 0x1F43B675;
+
+/// A block of frames in a cluster.
+pub const SIMPLE_BLOCK: u32 = 0xA3;
+
+/// A block of frames in a cluster, with extra information about it.
+pub const BLOCK_GROUP: u32 = 0xA0;
+
+/// The segment information (e.g. its duration).
+pub const INFO: u32 = 0x1549A966;
+
+/// The descriptions of the segment's tracks.
+pub const TRACKS: u32 = 0x1654AE6B;
+
+/// The index of the segment's clusters by time.
+pub const CUES: u32 = 0x1C53BB6B;
+
+/// The files attached to the segment (e.g. cover art).
+pub const ATTACHMENTS: u32 = 0x1941A469;
+
+/// The segment's chapters.
+pub const CHAPTERS: u32 = 0x1043A770;
+
+/// The metadata of the segment.
+pub const TAGS: u32 = 0x1254C367;
+
+/// A group of simple tags applying to the same targets.
+pub const TAG: u32 = 0x7373;
+
+/// What a tag applies to.
+pub const TARGETS: u32 = 0x63C0;
+
+/// The logical level of the target (e.g. 50 for an album, 30 for a track).
+pub const TARGET_TYPE_VALUE: u32 = 0x68CA;
+
+/// The informational name of the target level (e.g. `ALBUM`).
+pub const TARGET_TYPE: u32 = 0x63CA;
+
+/// A name and value pair of metadata.
+pub const SIMPLE_TAG: u32 = 0x67C8;
+
+/// The name of a simple tag.
+pub const TAG_NAME: u32 = 0x45A3;
+
+/// The language of a simple tag.
+pub const TAG_LANGUAGE: u32 = 0x447A;
+
+/// The string value of a simple tag.
+pub const TAG_STRING: u32 = 0x4487;
+
+/// The binary value of a simple tag.
+pub const TAG_BINARY: u32 = 0x4485;
+
+/// Space reserved for later use.
+pub const VOID: u32 = 0xEC;
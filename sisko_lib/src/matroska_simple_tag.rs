@@ -0,0 +1,102 @@
+use crate::ebml_element::EbmlElement;
+use crate::matroska_element_id::{SIMPLE_TAG, TAG_BINARY, TAG_LANGUAGE, TAG_NAME, TAG_STRING};
+use anyhow::Result;
+
+/// Represents a `SimpleTag` element in a Matroska file: a name and value pair of metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatroskaSimpleTag {
+    /// The name of the tag (e.g. `TITLE`).
+    pub name: String,
+
+    /// The language of the tag, if there is one.
+    pub language: Option<String>,
+
+    /// The string value of the tag, if there is one.
+    pub string: Option<String>,
+
+    /// The binary value of the tag, if there is one.
+    pub binary: Option<Vec<u8>>,
+
+    /// The nested tags describing this one.
+    pub children: Vec<MatroskaSimpleTag>,
+}
+
+impl MatroskaSimpleTag {
+    /// Returns a new tag with the given name and string value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tag.
+    /// * `value` - The string value of the tag.
+    pub fn new(name: &str, value: &str) -> MatroskaSimpleTag {
+        MatroskaSimpleTag {
+            name: String::from(name),
+            language: None,
+            string: Some(String::from(value)),
+            binary: None,
+            children: vec![],
+        }
+    }
+
+    /// Parses the tag from the given `SimpleTag` element.
+    /// Unknown child elements are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The `SimpleTag` element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// # use sisko_lib::matroska_simple_tag::*;
+    /// let bytes = [
+    ///     0x67, 0xC8, 0x8F,
+    ///     0x45, 0xA3, 0x85, b'T', b'I', b'T', b'L', b'E',
+    ///     0x44, 0x87, 0x84, b'H', b'u', b'r', b't',
+    /// ];
+    /// let (element, _) = EbmlElement::parse(&bytes)?;
+    ///
+    /// let tag = MatroskaSimpleTag::parse(&element)?;
+    ///
+    /// assert_eq!(tag, MatroskaSimpleTag::new("TITLE", "Hurt"));
+    /// assert_eq!(tag.to_element().to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(element: &EbmlElement) -> Result<MatroskaSimpleTag> {
+        let mut tag = MatroskaSimpleTag {
+            name: String::new(),
+            language: None,
+            string: None,
+            binary: None,
+            children: vec![],
+        };
+        for child in element.children()? {
+            match child.id {
+                TAG_NAME => tag.name = child.string(),
+                TAG_LANGUAGE => tag.language = Some(child.string()),
+                TAG_STRING => tag.string = Some(child.string()),
+                TAG_BINARY => tag.binary = Some(child.data),
+                SIMPLE_TAG => tag.children.push(Self::parse(&child)?),
+                _ => {}
+            }
+        }
+        Ok(tag)
+    }
+
+    /// Returns this tag as a `SimpleTag` element.
+    pub fn to_element(&self) -> EbmlElement {
+        let mut children = vec![EbmlElement::new_string(TAG_NAME, &self.name)];
+        if let Some(language) = &self.language {
+            children.push(EbmlElement::new_string(TAG_LANGUAGE, language));
+        }
+        if let Some(string) = &self.string {
+            children.push(EbmlElement::new_string(TAG_STRING, string));
+        }
+        if let Some(binary) = &self.binary {
+            children.push(EbmlElement::new(TAG_BINARY, binary.clone()));
+        }
+        children.extend(self.children.iter().map(|child| child.to_element()));
+        EbmlElement::new_master(SIMPLE_TAG, &children)
+    }
+}
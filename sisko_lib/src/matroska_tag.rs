@@ -0,0 +1,109 @@
+use crate::ebml_element::EbmlElement;
+use crate::matroska_element_id::{SIMPLE_TAG, TAG, TARGETS, TARGET_TYPE_VALUE};
+use crate::matroska_simple_tag::MatroskaSimpleTag;
+use anyhow::Result;
+
+/// The target type value of album-level tags.
+pub const ALBUM_TARGET_TYPE_VALUE: u64 = 50;
+
+/// The target type value of part-level tags, such as the discs of an album.
+pub const PART_TARGET_TYPE_VALUE: u64 = 40;
+
+/// The target type value of track-level tags, which is also the default.
+pub const TRACK_TARGET_TYPE_VALUE: u64 = 30;
+
+/// Represents a `Tag` element in a Matroska file: simple tags applying to the same targets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatroskaTag {
+    /// The logical level of the target (e.g. 50 for an album, 30 for a track).
+    pub target_type_value: u64,
+
+    /// The other elements of the `Targets` element (e.g. `TargetType` or `TagTrackUID`).
+    pub targets: Vec<EbmlElement>,
+
+    /// The simple tags.
+    pub simple_tags: Vec<MatroskaSimpleTag>,
+}
+
+impl MatroskaTag {
+    /// Returns a new tag at the given target level, with no simple tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_type_value` - The logical level of the target.
+    pub fn new(target_type_value: u64) -> MatroskaTag {
+        MatroskaTag {
+            target_type_value,
+            targets: vec![],
+            simple_tags: vec![],
+        }
+    }
+
+    /// Parses the tag from the given `Tag` element.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The `Tag` element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// # use sisko_lib::matroska_simple_tag::*;
+    /// # use sisko_lib::matroska_tag::*;
+    /// let mut tag = MatroskaTag::new(ALBUM_TARGET_TYPE_VALUE);
+    /// tag.simple_tags.push(MatroskaSimpleTag::new("TITLE", "The Downward Spiral"));
+    ///
+    /// let parsed = MatroskaTag::parse(&tag.to_element())?;
+    ///
+    /// assert_eq!(parsed, tag);
+    /// assert_eq!(parsed.get("TITLE"), Some("The Downward Spiral"));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(element: &EbmlElement) -> Result<MatroskaTag> {
+        let mut tag = MatroskaTag::new(TRACK_TARGET_TYPE_VALUE);
+        for child in element.children()? {
+            match child.id {
+                TARGETS => {
+                    for target in child.children()? {
+                        match target.id {
+                            TARGET_TYPE_VALUE => tag.target_type_value = target.uint(),
+                            _ => tag.targets.push(target),
+                        }
+                    }
+                }
+                SIMPLE_TAG => tag.simple_tags.push(MatroskaSimpleTag::parse(&child)?),
+                _ => {}
+            }
+        }
+        Ok(tag)
+    }
+
+    /// Returns the string value of the first simple tag with the given name, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the simple tag to get.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.simple_tags
+            .iter()
+            .find(|simple_tag| simple_tag.name == name)
+            .and_then(|simple_tag| simple_tag.string.as_deref())
+    }
+
+    /// Returns this tag as a `Tag` element.
+    pub fn to_element(&self) -> EbmlElement {
+        let mut targets = vec![EbmlElement::new_uint(
+            TARGET_TYPE_VALUE,
+            self.target_type_value,
+        )];
+        targets.extend(self.targets.iter().cloned());
+        let mut children = vec![EbmlElement::new_master(TARGETS, &targets)];
+        children.extend(
+            self.simple_tags
+                .iter()
+                .map(|simple_tag| simple_tag.to_element()),
+        );
+        EbmlElement::new_master(TAG, &children)
+    }
+}
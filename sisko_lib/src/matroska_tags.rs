@@ -0,0 +1,383 @@
+use crate::ebml_element::{encode_id, encode_size_with_length, read_id, read_size, EbmlElement};
+use crate::matroska_element_id::{
+    EBML, SEEK, SEEK_HEAD, SEEK_ID, SEEK_POSITION, SEGMENT, TAG, TAGS, VOID,
+};
+use crate::matroska_tag::MatroskaTag;
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Represents the `Tags` element of a Matroska (MKA/MKV/WebM) file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatroskaTags {
+    /// The tags, each applying to its own targets.
+    pub tags: Vec<MatroskaTag>,
+}
+
+/// The position of an element within a byte slice.
+#[derive(Clone, Copy, Debug)]
+struct ElementSpan {
+    /// The ID of the element.
+    id: u32,
+
+    /// The index of the first byte of the element's ID.
+    start: usize,
+
+    /// The index of the first byte of the element's data.
+    data_start: usize,
+
+    /// The index after the last byte of the element.
+    end: usize,
+}
+
+impl MatroskaTags {
+    /// Parses the tags from the given `Tags` element.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The `Tags` element.
+    pub fn parse(element: &EbmlElement) -> Result<MatroskaTags> {
+        let tags = element
+            .children()?
+            .iter()
+            .filter(|child| child.id == TAG)
+            .map(MatroskaTag::parse)
+            .collect::<Result<Vec<MatroskaTag>>>()?;
+        Ok(MatroskaTags { tags })
+    }
+
+    /// Returns the tags at the given target level.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_type_value` - The logical level of the target (e.g. 50 for an album).
+    pub fn at_level(&self, target_type_value: u64) -> impl Iterator<Item = &MatroskaTag> {
+        self.tags
+            .iter()
+            .filter(move |tag| tag.target_type_value == target_type_value)
+    }
+
+    /// Reads the tags from the Matroska file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read the tags from.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<MatroskaTags> {
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(file);
+        Self::read_from_reader(&mut reader)
+    }
+
+    /// Reads the tags from the given Matroska stream.
+    /// The segment's top-level elements are skipped until the `Tags` element is found.
+    /// If an element of unknown size (usually a live-recorded cluster) is reached first,
+    /// the `Tags` element is found through the seek head instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to read the tags from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// # use sisko_lib::matroska_simple_tag::*;
+    /// # use sisko_lib::matroska_tag::*;
+    /// # use sisko_lib::matroska_tags::*;
+    /// # use std::io::Cursor;
+    /// let mut tag = MatroskaTag::new(TRACK_TARGET_TYPE_VALUE);
+    /// tag.simple_tags.push(MatroskaSimpleTag::new("TITLE", "Hurt"));
+    /// let tags = MatroskaTags { tags: vec![tag] };
+    /// let segment = EbmlElement::new_master(0x18538067, &[
+    ///     EbmlElement::new(0xEC, vec![0; 4]),
+    ///     tags.to_element(),
+    /// ]);
+    /// let mut bytes = EbmlElement::new(0x1A45DFA3, vec![]).to_bytes();
+    /// bytes.extend(segment.to_bytes());
+    ///
+    /// assert_eq!(MatroskaTags::read_from_reader(&mut Cursor::new(bytes))?, tags);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn read_from_reader<R: Read + Seek>(reader: &mut R) -> Result<MatroskaTags> {
+        match read_element_header(reader)? {
+            Some((EBML, Some(size))) => reader.seek(SeekFrom::Current(size as i64))?,
+            _ => return Err(anyhow!("No EBML header found!")),
+        };
+        let segment_size = loop {
+            match read_element_header(reader)? {
+                Some((SEGMENT, size)) => break size,
+                Some((_, Some(size))) => reader.seek(SeekFrom::Current(size as i64))?,
+                _ => return Err(anyhow!("No Matroska segment found!")),
+            };
+        };
+        let segment_start = reader.stream_position()?;
+        let segment_end = segment_size.map(|size| segment_start + size);
+
+        let mut tags_position: Option<u64> = None;
+        loop {
+            if let Some(end) = segment_end {
+                if reader.stream_position()? >= end {
+                    break;
+                }
+            }
+            match read_element_header(reader)? {
+                Some((TAGS, Some(size))) => return Self::read_element(reader, size),
+                Some((SEEK_HEAD, Some(size))) => {
+                    let mut data = vec![0u8; size as usize];
+                    reader.read_exact(&mut data)?;
+                    tags_position = Self::seek_position(&data)?.or(tags_position);
+                }
+                Some((_, Some(size))) => {
+                    reader.seek(SeekFrom::Current(size as i64))?;
+                }
+                Some((_, None)) | None => break,
+            }
+        }
+
+        match tags_position {
+            Some(position) => {
+                reader.seek(SeekFrom::Start(segment_start + position))?;
+                match read_element_header(reader)? {
+                    Some((TAGS, Some(size))) => Self::read_element(reader, size),
+                    _ => Err(anyhow!("Matroska seek head doesn't point to the tags!")),
+                }
+            }
+            None => Ok(MatroskaTags::default()),
+        }
+    }
+
+    /// Returns the given Matroska file's bytes with its `Tags` element replaced by this one.
+    /// The new element is written over the old one or into `Void` elements if it fits,
+    /// and otherwise appended to the end of the segment, leaving a `Void` element in place
+    /// of the old one. The segment size and the seek head are updated to match.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_bytes` - The bytes of the Matroska file to write the tags to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ebml_element::*;
+    /// # use sisko_lib::matroska_simple_tag::*;
+    /// # use sisko_lib::matroska_tag::*;
+    /// # use sisko_lib::matroska_tags::*;
+    /// # use std::io::Cursor;
+    /// let segment = EbmlElement::new_master(0x18538067, &[
+    ///     EbmlElement::new(0x1F43B675, vec![1, 2, 3]),
+    /// ]);
+    /// let mut file_bytes = EbmlElement::new(0x1A45DFA3, vec![]).to_bytes();
+    /// file_bytes.extend(segment.to_bytes());
+    ///
+    /// let mut tag = MatroskaTag::new(ALBUM_TARGET_TYPE_VALUE);
+    /// tag.simple_tags.push(MatroskaSimpleTag::new("TITLE", "The Downward Spiral"));
+    /// let tags = MatroskaTags { tags: vec![tag] };
+    /// let new_bytes = tags.write_to_bytes(&file_bytes)?;
+    ///
+    /// assert_eq!(new_bytes.len(), file_bytes.len() + tags.to_element().total_size());
+    /// assert_eq!(MatroskaTags::read_from_reader(&mut Cursor::new(new_bytes))?, tags);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_to_bytes(&self, file_bytes: &[u8]) -> Result<Vec<u8>> {
+        let segment = scan(file_bytes, 0, file_bytes.len())?
+            .into_iter()
+            .find(|span| span.id == SEGMENT)
+            .ok_or(anyhow!("No Matroska segment found!"))?;
+        let children = scan(file_bytes, segment.data_start, segment.end)?;
+        let new_tags = self.to_element().to_bytes();
+        let mut bytes = file_bytes.to_vec();
+
+        let tags_index = children.iter().position(|span| span.id == TAGS);
+        if let Some(index) = tags_index {
+            let region_end = void_run_end(&children, index + 1).unwrap_or(children[index].end);
+            let region = children[index].start..region_end;
+            if fits(region.len(), new_tags.len()) {
+                bytes.splice(region.clone(), fill(&new_tags, region.len())?);
+                return Ok(bytes);
+            }
+        }
+
+        let void_run = (0..children.len()).find_map(|index| {
+            let is_run_start =
+                children[index].id == VOID && (index == 0 || children[index - 1].id != VOID);
+            let end = void_run_end(&children, index)?;
+            (is_run_start && fits(end - children[index].start, new_tags.len()))
+                .then_some(children[index].start..end)
+        });
+        if let Some(index) = tags_index {
+            let old_tags = children[index].start..children[index].end;
+            bytes.splice(old_tags.clone(), void(old_tags.len())?);
+        }
+
+        let tags_start = match void_run {
+            Some(region) => {
+                bytes.splice(region.clone(), fill(&new_tags, region.len())?);
+                region.start
+            }
+            None => {
+                let size_start = segment.start + encode_id(SEGMENT).len();
+                let size_length = segment.data_start - size_start;
+                if let (Some(size), _) = read_size(&file_bytes[size_start..])? {
+                    let new_size = size + new_tags.len() as u64;
+                    bytes.splice(
+                        size_start..segment.data_start,
+                        encode_size_with_length(new_size, size_length)?,
+                    );
+                }
+                bytes.splice(segment.end..segment.end, new_tags);
+                segment.end
+            }
+        };
+
+        let seek_position = (tags_start - segment.data_start) as u64;
+        for seek_head in children.iter().filter(|span| span.id == SEEK_HEAD) {
+            for seek in scan(&bytes, seek_head.data_start, seek_head.end)? {
+                if seek.id != SEEK {
+                    continue;
+                }
+                let entries = scan(&bytes, seek.data_start, seek.end)?;
+                let points_to_tags = entries.iter().any(|entry| {
+                    entry.id == SEEK_ID && bytes[entry.data_start..entry.end] == encode_id(TAGS)
+                });
+                let position = entries.iter().find(|entry| entry.id == SEEK_POSITION);
+                if let (true, Some(position)) = (points_to_tags, position) {
+                    let length = position.end - position.data_start;
+                    let position_bytes = seek_position.to_be_bytes();
+                    if position_bytes[..8 - length.min(8)]
+                        .iter()
+                        .any(|byte| *byte != 0)
+                    {
+                        return Err(anyhow!("Matroska seek position doesn't fit!"));
+                    }
+                    bytes.splice(
+                        position.data_start..position.end,
+                        position_bytes[8 - length.min(8)..].to_vec(),
+                    );
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Returns these tags as a `Tags` element.
+    pub fn to_element(&self) -> EbmlElement {
+        let children: Vec<EbmlElement> = self.tags.iter().map(|tag| tag.to_element()).collect();
+        EbmlElement::new_master(TAGS, &children)
+    }
+
+    /// Reads and parses the data of a `Tags` element of the given size.
+    fn read_element<R: Read>(reader: &mut R, size: u64) -> Result<MatroskaTags> {
+        let mut data = vec![0u8; size as usize];
+        reader.read_exact(&mut data)?;
+        Self::parse(&EbmlElement::new(TAGS, data))
+    }
+
+    /// Returns the position of the `Tags` element in the given seek head data, if there is one.
+    fn seek_position(seek_head: &[u8]) -> Result<Option<u64>> {
+        for seek in EbmlElement::parse_all(seek_head)? {
+            let entries = seek.children()?;
+            let points_to_tags = entries
+                .iter()
+                .any(|entry| entry.id == SEEK_ID && entry.data == encode_id(TAGS));
+            let position = entries.iter().find(|entry| entry.id == SEEK_POSITION);
+            if let (true, Some(position)) = (points_to_tags, position) {
+                return Ok(Some(position.uint()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Reads the ID and size of the next element, or returns `None` at the end of the stream.
+fn read_element_header<R: Read>(reader: &mut R) -> Result<Option<(u32, Option<u64>)>> {
+    let mut bytes = [0u8; 12];
+    if let Err(error) = reader.read_exact(&mut bytes[0..1]) {
+        return match error.kind() {
+            ErrorKind::UnexpectedEof => Ok(None),
+            _ => Err(error.into()),
+        };
+    }
+    let id_length = bytes[0].leading_zeros() as usize + 1;
+    if id_length > 4 {
+        return Err(anyhow!("EBML element ID is too long!"));
+    }
+    reader.read_exact(&mut bytes[1..id_length + 1])?;
+    let size_length = bytes[id_length].leading_zeros() as usize + 1;
+    if size_length > 8 {
+        return Err(anyhow!("EBML variable-length integer is invalid!"));
+    }
+    reader.read_exact(&mut bytes[id_length + 1..id_length + size_length])?;
+    let (id, _) = read_id(&bytes)?;
+    let (size, _) = read_size(&bytes[id_length..])?;
+    Ok(Some((id, size)))
+}
+
+/// Returns the positions of the consecutive elements between the given indexes.
+/// An element of unknown size is taken to run until the end.
+fn scan(bytes: &[u8], start: usize, end: usize) -> Result<Vec<ElementSpan>> {
+    let mut spans = vec![];
+    let mut index = start;
+    while index < end {
+        let (id, id_length) = read_id(&bytes[index..end])?;
+        let (size, size_length) = read_size(&bytes[index + id_length..end])?;
+        let data_start = index + id_length + size_length;
+        let element_end = match size {
+            Some(size) => data_start + size as usize,
+            None => end,
+        };
+        if element_end > end {
+            return Err(anyhow!("EBML element {:X} is truncated!", id));
+        }
+        spans.push(ElementSpan {
+            id,
+            start: index,
+            data_start,
+            end: element_end,
+        });
+        index = element_end;
+    }
+    Ok(spans)
+}
+
+/// Returns the end of the run of `Void` elements starting at the given index, if there is one.
+fn void_run_end(spans: &[ElementSpan], index: usize) -> Option<usize> {
+    spans[index.min(spans.len())..]
+        .iter()
+        .take_while(|span| span.id == VOID)
+        .last()
+        .map(|span| span.end)
+}
+
+/// Returns whether an element of the given size fits into a region of the given size,
+/// leaving either nothing or enough room for a `Void` element.
+fn fits(region_size: usize, element_size: usize) -> bool {
+    region_size == element_size || region_size >= element_size + 2
+}
+
+/// Returns the given element bytes followed by a `Void` element filling the rest of the region.
+fn fill(element_bytes: &[u8], region_size: usize) -> Result<Vec<u8>> {
+    let mut bytes = element_bytes.to_vec();
+    if region_size > element_bytes.len() {
+        bytes.extend(void(region_size - element_bytes.len())?);
+    }
+    Ok(bytes)
+}
+
+/// Returns a `Void` element taking up exactly the given number of bytes.
+///
+/// # Arguments
+///
+/// * `size` - The total size of the element, which must be at least 2.
+fn void(size: usize) -> Result<Vec<u8>> {
+    let size_length = match size {
+        0 | 1 => return Err(anyhow!("Matroska void elements need at least 2 bytes!")),
+        2..=128 => 1,
+        _ => 8,
+    };
+    let data_size = size - 1 - size_length;
+    let mut bytes = encode_id(VOID);
+    bytes.extend(encode_size_with_length(data_size as u64, size_length)?);
+    bytes.resize(size, 0);
+    Ok(bytes)
+}
@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+
+/// The types of atoms that only contain other atoms.
+const CONTAINER_TYPES: [&str; 10] = [
+    "dinf", "edts", "ilst", "mdia", "meta", "minf", "moov", "stbl", "trak", "udta",
+];
+
+/// Represents an atom (box) in an MPEG-4 file.
+/// See [the QuickTime file format](https://developer.apple.com/documentation/quicktime-file-format)
+/// for more detail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mp4Atom {
+    /// The four character type of the atom. Bytes are decoded as ISO-8859-1, so © is kept.
+    pub atom_type: String,
+
+    /// Whether or not the size of the atom is stored in 64 bits.
+    pub has_extended_size: bool,
+
+    /// The payload of the atom before its children, or all of it if the atom has no children.
+    /// For a `meta` atom, this is its version and flags.
+    pub data: Vec<u8>,
+
+    /// The atoms contained in this atom.
+    pub children: Vec<Mp4Atom>,
+}
+
+impl Mp4Atom {
+    /// Returns a new atom with the given type and payload, and no children.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - The four character type of the atom.
+    /// * `data` - The payload of the atom.
+    pub fn new(atom_type: &str, data: Vec<u8>) -> Mp4Atom {
+        Mp4Atom {
+            atom_type: String::from(atom_type),
+            has_extended_size: false,
+            data,
+            children: vec![],
+        }
+    }
+
+    /// Returns a new atom with the given type that contains the given atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - The four character type of the atom.
+    /// * `data` - The payload of the atom before its children.
+    /// * `children` - The atoms to contain.
+    pub fn new_container(atom_type: &str, data: Vec<u8>, children: Vec<Mp4Atom>) -> Mp4Atom {
+        Mp4Atom {
+            atom_type: String::from(atom_type),
+            has_extended_size: false,
+            data,
+            children,
+        }
+    }
+
+    /// Parses an atom from the start of the given bytes.
+    /// Returns the atom and the number of bytes it took up.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the atom from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_atom::*;
+    /// let bytes = [
+    ///     0, 0, 0, 16, b'u', b'd', b't', b'a',
+    ///     0, 0, 0, 8, b'f', b'r', b'e', b'e',
+    /// ];
+    ///
+    /// let (atom, size) = Mp4Atom::parse(&bytes)?;
+    ///
+    /// assert_eq!(atom.atom_type, "udta");
+    /// assert_eq!(atom.children.len(), 1);
+    /// assert_eq!(atom.children[0].atom_type, "free");
+    /// assert_eq!(size, 16);
+    /// assert_eq!(atom.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(Mp4Atom, usize)> {
+        Self::parse_atom(bytes, false)
+    }
+
+    /// Parses all of the consecutive atoms in the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the atoms from.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<Mp4Atom>> {
+        Self::parse_children(bytes, false)
+    }
+
+    /// Returns the first descendant atom at the given path of atom types.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The types of the atoms to descend through, ending with the type to find.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_atom::*;
+    /// let ilst = Mp4Atom::new_container("ilst", vec![], vec![]);
+    /// let meta = Mp4Atom::new_container("meta", vec![0, 0, 0, 0], vec![ilst]);
+    /// let udta = Mp4Atom::new_container("udta", vec![], vec![meta]);
+    ///
+    /// assert!(udta.find(&["meta", "ilst"]).is_some());
+    /// assert!(udta.find(&["ilst"]).is_none());
+    /// ```
+    pub fn find(&self, path: &[&str]) -> Option<&Mp4Atom> {
+        match path.split_first() {
+            Some((atom_type, rest)) => self
+                .children
+                .iter()
+                .find(|child| child.atom_type == *atom_type)
+                .and_then(|child| child.find(rest)),
+            None => Some(self),
+        }
+    }
+
+    /// Returns the first descendant atom at the given path of atom types, mutably.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The types of the atoms to descend through, ending with the type to find.
+    pub fn find_mut(&mut self, path: &[&str]) -> Option<&mut Mp4Atom> {
+        match path.split_first() {
+            Some((atom_type, rest)) => self
+                .children
+                .iter_mut()
+                .find(|child| child.atom_type == *atom_type)
+                .and_then(|child| child.find_mut(rest)),
+            None => Some(self),
+        }
+    }
+
+    /// Returns all of the descendant atoms of the given type, depth first.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - The type of the atoms to find.
+    pub fn find_all_mut(&mut self, atom_type: &str) -> Vec<&mut Mp4Atom> {
+        let mut atoms = vec![];
+        for child in self.children.iter_mut() {
+            if child.atom_type == atom_type {
+                atoms.push(child);
+            } else {
+                atoms.extend(child.find_all_mut(atom_type));
+            }
+        }
+        atoms
+    }
+
+    /// Returns the total size of this atom in bytes, including its header.
+    pub fn total_size(&self) -> u64 {
+        let payload_size = self.data.len() as u64
+            + self
+                .children
+                .iter()
+                .map(|child| child.total_size())
+                .sum::<u64>();
+        match self.has_extended_size || payload_size + 8 > u32::MAX as u64 {
+            true => payload_size + 16,
+            false => payload_size + 8,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let total_size = self.total_size();
+        let mut atom_bytes: Vec<u8> = vec![];
+        if self.has_extended_size || total_size > u32::MAX as u64 {
+            atom_bytes.extend(1u32.to_be_bytes());
+            atom_bytes.extend(encode_atom_type(&self.atom_type));
+            atom_bytes.extend(total_size.to_be_bytes());
+        } else {
+            atom_bytes.extend((total_size as u32).to_be_bytes());
+            atom_bytes.extend(encode_atom_type(&self.atom_type));
+        }
+        atom_bytes.extend(&self.data);
+        for child in &self.children {
+            atom_bytes.extend(child.to_bytes());
+        }
+        atom_bytes
+    }
+
+    fn parse_atom(bytes: &[u8], is_container: bool) -> Result<(Mp4Atom, usize)> {
+        let header = bytes
+            .get(0..8)
+            .ok_or(anyhow!("MP4 atom header is truncated!"))?;
+        let size = u32::from_be_bytes(header[0..4].try_into()?) as u64;
+        let atom_type: String = header[4..8].iter().map(|&b| b as char).collect();
+        let (size, header_size, has_extended_size) = match size {
+            0 => (bytes.len() as u64, 8, false),
+            1 => {
+                let extended_size = bytes
+                    .get(8..16)
+                    .ok_or(anyhow!("MP4 atom extended size is truncated!"))?;
+                (u64::from_be_bytes(extended_size.try_into()?), 16, true)
+            }
+            size => (size, 8, false),
+        };
+        if size < header_size as u64 || size > bytes.len() as u64 {
+            return Err(anyhow!("MP4 atom {} has an invalid size!", atom_type));
+        }
+        let payload = &bytes[header_size..size as usize];
+
+        let is_container = is_container || CONTAINER_TYPES.contains(&atom_type.as_str());
+        let children = match (is_container, atom_type.as_str()) {
+            // NOTE: QuickTime meta atoms don't have a version and flags
+            (true, "meta") if payload.len() >= 4 && payload.get(4..8) != Some(b"hdlr") => {
+                Self::parse_children(&payload[4..], false).map(|children| (4, children))
+            }
+            (true, "ilst") => Self::parse_children(payload, true).map(|children| (0, children)),
+            (true, _) => Self::parse_children(payload, false).map(|children| (0, children)),
+            (false, _) => Err(anyhow!("MP4 atom {} is not a container!", atom_type)),
+        };
+        // NOTE: anything that can't be parsed as children is kept as is
+        let (data, children) = match children {
+            Ok((data_size, children)) => (payload[..data_size].to_vec(), children),
+            Err(_) => (payload.to_vec(), vec![]),
+        };
+
+        Ok((
+            Mp4Atom {
+                atom_type,
+                has_extended_size,
+                data,
+                children,
+            },
+            size as usize,
+        ))
+    }
+
+    fn parse_children(bytes: &[u8], are_containers: bool) -> Result<Vec<Mp4Atom>> {
+        let mut atoms = vec![];
+        let mut index = 0;
+        while index < bytes.len() {
+            let (atom, size) = Self::parse_atom(&bytes[index..], are_containers)?;
+            atoms.push(atom);
+            index += size;
+        }
+        Ok(atoms)
+    }
+}
+
+fn encode_atom_type(atom_type: &str) -> Vec<u8> {
+    atom_type.chars().map(|c| c as u8).collect()
+}
@@ -0,0 +1,94 @@
+use crate::mp4_atom::Mp4Atom;
+use crate::mp4_data_type::Mp4DataType;
+use anyhow::{anyhow, Result};
+
+/// Represents the value of an MP4 `data` atom in a metadata item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mp4Data {
+    /// The type of the value.
+    pub data_type: Mp4DataType,
+
+    /// The locale of the value. This is 0 for the default locale.
+    pub locale: u32,
+
+    /// The bytes of the value.
+    pub value: Vec<u8>,
+}
+
+impl Mp4Data {
+    /// Returns new data of the given type and value in the default locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - The type of the value.
+    /// * `value` - The bytes of the value.
+    pub fn new(data_type: Mp4DataType, value: Vec<u8>) -> Mp4Data {
+        Mp4Data {
+            data_type,
+            locale: 0,
+            value,
+        }
+    }
+
+    /// Parses data from the payload of a `data` atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The payload of the `data` atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_data::*;
+    /// # use sisko_lib::mp4_data_type::*;
+    /// let bytes = [0, 0, 0, 1, 0, 0, 0, 0, b'H', b'u', b'r', b't'];
+    ///
+    /// let data = Mp4Data::parse(&bytes)?;
+    ///
+    /// assert_eq!(data.data_type, Mp4DataType::Utf8);
+    /// assert_eq!(data.text(), Some(String::from("Hurt")));
+    /// assert_eq!(data.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<Mp4Data> {
+        if bytes.len() < 8 {
+            return Err(anyhow!("MP4 data atom is truncated!"));
+        }
+        let data_type = Mp4DataType::parse(u32::from_be_bytes(bytes[0..4].try_into()?));
+        let locale = u32::from_be_bytes(bytes[4..8].try_into()?);
+        Ok(Mp4Data {
+            data_type,
+            locale,
+            value: bytes[8..].to_vec(),
+        })
+    }
+
+    /// Returns the value as text, if it is text.
+    pub fn text(&self) -> Option<String> {
+        match self.data_type {
+            Mp4DataType::Utf8 => Some(String::from_utf8_lossy(&self.value).to_string()),
+            Mp4DataType::Utf16 => {
+                let units: Vec<u16> = self
+                    .value
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                Some(String::from_utf16_lossy(&units))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns this data as a `data` atom.
+    pub fn to_atom(&self) -> Mp4Atom {
+        Mp4Atom::new("data", self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data_bytes: Vec<u8> = vec![];
+        data_bytes.extend(self.data_type.to_bytes());
+        data_bytes.extend(self.locale.to_be_bytes());
+        data_bytes.extend(&self.value);
+        data_bytes
+    }
+}
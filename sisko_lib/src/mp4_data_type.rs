@@ -0,0 +1,105 @@
+/// Represents the well-known type of the value in an MP4 `data` atom.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mp4DataType {
+    /// Binary data whose type is implied by the item it belongs to (e.g. `trkn`).
+    Implicit,
+
+    /// UTF-8 text without a terminator.
+    Utf8,
+
+    /// UTF-16 (big-endian) text without a terminator.
+    Utf16,
+
+    /// A JPEG image.
+    Jpeg,
+
+    /// A PNG image.
+    Png,
+
+    /// A big-endian signed integer of 1, 2, 3, 4, or 8 bytes.
+    BeSignedInteger,
+
+    /// A big-endian unsigned integer of 1, 2, 3, 4, or 8 bytes.
+    BeUnsignedInteger,
+
+    /// A BMP image.
+    Bmp,
+
+    /// Any other type.
+    Other(u32),
+}
+
+impl Mp4DataType {
+    /// Parses the data type from the given type indicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_indicator` - The type indicator of a `data` atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_data_type::*;
+    /// assert_eq!(Mp4DataType::parse(1), Mp4DataType::Utf8);
+    /// assert_eq!(Mp4DataType::parse(13), Mp4DataType::Jpeg);
+    /// assert_eq!(Mp4DataType::parse(66), Mp4DataType::Other(66));
+    /// ```
+    pub fn parse(type_indicator: u32) -> Mp4DataType {
+        match type_indicator {
+            0 => Mp4DataType::Implicit,
+            1 => Mp4DataType::Utf8,
+            2 => Mp4DataType::Utf16,
+            13 => Mp4DataType::Jpeg,
+            14 => Mp4DataType::Png,
+            21 => Mp4DataType::BeSignedInteger,
+            22 => Mp4DataType::BeUnsignedInteger,
+            27 => Mp4DataType::Bmp,
+            other => Mp4DataType::Other(other),
+        }
+    }
+
+    /// Returns the data type for the given picture MIME type, if it is supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `mime_type` - The MIME type of a picture.
+    pub fn from_mime_type(mime_type: &str) -> Option<Mp4DataType> {
+        match mime_type {
+            "image/jpeg" | "image/jpg" => Some(Mp4DataType::Jpeg),
+            "image/png" => Some(Mp4DataType::Png),
+            "image/bmp" => Some(Mp4DataType::Bmp),
+            _ => None,
+        }
+    }
+
+    /// Returns the MIME type of the picture with this data type, if it is a picture.
+    pub fn mime_type(&self) -> Option<&str> {
+        match self {
+            Mp4DataType::Jpeg => Some("image/jpeg"),
+            Mp4DataType::Png => Some("image/png"),
+            Mp4DataType::Bmp => Some("image/bmp"),
+            _ => None,
+        }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_data_type::*;
+    /// assert_eq!(Mp4DataType::Png.to_bytes(), vec![0, 0, 0, 14]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let type_indicator: u32 = match self {
+            Mp4DataType::Implicit => 0,
+            Mp4DataType::Utf8 => 1,
+            Mp4DataType::Utf16 => 2,
+            Mp4DataType::Jpeg => 13,
+            Mp4DataType::Png => 14,
+            Mp4DataType::BeSignedInteger => 21,
+            Mp4DataType::BeUnsignedInteger => 22,
+            Mp4DataType::Bmp => 27,
+            Mp4DataType::Other(other) => *other,
+        };
+        type_indicator.to_be_bytes().to_vec()
+    }
+}
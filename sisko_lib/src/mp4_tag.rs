@@ -0,0 +1,215 @@
+use crate::mp4_atom::Mp4Atom;
+use crate::mp4_tag_item::Mp4TagItem;
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// The path of the item list atom from the `moov` atom.
+const ILST_PATH: [&str; 3] = ["udta", "meta", "ilst"];
+
+/// Represents the iTunes-style metadata of an MPEG-4 file: the items in `moov/udta/meta/ilst`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Mp4Tag {
+    /// The metadata items.
+    pub items: Vec<Mp4TagItem>,
+}
+
+impl Mp4Tag {
+    /// Parses the tag from the given `moov` atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `moov` - The `moov` atom of an MPEG-4 file.
+    pub fn parse(moov: &Mp4Atom) -> Result<Mp4Tag> {
+        let items = match moov.find(&ILST_PATH) {
+            Some(ilst) => ilst
+                .children
+                .iter()
+                .map(Mp4TagItem::from_atom)
+                .collect::<Result<Vec<Mp4TagItem>>>()?,
+            None => vec![],
+        };
+        Ok(Mp4Tag { items })
+    }
+
+    /// Returns the first item with the given name, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the item to get.
+    pub fn get(&self, name: &str) -> Option<&Mp4TagItem> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Reads the tag from the MPEG-4 file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read the tag from.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Mp4Tag> {
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(file);
+        Self::read_from_reader(&mut reader)
+    }
+
+    /// Reads the tag from the given MPEG-4 stream.
+    /// Only the `moov` atom is read; other top-level atoms are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to read the tag from.
+    pub fn read_from_reader<R: Read + Seek>(reader: &mut R) -> Result<Mp4Tag> {
+        loop {
+            let mut header = [0u8; 8];
+            reader.read_exact(&mut header)?;
+            let (size, header_size) = match u32::from_be_bytes(header[0..4].try_into()?) {
+                1 => {
+                    let mut extended_size = [0u8; 8];
+                    reader.read_exact(&mut extended_size)?;
+                    (u64::from_be_bytes(extended_size), 16)
+                }
+                0 => return Err(anyhow!("No MP4 moov atom found!")),
+                size => (size as u64, 8),
+            };
+            if size < header_size {
+                return Err(anyhow!("MP4 atom has an invalid size!"));
+            }
+
+            if &header[4..8] == b"moov" {
+                reader.seek(SeekFrom::Current(-(header_size as i64)))?;
+                let mut moov_bytes = vec![0u8; size as usize];
+                reader.read_exact(&mut moov_bytes)?;
+                let (moov, _) = Mp4Atom::parse(&moov_bytes)?;
+                return Self::parse(&moov);
+            }
+            reader.seek(SeekFrom::Current((size - header_size) as i64))?;
+        }
+    }
+
+    /// Returns the given MPEG-4 file's bytes with its metadata items replaced by this tag's.
+    /// The chunk offsets in `stco` and `co64` atoms are fixed up if the `moov` atom
+    /// changes size and comes before the media data.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_bytes` - The bytes of the MPEG-4 file to write the tag to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_atom::*;
+    /// # use sisko_lib::mp4_tag::*;
+    /// # use sisko_lib::mp4_tag_item::*;
+    /// let stco = Mp4Atom::new("stco", vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 68]);
+    /// let stbl = Mp4Atom::new_container("stbl", vec![], vec![stco]);
+    /// let minf = Mp4Atom::new_container("minf", vec![], vec![stbl]);
+    /// let mdia = Mp4Atom::new_container("mdia", vec![], vec![minf]);
+    /// let trak = Mp4Atom::new_container("trak", vec![], vec![mdia]);
+    /// let moov = Mp4Atom::new_container("moov", vec![], vec![trak]);
+    /// let mdat = Mp4Atom::new("mdat", b"audio".to_vec());
+    /// let mut file_bytes = moov.to_bytes();
+    /// file_bytes.extend(mdat.to_bytes());
+    /// assert_eq!(&file_bytes[68..73], b"audio");
+    ///
+    /// let tag = Mp4Tag {
+    ///     items: vec![Mp4TagItem::new_text("\u{a9}nam", &[String::from("Hurt")])],
+    /// };
+    /// let new_bytes = tag.write_to_bytes(&file_bytes)?;
+    ///
+    /// let atoms = Mp4Atom::parse_all(&new_bytes)?;
+    /// let stco = atoms[0].find(&["trak", "mdia", "minf", "stbl", "stco"]).unwrap();
+    /// let offset = u32::from_be_bytes(stco.data[8..12].try_into()?) as usize;
+    /// assert_eq!(&new_bytes[offset..offset + 5], b"audio");
+    /// assert_eq!(Mp4Tag::parse(&atoms[0])?, tag);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_to_bytes(&self, file_bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut atoms = Mp4Atom::parse_all(file_bytes)?;
+        let moov_index = atoms
+            .iter()
+            .position(|atom| atom.atom_type == "moov")
+            .ok_or(anyhow!("No MP4 moov atom found!"))?;
+        let moov_start: u64 = atoms[..moov_index]
+            .iter()
+            .map(|atom| atom.total_size())
+            .sum();
+        let moov = &mut atoms[moov_index];
+        let old_moov_size = moov.total_size();
+
+        let ilst = Self::find_or_create_ilst(moov)?;
+        ilst.children = self
+            .items
+            .iter()
+            .map(|item| item.to_atom())
+            .collect::<Result<Vec<Mp4Atom>>>()?;
+
+        let offset_delta = moov.total_size() as i64 - old_moov_size as i64;
+        if offset_delta != 0 {
+            let old_moov_end = moov_start + old_moov_size;
+            for stco in moov.find_all_mut("stco") {
+                fix_chunk_offsets::<4>(stco, old_moov_end, offset_delta)?;
+            }
+            for co64 in moov.find_all_mut("co64") {
+                fix_chunk_offsets::<8>(co64, old_moov_end, offset_delta)?;
+            }
+        }
+
+        Ok(atoms.iter().flat_map(|atom| atom.to_bytes()).collect())
+    }
+
+    fn find_or_create_ilst(moov: &mut Mp4Atom) -> Result<&mut Mp4Atom> {
+        if moov.find(&ILST_PATH[..1]).is_none() {
+            moov.children
+                .push(Mp4Atom::new_container("udta", vec![], vec![]));
+        }
+        if moov.find(&ILST_PATH[..2]).is_none() {
+            let mut hdlr_data: Vec<u8> = vec![0; 8];
+            hdlr_data.extend(b"mdirappl");
+            hdlr_data.extend([0; 9]);
+            let hdlr = Mp4Atom::new("hdlr", hdlr_data);
+            let meta = Mp4Atom::new_container("meta", vec![0, 0, 0, 0], vec![hdlr]);
+            moov.find_mut(&ILST_PATH[..1])
+                .ok_or(anyhow!("Failed to add MP4 udta atom!"))?
+                .children
+                .push(meta);
+        }
+        if moov.find(&ILST_PATH).is_none() {
+            moov.find_mut(&ILST_PATH[..2])
+                .ok_or(anyhow!("Failed to add MP4 meta atom!"))?
+                .children
+                .push(Mp4Atom::new_container("ilst", vec![], vec![]));
+        }
+        moov.find_mut(&ILST_PATH)
+            .ok_or(anyhow!("Failed to add MP4 ilst atom!"))
+    }
+}
+
+/// Shifts the chunk offsets in a `stco` (4 byte) or `co64` (8 byte) atom
+/// that point past the given position by the given delta.
+fn fix_chunk_offsets<const N: usize>(atom: &mut Mp4Atom, position: u64, delta: i64) -> Result<()> {
+    let entry_count = u32::from_be_bytes(
+        atom.data
+            .get(4..8)
+            .ok_or(anyhow!("MP4 {} atom is truncated!", atom.atom_type))?
+            .try_into()?,
+    ) as usize;
+    let entries = atom
+        .data
+        .get_mut(8..8 + entry_count * N)
+        .ok_or(anyhow!("MP4 {} atom is truncated!", atom.atom_type))?;
+    for entry in entries.chunks_exact_mut(N) {
+        let mut offset_bytes = [0u8; 8];
+        offset_bytes[8 - N..].copy_from_slice(entry);
+        let offset = u64::from_be_bytes(offset_bytes);
+        if offset < position {
+            continue;
+        }
+        let offset = offset.checked_add_signed(delta).unwrap_or(0);
+        if N == 4 && offset > u32::MAX as u64 {
+            return Err(anyhow!("MP4 chunk offset no longer fits in a stco atom!"));
+        }
+        entry.copy_from_slice(&offset.to_be_bytes()[8 - N..]);
+    }
+    Ok(())
+}
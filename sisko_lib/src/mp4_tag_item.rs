@@ -0,0 +1,167 @@
+use crate::mp4_atom::Mp4Atom;
+use crate::mp4_data::Mp4Data;
+use crate::mp4_data_type::Mp4DataType;
+use anyhow::{anyhow, Result};
+
+/// The atom type of freeform metadata items.
+pub const FREEFORM: &str = "----";
+
+/// The mean of the freeform items iTunes and MusicBrainz Picard write.
+pub const ITUNES_MEAN: &str = "com.apple.iTunes";
+
+/// Represents an item in an MP4 `ilst` atom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mp4TagItem {
+    /// The name of the item.
+    /// This is the atom type (e.g. `©nam`), or `----:mean:name` for freeform items.
+    pub name: String,
+
+    /// The values of the item.
+    pub data: Vec<Mp4Data>,
+}
+
+impl Mp4TagItem {
+    /// Returns a new item with the given text values.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the item.
+    /// * `values` - The text values of the item.
+    pub fn new_text(name: &str, values: &[String]) -> Mp4TagItem {
+        Mp4TagItem {
+            name: String::from(name),
+            data: values
+                .iter()
+                .map(|value| Mp4Data::new(Mp4DataType::Utf8, value.clone().into_bytes()))
+                .collect(),
+        }
+    }
+
+    /// Returns a new `trkn` or `disk` item with the given number and total.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the item; either `trkn` or `disk`.
+    /// * `number` - The track or disc number.
+    /// * `total` - The total number of tracks or discs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_tag_item::*;
+    /// let item = Mp4TagItem::new_number_pair("trkn", 3, 12);
+    ///
+    /// assert_eq!(item.data[0].value, vec![0, 0, 0, 3, 0, 12, 0, 0]);
+    /// assert_eq!(item.number_pair(), Some((3, 12)));
+    /// ```
+    pub fn new_number_pair(name: &str, number: u16, total: u16) -> Mp4TagItem {
+        let mut value: Vec<u8> = vec![0, 0];
+        value.extend(number.to_be_bytes());
+        value.extend(total.to_be_bytes());
+        if name == "trkn" {
+            value.extend([0, 0]);
+        }
+        Mp4TagItem {
+            name: String::from(name),
+            data: vec![Mp4Data::new(Mp4DataType::Implicit, value)],
+        }
+    }
+
+    /// Parses an item from an atom in an `ilst` atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - The item atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mp4_atom::*;
+    /// # use sisko_lib::mp4_data::*;
+    /// # use sisko_lib::mp4_data_type::*;
+    /// # use sisko_lib::mp4_tag_item::*;
+    /// let item = Mp4TagItem::new_text(
+    ///     "----:com.apple.iTunes:MusicBrainz Album Id",
+    ///     &[String::from("e1b7a5c4-2a8a-4ab1-8c4d-28a6bba4fc32")],
+    /// );
+    ///
+    /// let atom = item.to_atom()?;
+    ///
+    /// assert_eq!(atom.atom_type, FREEFORM);
+    /// assert_eq!(atom.children[0].atom_type, "mean");
+    /// assert_eq!(atom.children[1].atom_type, "name");
+    /// assert_eq!(Mp4TagItem::from_atom(&atom)?, item);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_atom(atom: &Mp4Atom) -> Result<Mp4TagItem> {
+        let name = match atom.atom_type.as_str() {
+            FREEFORM => {
+                let mean = freeform_string(atom, "mean")?;
+                let name = freeform_string(atom, "name")?;
+                format!("{}:{}:{}", FREEFORM, mean, name)
+            }
+            atom_type => String::from(atom_type),
+        };
+        let data = atom
+            .children
+            .iter()
+            .filter(|child| child.atom_type == "data")
+            .map(|child| Mp4Data::parse(&child.data))
+            .collect::<Result<Vec<Mp4Data>>>()?;
+        Ok(Mp4TagItem { name, data })
+    }
+
+    /// Returns the number and total of a `trkn` or `disk` item.
+    pub fn number_pair(&self) -> Option<(u16, u16)> {
+        let value = &self.data.first()?.value;
+        let number = u16::from_be_bytes(value.get(2..4)?.try_into().ok()?);
+        let total = u16::from_be_bytes(value.get(4..6)?.try_into().ok()?);
+        Some((number, total))
+    }
+
+    /// Returns the text values of this item.
+    pub fn texts(&self) -> Vec<String> {
+        self.data.iter().filter_map(|data| data.text()).collect()
+    }
+
+    /// Returns this item as an atom for an `ilst` atom.
+    pub fn to_atom(&self) -> Result<Mp4Atom> {
+        let mut children = vec![];
+        let atom_type = match self.name.split_once(':') {
+            Some((FREEFORM, mean_and_name)) => {
+                let (mean, name) = mean_and_name
+                    .split_once(':')
+                    .ok_or(anyhow!("Freeform MP4 item {} has no name!", self.name))?;
+                children.push(freeform_atom("mean", mean));
+                children.push(freeform_atom("name", name));
+                FREEFORM
+            }
+            _ => self.name.as_str(),
+        };
+        if atom_type.chars().count() != 4 {
+            return Err(anyhow!(
+                "MP4 item {} is not a four character type!",
+                atom_type
+            ));
+        }
+        children.extend(self.data.iter().map(|data| data.to_atom()));
+        Ok(Mp4Atom::new_container(atom_type, vec![], children))
+    }
+}
+
+fn freeform_atom(atom_type: &str, value: &str) -> Mp4Atom {
+    let mut data: Vec<u8> = vec![0, 0, 0, 0];
+    data.extend(value.as_bytes());
+    Mp4Atom::new(atom_type, data)
+}
+
+fn freeform_string(atom: &Mp4Atom, atom_type: &str) -> Result<String> {
+    let child = atom
+        .find(&[atom_type])
+        .ok_or(anyhow!("Freeform MP4 item has no {} atom!", atom_type))?;
+    let value = child
+        .data
+        .get(4..)
+        .ok_or(anyhow!("Freeform MP4 {} atom is truncated!", atom_type))?;
+    Ok(String::from_utf8_lossy(value).to_string())
+}
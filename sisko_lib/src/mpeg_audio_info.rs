@@ -0,0 +1,230 @@
+use crate::id3v2_tag_size;
+use crate::lame_header::LameHeader;
+use crate::mpeg_frame_header::{MpegFrameHeader, FRAME_HEADER_SIZE};
+use crate::mpeg_layer::MpegLayer;
+use crate::vbri_header::{VbriHeader, VBRI_HEADER_OFFSET};
+use crate::xing_header::XingHeader;
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+/// The size of an ID3v1 tag at the end of a stream.
+const ID3V1_TAG_SIZE: usize = 128;
+
+/// Represents the properties of an MPEG audio stream, found by scanning its frames.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MpegAudioInfo {
+    /// The header of the first frame.
+    pub header: MpegFrameHeader,
+
+    /// The Xing or Info header in the first frame, if any.
+    pub xing_header: Option<XingHeader>,
+
+    /// The VBRI header in the first frame, if any.
+    pub vbri_header: Option<VbriHeader>,
+
+    /// The number of audio frames in the stream.
+    pub frame_count: u64,
+
+    /// The number of bytes of audio frames in the stream.
+    pub audio_size: u64,
+}
+
+impl MpegAudioInfo {
+    /// Parses the properties of the MPEG audio stream in the given bytes.
+    /// The frame count comes from a Xing or VBRI header if there is one; otherwise, every frame
+    /// is counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the file, including any ID3v2 and ID3v1 tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mpeg_audio_info::*;
+    /// let mut frame = vec![0xFF, 0xFB, 0x90, 0x64];
+    /// frame.resize(417, 0);
+    /// let mut bytes = frame.repeat(3);
+    /// bytes.extend(b"TAG");
+    /// bytes.resize(bytes.len() + 125, 0);
+    ///
+    /// let info = MpegAudioInfo::parse(&bytes)?;
+    ///
+    /// assert_eq!(info.frame_count, 3);
+    /// assert_eq!(info.audio_size, 1251);
+    /// assert_eq!(info.duration().as_millis(), 78);
+    ///
+    /// frame[36..48].copy_from_slice(b"Xing\0\0\0\x01\0\0\x03\xE8");
+    /// let info = MpegAudioInfo::parse(&frame.repeat(2))?;
+    ///
+    /// assert_eq!(info.frame_count, 1000);
+    /// assert_eq!(info.duration().as_secs(), 26);
+    /// assert!(info.is_vbr());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<MpegAudioInfo> {
+        let mut start = 0;
+        while let Some(size) = bytes.get(start..).and_then(id3v2_tag_size) {
+            start += size as usize;
+        }
+        let mut end = bytes.len();
+        if end >= start + ID3V1_TAG_SIZE && bytes[end - ID3V1_TAG_SIZE..].starts_with(b"TAG") {
+            end -= ID3V1_TAG_SIZE;
+        }
+        let bytes = bytes
+            .get(..end)
+            .ok_or(anyhow!("MPEG audio stream is truncated!"))?;
+        let (offset, header) = find_first_frame(bytes, start)?;
+        let frame = &bytes[offset..];
+        let xing_header = match header.layer {
+            MpegLayer::Layer3 => frame
+                .get(FRAME_HEADER_SIZE + header.side_info_size()..)
+                .and_then(|bytes| XingHeader::parse(bytes).ok()),
+            _ => None,
+        };
+        let vbri_header = frame
+            .get(VBRI_HEADER_OFFSET..)
+            .and_then(|bytes| VbriHeader::parse(bytes).ok());
+        let frame_count = xing_header
+            .as_ref()
+            .and_then(|xing_header| xing_header.frame_count)
+            .or(vbri_header
+                .as_ref()
+                .map(|vbri_header| vbri_header.frame_count));
+        let (frame_count, audio_size) = match frame_count {
+            Some(frame_count) => {
+                let audio_start = (offset + header.frame_size()).min(end);
+                let audio_size = xing_header
+                    .as_ref()
+                    .and_then(|xing_header| xing_header.byte_count)
+                    .or(vbri_header
+                        .as_ref()
+                        .map(|vbri_header| vbri_header.byte_count))
+                    .map(|byte_count| byte_count as u64)
+                    .unwrap_or((end - audio_start) as u64);
+                (frame_count as u64, audio_size)
+            }
+            None => count_frames(bytes, offset),
+        };
+        Ok(MpegAudioInfo {
+            header,
+            xing_header,
+            vbri_header,
+            frame_count,
+            audio_size,
+        })
+    }
+
+    /// Reads the properties of the MPEG audio stream in the file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<MpegAudioInfo> {
+        let mut file = File::open(path)?;
+        Self::read_from_reader(&mut file)
+    }
+
+    /// Reads the properties of the MPEG audio stream in the given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to read the stream from.
+    pub fn read_from_reader<R: Read>(reader: &mut R) -> Result<MpegAudioInfo> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Self::parse(&bytes)
+    }
+
+    /// Returns the number of samples per channel in the stream, without the encoder delay and
+    /// padding given by a LAME header.
+    pub fn sample_count(&self) -> u64 {
+        let samples = self.frame_count * self.header.samples_per_frame() as u64;
+        match self.lame_header_gap() {
+            Some(gap) => samples.saturating_sub(gap),
+            None => samples,
+        }
+    }
+
+    /// Returns the duration of the stream.
+    pub fn duration(&self) -> Duration {
+        Duration::from_micros(self.sample_count() * 1_000_000 / self.header.sample_rate as u64)
+    }
+
+    /// Returns the average bitrate of the stream in bit/s.
+    pub fn bitrate(&self) -> u32 {
+        match self.duration().as_micros() {
+            0 => self.header.bitrate,
+            micros => (self.audio_size as u128 * 8 * 1_000_000 / micros) as u32,
+        }
+    }
+
+    /// Returns the encoder and its version given by a LAME header, if any.
+    pub fn encoder(&self) -> Option<&str> {
+        self.lame_header()
+            .map(|lame_header| lame_header.encoder.as_str())
+    }
+
+    /// Returns whether or not the stream has a variable bitrate, according to its first frame.
+    pub fn is_vbr(&self) -> bool {
+        self.vbri_header.is_some()
+            || self
+                .xing_header
+                .as_ref()
+                .is_some_and(|xing_header| xing_header.vbr)
+    }
+
+    /// Returns the LAME header of the stream's Xing/Info header, if any, which gives the
+    /// encoder delay and padding for gapless playback.
+    pub fn lame_header(&self) -> Option<&LameHeader> {
+        self.xing_header
+            .as_ref()
+            .and_then(|xing_header| xing_header.lame_header.as_ref())
+    }
+
+    fn lame_header_gap(&self) -> Option<u64> {
+        self.lame_header().map(|lame_header| {
+            lame_header.encoder_delay as u64 + lame_header.encoder_padding as u64
+        })
+    }
+}
+
+/// Finds the first frame at or after the given offset that is followed by another frame of the
+/// same stream (or the end), so that stray sync bytes aren't mistaken for a frame.
+fn find_first_frame(bytes: &[u8], start: usize) -> Result<(usize, MpegFrameHeader)> {
+    for offset in start..bytes.len() {
+        if bytes[offset] != 0xFF {
+            continue;
+        }
+        let header = match MpegFrameHeader::parse(&bytes[offset..]) {
+            Ok(header) => header,
+            Err(_) => continue,
+        };
+        let next_offset = offset + header.frame_size();
+        let is_followed = match bytes.get(next_offset..) {
+            None | Some([]) => true,
+            Some(next) => {
+                MpegFrameHeader::parse(next).is_ok_and(|next| header.is_compatible(&next))
+            }
+        };
+        if is_followed {
+            return Ok((offset, header));
+        }
+    }
+    Err(anyhow!("No MPEG audio frames found!"))
+}
+
+/// Counts the frames from the given offset until the end of the stream or the first bytes that
+/// aren't a frame. Returns the number of frames and their total size.
+fn count_frames(bytes: &[u8], start: usize) -> (u64, u64) {
+    let mut frame_count = 0;
+    let mut offset = start;
+    while let Some(Ok(header)) = bytes.get(offset..).map(MpegFrameHeader::parse) {
+        frame_count += 1;
+        offset += header.frame_size();
+    }
+    (frame_count, (offset.min(bytes.len()) - start) as u64)
+}
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+/// Represents the channel modes of MPEG audio, as given in a frame header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MpegChannelMode {
+    /// Two independent channels.
+    Stereo,
+
+    /// Two channels that may be coded together (mid/side or intensity stereo).
+    JointStereo,
+
+    /// Two unrelated mono channels (e.g. two languages).
+    DualChannel,
+
+    /// A single channel.
+    Mono,
+}
+
+impl MpegChannelMode {
+    /// Parses the channel mode from the two channel mode bits of a frame header.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The channel mode bits, shifted to the lowest bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mpeg_channel_mode::*;
+    /// assert_eq!(MpegChannelMode::parse(0b01), MpegChannelMode::JointStereo);
+    /// assert_eq!(MpegChannelMode::parse(0b11), MpegChannelMode::Mono);
+    /// ```
+    pub fn parse(bits: u8) -> MpegChannelMode {
+        match bits & 0b11 {
+            0b00 => MpegChannelMode::Stereo,
+            0b01 => MpegChannelMode::JointStereo,
+            0b10 => MpegChannelMode::DualChannel,
+            _ => MpegChannelMode::Mono,
+        }
+    }
+
+    /// Returns the number of channels in this mode.
+    pub fn channel_count(&self) -> u8 {
+        match self {
+            MpegChannelMode::Mono => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl Display for MpegChannelMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MpegChannelMode::Stereo => "Stereo",
+                MpegChannelMode::JointStereo => "Joint Stereo",
+                MpegChannelMode::DualChannel => "Dual Channel",
+                MpegChannelMode::Mono => "Mono",
+            }
+        )
+    }
+}
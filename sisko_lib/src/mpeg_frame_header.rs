@@ -0,0 +1,170 @@
+use crate::mpeg_channel_mode::MpegChannelMode;
+use crate::mpeg_layer::MpegLayer;
+use crate::mpeg_version::MpegVersion;
+use anyhow::{anyhow, Result};
+
+/// The size of an MPEG audio frame header.
+pub const FRAME_HEADER_SIZE: usize = 4;
+
+/// The bitrates in kbit/s of MPEG-1 Layer I, by bitrate index.
+const MPEG1_LAYER1_BITRATES: [u32; 15] = [
+    0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+];
+
+/// The bitrates in kbit/s of MPEG-1 Layer II, by bitrate index.
+const MPEG1_LAYER2_BITRATES: [u32; 15] = [
+    0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+];
+
+/// The bitrates in kbit/s of MPEG-1 Layer III, by bitrate index.
+const MPEG1_LAYER3_BITRATES: [u32; 15] = [
+    0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+];
+
+/// The bitrates in kbit/s of MPEG-2 and MPEG-2.5 Layer I, by bitrate index.
+const MPEG2_LAYER1_BITRATES: [u32; 15] = [
+    0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+];
+
+/// The bitrates in kbit/s of MPEG-2 and MPEG-2.5 Layers II and III, by bitrate index.
+const MPEG2_LAYER23_BITRATES: [u32; 15] =
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+/// The sample rates in Hz of MPEG-1, by sample rate index.
+const MPEG1_SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+
+/// Represents the header at the start of every frame of an MPEG audio stream.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MpegFrameHeader {
+    /// The MPEG version of the frame.
+    pub version: MpegVersion,
+
+    /// The MPEG layer of the frame.
+    pub layer: MpegLayer,
+
+    /// Whether or not a CRC follows the header.
+    pub protected: bool,
+
+    /// The bitrate of the frame in bit/s.
+    pub bitrate: u32,
+
+    /// The sample rate of the frame in Hz.
+    pub sample_rate: u32,
+
+    /// Whether or not the frame has an extra padding slot.
+    pub padding: bool,
+
+    /// The channel mode of the frame.
+    pub channel_mode: MpegChannelMode,
+}
+
+impl MpegFrameHeader {
+    /// Parses the frame header from the start of the given bytes.
+    /// Free-format frames (with no bitrate) aren't supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the frame header from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mpeg_channel_mode::*;
+    /// # use sisko_lib::mpeg_frame_header::*;
+    /// # use sisko_lib::mpeg_layer::*;
+    /// # use sisko_lib::mpeg_version::*;
+    /// let header = MpegFrameHeader::parse(&[0xFF, 0xFB, 0x90, 0x64])?;
+    ///
+    /// assert_eq!(header.version, MpegVersion::Mpeg1);
+    /// assert_eq!(header.layer, MpegLayer::Layer3);
+    /// assert_eq!(header.bitrate, 128000);
+    /// assert_eq!(header.sample_rate, 44100);
+    /// assert_eq!(header.channel_mode, MpegChannelMode::JointStereo);
+    /// assert_eq!(header.frame_size(), 417);
+    /// assert_eq!(header.samples_per_frame(), 1152);
+    /// assert!(MpegFrameHeader::parse(&[0xFF, 0xFB, 0xF0, 0x64]).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<MpegFrameHeader> {
+        let bytes = bytes
+            .get(0..FRAME_HEADER_SIZE)
+            .ok_or(anyhow!("MPEG frame header is truncated!"))?;
+        if bytes[0] != 0xFF || bytes[1] & 0xE0 != 0xE0 {
+            return Err(anyhow!("MPEG frame header has no frame sync!"));
+        }
+        let version = MpegVersion::parse(bytes[1] >> 3)?;
+        let layer = MpegLayer::parse(bytes[1] >> 1)?;
+        let bitrates = match (version, layer) {
+            (MpegVersion::Mpeg1, MpegLayer::Layer1) => &MPEG1_LAYER1_BITRATES,
+            (MpegVersion::Mpeg1, MpegLayer::Layer2) => &MPEG1_LAYER2_BITRATES,
+            (MpegVersion::Mpeg1, MpegLayer::Layer3) => &MPEG1_LAYER3_BITRATES,
+            (_, MpegLayer::Layer1) => &MPEG2_LAYER1_BITRATES,
+            (_, _) => &MPEG2_LAYER23_BITRATES,
+        };
+        let bitrate = match bitrates.get((bytes[2] >> 4) as usize) {
+            Some(0) => return Err(anyhow!("Free-format MPEG audio is unsupported!")),
+            Some(bitrate) => bitrate * 1000,
+            None => return Err(anyhow!("Invalid MPEG audio bitrate index!")),
+        };
+        let sample_rate = MPEG1_SAMPLE_RATES
+            .get(((bytes[2] >> 2) & 0b11) as usize)
+            .ok_or(anyhow!("Reserved MPEG audio sample rate index!"))?;
+        let sample_rate = match version {
+            MpegVersion::Mpeg1 => *sample_rate,
+            MpegVersion::Mpeg2 => sample_rate / 2,
+            MpegVersion::Mpeg25 => sample_rate / 4,
+        };
+        Ok(MpegFrameHeader {
+            version,
+            layer,
+            protected: bytes[1] & 0x01 == 0,
+            bitrate,
+            sample_rate,
+            padding: bytes[2] & 0x02 != 0,
+            channel_mode: MpegChannelMode::parse(bytes[3] >> 6),
+        })
+    }
+
+    /// Returns whether or not the other frame header could belong to the same stream as this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other frame header.
+    pub fn is_compatible(&self, other: &MpegFrameHeader) -> bool {
+        self.version == other.version
+            && self.layer == other.layer
+            && self.sample_rate == other.sample_rate
+    }
+
+    /// Returns the size of the whole frame, including this header.
+    pub fn frame_size(&self) -> usize {
+        let padding = self.padding as usize;
+        match self.layer {
+            MpegLayer::Layer1 => (12 * self.bitrate / self.sample_rate) as usize * 4 + padding * 4,
+            _ => {
+                let slots = self.samples_per_frame() as u64 / 8 * self.bitrate as u64;
+                (slots / self.sample_rate as u64) as usize + padding
+            }
+        }
+    }
+
+    /// Returns the number of samples per channel in each frame.
+    pub fn samples_per_frame(&self) -> u32 {
+        match (self.version, self.layer) {
+            (_, MpegLayer::Layer1) => 384,
+            (MpegVersion::Mpeg1, _) | (_, MpegLayer::Layer2) => 1152,
+            (_, MpegLayer::Layer3) => 576,
+        }
+    }
+
+    /// Returns the size of the Layer III side information that follows the header (and CRC).
+    /// This is where a Xing or Info header starts in the first frame.
+    pub fn side_info_size(&self) -> usize {
+        match (self.version, self.channel_mode) {
+            (MpegVersion::Mpeg1, MpegChannelMode::Mono) => 17,
+            (MpegVersion::Mpeg1, _) => 32,
+            (_, MpegChannelMode::Mono) => 9,
+            (_, _) => 17,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+
+/// Represents the layers of MPEG audio, as given in a frame header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MpegLayer {
+    /// Layer I (MP1).
+    Layer1,
+
+    /// Layer II (MP2).
+    Layer2,
+
+    /// Layer III (MP3).
+    Layer3,
+}
+
+impl MpegLayer {
+    /// Parses the layer from the two layer bits of a frame header.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The layer bits, shifted to the lowest bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mpeg_layer::*;
+    /// assert_eq!(MpegLayer::parse(0b11)?, MpegLayer::Layer1);
+    /// assert_eq!(MpegLayer::parse(0b10)?, MpegLayer::Layer2);
+    /// assert_eq!(MpegLayer::parse(0b01)?, MpegLayer::Layer3);
+    /// assert!(MpegLayer::parse(0b00).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bits: u8) -> Result<MpegLayer> {
+        match bits & 0b11 {
+            0b01 => Ok(MpegLayer::Layer3),
+            0b10 => Ok(MpegLayer::Layer2),
+            0b11 => Ok(MpegLayer::Layer1),
+            _ => Err(anyhow!("Reserved MPEG audio layer!")),
+        }
+    }
+}
+
+impl Display for MpegLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MpegLayer::Layer1 => "Layer I",
+                MpegLayer::Layer2 => "Layer II",
+                MpegLayer::Layer3 => "Layer III",
+            }
+        )
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+
+/// Represents the versions of MPEG audio, as given in a frame header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MpegVersion {
+    /// MPEG-1 audio (ISO/IEC 11172-3).
+    Mpeg1,
+
+    /// MPEG-2 audio at low sample rates (ISO/IEC 13818-3).
+    Mpeg2,
+
+    /// The unofficial MPEG-2.5 extension for very low sample rates.
+    Mpeg25,
+}
+
+impl MpegVersion {
+    /// Parses the version from the two version bits of a frame header.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The version bits, shifted to the lowest bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::mpeg_version::*;
+    /// assert_eq!(MpegVersion::parse(0b11)?, MpegVersion::Mpeg1);
+    /// assert_eq!(MpegVersion::parse(0b10)?, MpegVersion::Mpeg2);
+    /// assert_eq!(MpegVersion::parse(0b00)?, MpegVersion::Mpeg25);
+    /// assert!(MpegVersion::parse(0b01).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bits: u8) -> Result<MpegVersion> {
+        match bits & 0b11 {
+            0b00 => Ok(MpegVersion::Mpeg25),
+            0b10 => Ok(MpegVersion::Mpeg2),
+            0b11 => Ok(MpegVersion::Mpeg1),
+            _ => Err(anyhow!("Reserved MPEG audio version!")),
+        }
+    }
+}
+
+impl Display for MpegVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MpegVersion::Mpeg1 => "MPEG-1",
+                MpegVersion::Mpeg2 => "MPEG-2",
+                MpegVersion::Mpeg25 => "MPEG-2.5",
+            }
+        )
+    }
+}
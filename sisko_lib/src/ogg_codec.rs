@@ -0,0 +1,68 @@
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+
+/// Represents the codecs of Ogg logical bitstreams that carry Vorbis comments.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OggCodec {
+    /// An Opus stream. Its comment header starts with "OpusTags".
+    Opus,
+
+    /// A Vorbis stream. Its comment header starts with "\x03vorbis".
+    Vorbis,
+}
+
+impl OggCodec {
+    /// Identifies the codec of a logical bitstream from its first packet.
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The first (identification header) packet of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_codec::*;
+    /// assert_eq!(OggCodec::identify(b"OpusHead\x01\x02")?, OggCodec::Opus);
+    /// assert_eq!(OggCodec::identify(b"\x01vorbis\x00\x00")?, OggCodec::Vorbis);
+    /// assert!(OggCodec::identify(b"\x7fFLAC").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn identify(packet: &[u8]) -> Result<OggCodec> {
+        if packet.starts_with(b"OpusHead") {
+            Ok(OggCodec::Opus)
+        } else if packet.starts_with(b"\x01vorbis") {
+            Ok(OggCodec::Vorbis)
+        } else {
+            Err(anyhow!("Unsupported Ogg codec identification header!"))
+        }
+    }
+
+    /// Returns the signature that the comment header packet starts with.
+    pub fn comment_signature(&self) -> &'static [u8] {
+        match self {
+            OggCodec::Opus => b"OpusTags",
+            OggCodec::Vorbis => b"\x03vorbis",
+        }
+    }
+
+    /// Returns the number of header packets at the start of the stream.
+    pub fn header_packet_count(&self) -> usize {
+        match self {
+            OggCodec::Opus => 2,
+            OggCodec::Vorbis => 3,
+        }
+    }
+}
+
+impl Display for OggCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OggCodec::Opus => "Opus",
+                OggCodec::Vorbis => "Vorbis",
+            }
+        )
+    }
+}
@@ -0,0 +1,229 @@
+use crate::ogg_codec::OggCodec;
+use crate::ogg_packet::OggPacket;
+use crate::ogg_page::OggPage;
+use crate::vorbis_comment::VorbisComment;
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Represents the comment header packet of an Ogg Vorbis or Opus stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OggCommentHeader {
+    /// The codec of the stream the header belongs to.
+    pub codec: OggCodec,
+
+    /// The Vorbis comment in the header.
+    pub comment: VorbisComment,
+
+    /// (Opus only) Any data after the comment that must be preserved.
+    pub padding: Vec<u8>,
+}
+
+impl OggCommentHeader {
+    /// Parses a comment header for the given codec from the given packet.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec of the stream the packet is from.
+    /// * `packet` - The bytes of the comment header packet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_codec::*;
+    /// # use sisko_lib::ogg_comment_header::*;
+    /// let bytes = [
+    ///     b'O', b'p', b'u', b's', b'T', b'a', b'g', b's',
+    ///     3, 0, 0, 0, b'l', b'i', b'b',
+    ///     1, 0, 0, 0,
+    ///     7, 0, 0, 0, b'T', b'I', b'T', b'L', b'E', b'=', b'2',
+    /// ];
+    ///
+    /// let header = OggCommentHeader::parse(OggCodec::Opus, &bytes)?;
+    ///
+    /// assert_eq!(header.comment.vendor, "lib");
+    /// assert_eq!(header.comment.get("title"), vec!["2"]);
+    /// assert_eq!(header.padding.len(), 0);
+    /// assert_eq!(header.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(codec: OggCodec, packet: &[u8]) -> Result<OggCommentHeader> {
+        let signature = codec.comment_signature();
+        if !packet.starts_with(signature) {
+            return Err(anyhow!("Missing {} comment header signature!", codec));
+        }
+        let (comment, size) = VorbisComment::parse(&packet[signature.len()..])?;
+        let padding = match codec {
+            OggCodec::Opus => packet[signature.len() + size..].to_vec(),
+            OggCodec::Vorbis => vec![],
+        };
+
+        Ok(OggCommentHeader {
+            codec,
+            comment,
+            padding,
+        })
+    }
+
+    /// Reads the comment header from the first logical bitstream of the Ogg
+    /// file with the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read the comment header from.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<OggCommentHeader> {
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(file);
+        Self::read_from_reader(&mut reader)
+    }
+
+    /// Reads the comment header from the first logical bitstream in the given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream of Ogg pages to read the comment header from.
+    pub fn read_from_reader<R: Read>(reader: &mut R) -> Result<OggCommentHeader> {
+        let first_page = OggPage::read_from_reader(reader)?;
+        if !first_page.flags.is_beginning_of_stream {
+            return Err(anyhow!("The first Ogg page doesn't begin a stream!"));
+        }
+        let serial_number = first_page.serial_number;
+        let mut pages = vec![first_page];
+        let mut packets = OggPacket::parse_all(&pages);
+        while packets.len() < 2 {
+            let page = OggPage::read_from_reader(reader)?;
+            if page.serial_number == serial_number {
+                pages.push(page);
+                packets = OggPacket::parse_all(&pages);
+            }
+        }
+        let codec = OggCodec::identify(&packets[0].data)?;
+        Self::parse(codec, &packets[1].data)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut header_bytes: Vec<u8> = vec![];
+        header_bytes.extend(self.codec.comment_signature());
+        header_bytes.extend(self.comment.to_bytes());
+        match self.codec {
+            OggCodec::Opus => header_bytes.extend(&self.padding),
+            OggCodec::Vorbis => header_bytes.push(1),
+        }
+        header_bytes
+    }
+
+    /// Returns the given Ogg file's bytes with its first logical bitstream's
+    /// comment header replaced by this one.
+    /// The header packets are laid out on new pages, the stream's later pages
+    /// are renumbered, and the checksums of changed pages are recalculated.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_bytes` - The bytes of the Ogg file to replace the comment header in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_comment_header::*;
+    /// # use sisko_lib::ogg_packet::*;
+    /// # use sisko_lib::vorbis_comment_field::*;
+    /// let packets = [
+    ///     OggPacket { data: b"OpusHead\x01\x02".to_vec() },
+    ///     OggPacket { data: b"OpusTags\x03\x00\x00\x00lib\x00\x00\x00\x00".to_vec() },
+    ///     OggPacket { data: vec![0xfc; 300] },
+    /// ];
+    /// let mut pages = OggPacket::paginate(&packets[..1], 7, 0);
+    /// pages[0].flags.is_beginning_of_stream = true;
+    /// pages.extend(OggPacket::paginate(&packets[1..2], 7, 1));
+    /// pages.extend(OggPacket::paginate(&packets[2..], 7, 2));
+    /// let file_bytes: Vec<u8> = pages.iter().flat_map(|page| page.to_bytes()).collect();
+    ///
+    /// let mut header = OggCommentHeader::read_from_reader(&mut file_bytes.as_slice())?;
+    /// header.comment.fields.push(VorbisCommentField::new("TITLE", "2"));
+    /// let new_bytes = header.write_to_bytes(&file_bytes)?;
+    ///
+    /// let new_header = OggCommentHeader::read_from_reader(&mut new_bytes.as_slice())?;
+    /// assert_eq!(new_header.comment.get("TITLE"), vec!["2"]);
+    /// assert!(new_bytes.ends_with(&pages[2].to_bytes()));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_to_bytes(&self, file_bytes: &[u8]) -> Result<Vec<u8>> {
+        let pages = OggPage::parse_all(file_bytes)?;
+        let serial_number = pages
+            .iter()
+            .find(|page| page.flags.is_beginning_of_stream)
+            .ok_or(anyhow!("No Ogg page begins a stream!"))?
+            .serial_number;
+        let stream_pages: Vec<&OggPage> = pages
+            .iter()
+            .filter(|page| page.serial_number == serial_number)
+            .collect();
+
+        let header_packet_count = self.codec.header_packet_count();
+        let mut finished_packet_count = 0;
+        let header_page_count = stream_pages
+            .iter()
+            .take_while(|page| {
+                let is_header_page = finished_packet_count < header_packet_count;
+                if is_header_page {
+                    finished_packet_count += page.finished_packet_count();
+                }
+                is_header_page
+            })
+            .count();
+        let last_header_page = stream_pages
+            .get(header_page_count - 1)
+            .ok_or(anyhow!("No {} header pages found!", self.codec))?;
+        if finished_packet_count != header_packet_count || last_header_page.has_unfinished_packet()
+        {
+            return Err(anyhow!(
+                "The {} header packets don't finish on a page boundary!",
+                self.codec
+            ));
+        }
+
+        let header_pages: Vec<OggPage> = stream_pages[..header_page_count]
+            .iter()
+            .map(|&page| page.clone())
+            .collect();
+        let mut packets = OggPacket::parse_all(&header_pages);
+        if OggCodec::identify(&packets[0].data)? != self.codec {
+            return Err(anyhow!("The Ogg file's first stream isn't {}!", self.codec));
+        }
+        packets[1] = OggPacket {
+            data: self.to_bytes(),
+        };
+
+        let first_sequence_number = header_pages[0].sequence_number;
+        let mut new_header_pages =
+            OggPacket::paginate(&packets[..1], serial_number, first_sequence_number);
+        new_header_pages[0].flags.is_beginning_of_stream = true;
+        new_header_pages.extend(OggPacket::paginate(
+            &packets[1..],
+            serial_number,
+            first_sequence_number + new_header_pages.len() as u32,
+        ));
+        let sequence_offset = new_header_pages.len() as i64 - header_page_count as i64;
+
+        let mut new_bytes: Vec<u8> = vec![];
+        let mut stream_page_index = 0;
+        for page in &pages {
+            if page.serial_number != serial_number {
+                new_bytes.extend(page.to_bytes());
+                continue;
+            }
+            if stream_page_index == 0 {
+                for header_page in &new_header_pages {
+                    new_bytes.extend(header_page.to_bytes());
+                }
+            } else if stream_page_index >= header_page_count {
+                let mut page = page.clone();
+                page.sequence_number = (page.sequence_number as i64 + sequence_offset) as u32;
+                new_bytes.extend(page.to_bytes());
+            }
+            stream_page_index += 1;
+        }
+        Ok(new_bytes)
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ogg_page::{OggPage, NO_GRANULE_POSITION};
+use crate::ogg_page_flags::OggPageFlags;
+
+/// The most lacing values an Ogg page's segment table can hold.
+const MAX_SEGMENTS_PER_PAGE: usize = 255;
+
+/// Represents a packet of codec data in an Ogg logical bitstream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OggPacket {
+    /// The data of the packet.
+    pub data: Vec<u8>,
+}
+
+impl OggPacket {
+    /// Reassembles the packets that finish on the given pages of a single logical bitstream.
+    /// A packet that is still unfinished on the last page is not returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pages` - The pages to reassemble the packets from, in sequence order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_packet::*;
+    /// let packets = vec![
+    ///     OggPacket { data: vec![1; 10] },
+    ///     OggPacket { data: vec![2; 600] },
+    ///     OggPacket { data: vec![3; 255] },
+    /// ];
+    /// let pages = OggPacket::paginate(&packets, 7, 0);
+    ///
+    /// assert_eq!(OggPacket::parse_all(&pages), packets);
+    /// ```
+    pub fn parse_all(pages: &[OggPage]) -> Vec<OggPacket> {
+        let mut packets: Vec<OggPacket> = vec![];
+        let mut current: Vec<u8> = vec![];
+        for page in pages {
+            let mut index = 0;
+            for &lacing_value in &page.segment_table {
+                let end = index + lacing_value as usize;
+                current.extend(&page.data[index..end]);
+                index = end;
+                if lacing_value < 255 {
+                    packets.push(OggPacket {
+                        data: std::mem::take(&mut current),
+                    });
+                }
+            }
+        }
+        packets
+    }
+
+    /// Lays out the given packets onto new pages of a logical bitstream.
+    /// The first packet starts at the beginning of the first page and the last
+    /// packet finishes at the end of the last page. This is meant for header
+    /// packets, so pages that finish a packet get a granule position of 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `packets` - The packets to lay out.
+    /// * `serial_number` - The serial number of the logical bitstream.
+    /// * `first_sequence_number` - The sequence number to give the first page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_packet::*;
+    /// let packets = vec![OggPacket { data: vec![0; 70000] }];
+    ///
+    /// let pages = OggPacket::paginate(&packets, 7, 2);
+    ///
+    /// assert_eq!(pages.len(), 2);
+    /// assert_eq!(pages[0].sequence_number, 2);
+    /// assert_eq!(pages[0].segment_table.len(), 255);
+    /// assert_eq!(pages[1].sequence_number, 3);
+    /// assert_eq!(pages[1].flags.is_continued_packet, true);
+    /// assert_eq!(pages[1].granule_position, 0);
+    /// ```
+    pub fn paginate(
+        packets: &[OggPacket],
+        serial_number: u32,
+        first_sequence_number: u32,
+    ) -> Vec<OggPage> {
+        let segments: Vec<(usize, usize, u8)> = packets
+            .iter()
+            .enumerate()
+            .flat_map(|(packet_index, packet)| {
+                let full_segments = packet.data.len() / 255;
+                let remainder = (packet.data.len() % 255) as u8;
+                (0..full_segments)
+                    .map(move |segment| (packet_index, segment * 255, 255u8))
+                    .chain([(packet_index, full_segments * 255, remainder)])
+            })
+            .collect();
+
+        let mut pages: Vec<OggPage> = vec![];
+        let mut is_continued_packet = false;
+        for chunk in segments.chunks(MAX_SEGMENTS_PER_PAGE) {
+            let segment_table: Vec<u8> = chunk.iter().map(|(_, _, size)| *size).collect();
+            let data: Vec<u8> = chunk
+                .iter()
+                .flat_map(|&(packet_index, start, size)| {
+                    packets[packet_index].data[start..start + size as usize].to_vec()
+                })
+                .collect();
+            let page = OggPage {
+                version: 0,
+                flags: OggPageFlags {
+                    is_continued_packet,
+                    ..OggPageFlags::default()
+                },
+                granule_position: NO_GRANULE_POSITION,
+                serial_number,
+                sequence_number: first_sequence_number + pages.len() as u32,
+                segment_table,
+                data,
+            };
+            let page = match page.finished_packet_count() {
+                0 => page,
+                _ => OggPage {
+                    granule_position: 0,
+                    ..page
+                },
+            };
+            is_continued_packet = page.has_unfinished_packet();
+            pages.push(page);
+        }
+        pages
+    }
+}
@@ -0,0 +1,223 @@
+use crate::ogg_page_flags::OggPageFlags;
+use anyhow::{anyhow, Result};
+use std::io::Read;
+
+/// The granule position of a page on which no packet finishes.
+pub const NO_GRANULE_POSITION: u64 = u64::MAX;
+
+/// Represents a page in an Ogg bitstream.
+/// See [RFC 3533](https://www.rfc-editor.org/rfc/rfc3533) for more detail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OggPage {
+    /// The version of the Ogg page format. This is always 0.
+    pub version: u8,
+
+    /// The header type flags for the page.
+    pub flags: OggPageFlags,
+
+    /// The codec-specific position of the last packet that finishes on this page.
+    pub granule_position: u64,
+
+    /// The serial number of the logical bitstream this page belongs to.
+    pub serial_number: u32,
+
+    /// The sequence number of this page in its logical bitstream.
+    pub sequence_number: u32,
+
+    /// The lacing values of the packet segments on this page.
+    pub segment_table: Vec<u8>,
+
+    /// The packet data on this page.
+    pub data: Vec<u8>,
+}
+
+impl OggPage {
+    /// Parses an Ogg page from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the page from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_page::*;
+    /// let bytes = [
+    ///     b'O', b'g', b'g', b'S', 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+    ///     0x92, 0xdd, 0xf6, 0x15, 1, 3, b'a', b'b', b'c',
+    /// ];
+    ///
+    /// let page = OggPage::parse(&bytes)?;
+    ///
+    /// assert_eq!(page.flags.is_beginning_of_stream, true);
+    /// assert_eq!(page.granule_position, 0);
+    /// assert_eq!(page.serial_number, 1);
+    /// assert_eq!(page.sequence_number, 0);
+    /// assert_eq!(page.segment_table, vec![3]);
+    /// assert_eq!(page.data, b"abc".to_vec());
+    /// assert_eq!(page.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<OggPage> {
+        if bytes.len() < 27 {
+            return Err(anyhow!("Not enough bytes for an Ogg page header!"));
+        }
+        if &bytes[0..4] != b"OggS" {
+            return Err(anyhow!("Missing Ogg page capture pattern!"));
+        }
+        let version = bytes[4];
+        let flags = OggPageFlags::parse(bytes[5]);
+        let granule_position = u64::from_le_bytes(bytes[6..14].try_into()?);
+        let serial_number = u32::from_le_bytes(bytes[14..18].try_into()?);
+        let sequence_number = u32::from_le_bytes(bytes[18..22].try_into()?);
+        let checksum = u32::from_le_bytes(bytes[22..26].try_into()?);
+        let segment_count = bytes[26] as usize;
+        let segment_table = bytes
+            .get(27..27 + segment_count)
+            .ok_or(anyhow!("Ogg page segment table is truncated!"))?
+            .to_vec();
+        let data_size: usize = segment_table.iter().map(|&s| s as usize).sum();
+        let data_start = 27 + segment_count;
+        let data = bytes
+            .get(data_start..data_start + data_size)
+            .ok_or(anyhow!("Ogg page data is truncated!"))?
+            .to_vec();
+
+        let page = OggPage {
+            version,
+            flags,
+            granule_position,
+            serial_number,
+            sequence_number,
+            segment_table,
+            data,
+        };
+        if page.checksum() != checksum {
+            return Err(anyhow!(
+                "Ogg page {} of stream {} has a bad checksum!",
+                page.sequence_number,
+                page.serial_number
+            ));
+        }
+        Ok(page)
+    }
+
+    /// Parses all of the Ogg pages from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the pages from.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<OggPage>> {
+        let mut pages: Vec<OggPage> = vec![];
+        let mut index = 0;
+        while index < bytes.len() {
+            let page = OggPage::parse(&bytes[index..])?;
+            index += page.total_size();
+            pages.push(page);
+        }
+        Ok(pages)
+    }
+
+    /// Reads the next Ogg page from the given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream of bytes to read the page from.
+    pub fn read_from_reader<R: Read>(reader: &mut R) -> Result<OggPage> {
+        let mut header_bytes: [u8; 27] = [0; 27];
+        reader.read_exact(&mut header_bytes)?;
+        let segment_count = header_bytes[26] as usize;
+        let mut segment_table: Vec<u8> = vec![0; segment_count];
+        reader.read_exact(&mut segment_table)?;
+        let data_size: usize = segment_table.iter().map(|&s| s as usize).sum();
+        let mut data: Vec<u8> = vec![0; data_size];
+        reader.read_exact(&mut data)?;
+
+        let mut page_bytes: Vec<u8> = vec![];
+        page_bytes.extend(header_bytes);
+        page_bytes.extend(segment_table);
+        page_bytes.extend(data);
+        OggPage::parse(&page_bytes)
+    }
+
+    /// Returns the CRC-32 checksum of this page.
+    pub fn checksum(&self) -> u32 {
+        ogg_crc32(&self.to_bytes_with_checksum(0))
+    }
+
+    /// Returns whether or not the last packet on this page continues onto the next page.
+    pub fn has_unfinished_packet(&self) -> bool {
+        self.segment_table
+            .last()
+            .map(|&s| s == 255)
+            .unwrap_or(false)
+    }
+
+    /// Returns the number of packets that finish on this page.
+    pub fn finished_packet_count(&self) -> usize {
+        self.segment_table.iter().filter(|&&s| s < 255).count()
+    }
+
+    /// Returns the size of this page in bytes, including its header.
+    pub fn total_size(&self) -> usize {
+        27 + self.segment_table.len() + self.data.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with_checksum(self.checksum())
+    }
+
+    fn to_bytes_with_checksum(&self, checksum: u32) -> Vec<u8> {
+        let mut page_bytes: Vec<u8> = vec![];
+        page_bytes.extend(b"OggS");
+        page_bytes.push(self.version);
+        page_bytes.extend(self.flags.to_bytes());
+        page_bytes.extend(self.granule_position.to_le_bytes());
+        page_bytes.extend(self.serial_number.to_le_bytes());
+        page_bytes.extend(self.sequence_number.to_le_bytes());
+        page_bytes.extend(checksum.to_le_bytes());
+        page_bytes.push(self.segment_table.len() as u8);
+        page_bytes.extend(&self.segment_table);
+        page_bytes.extend(&self.data);
+        page_bytes
+    }
+}
+
+/// Calculates the Ogg CRC-32 checksum (polynomial 0x04c11db7, unreflected,
+/// zero initial value) of the given bytes.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to calculate the checksum for.
+///
+/// # Examples
+///
+/// ```
+/// # use sisko_lib::ogg_page::*;
+/// assert_eq!(ogg_crc32(b""), 0);
+/// assert_eq!(ogg_crc32(b"123456789"), 0x89a1897f);
+/// ```
+pub fn ogg_crc32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |crc, &byte| {
+        (crc << 8) ^ OGG_CRC_TABLE[(((crc >> 24) as u8) ^ byte) as usize]
+    })
+}
+
+const OGG_CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = (index as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 0x8000_0000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ 0x04c1_1db7,
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
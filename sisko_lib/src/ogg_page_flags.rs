@@ -0,0 +1,68 @@
+use crate::{is_bit_set, set_bit};
+
+/// Represents the header type flags of an Ogg page.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OggPageFlags {
+    /// Whether or not the first packet on the page continues a packet from the previous page.
+    pub is_continued_packet: bool,
+
+    /// Whether or not this is the first page of a logical bitstream.
+    pub is_beginning_of_stream: bool,
+
+    /// Whether or not this is the last page of a logical bitstream.
+    pub is_end_of_stream: bool,
+}
+
+impl OggPageFlags {
+    /// Parses the flags for an Ogg page from the given header type byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte` - The header type byte of an Ogg page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_page_flags::*;
+    /// let byte = 0b0000_0101;
+    ///
+    /// let flags = OggPageFlags::parse(byte);
+    ///
+    /// assert_eq!(flags.is_continued_packet, true);
+    /// assert_eq!(flags.is_beginning_of_stream, false);
+    /// assert_eq!(flags.is_end_of_stream, true);
+    /// ```
+    pub fn parse(byte: u8) -> OggPageFlags {
+        OggPageFlags {
+            is_continued_packet: is_bit_set(byte, 0),
+            is_beginning_of_stream: is_bit_set(byte, 1),
+            is_end_of_stream: is_bit_set(byte, 2),
+        }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::ogg_page_flags::*;
+    /// let flags = OggPageFlags {
+    ///     is_continued_packet: false,
+    ///     is_beginning_of_stream: true,
+    ///     is_end_of_stream: false,
+    /// };
+    ///
+    /// assert_eq!(flags.to_bytes(), vec![0b0000_0010]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut byte = 0u8;
+        if self.is_continued_packet {
+            set_bit(&mut byte, 0);
+        }
+        if self.is_beginning_of_stream {
+            set_bit(&mut byte, 1);
+        }
+        if self.is_end_of_stream {
+            set_bit(&mut byte, 2);
+        }
+        vec![byte]
+    }
+}
@@ -133,4 +133,38 @@ impl PictureType {
             _ => Err(anyhow!("Unknown picture type: {}", byte)),
         }
     }
+
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::picture_type::*;
+    /// assert_eq!(PictureType::CoverFront.to_bytes(), vec![b'\x03']);
+    /// assert_eq!(PictureType::PublisherLogoType.to_bytes(), vec![b'\x14']);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let byte = match self {
+            PictureType::Other => b'\x00',
+            PictureType::FileIcon => b'\x01',
+            PictureType::OtherFileIcon => b'\x02',
+            PictureType::CoverFront => b'\x03',
+            PictureType::CoverBack => b'\x04',
+            PictureType::LeafletPage => b'\x05',
+            PictureType::Media => b'\x06',
+            PictureType::LeadArtist => b'\x07',
+            PictureType::Artist => b'\x08',
+            PictureType::Conductor => b'\x09',
+            PictureType::Band => b'\x0A',
+            PictureType::Composer => b'\x0B',
+            PictureType::Lyricist => b'\x0C',
+            PictureType::RecordingLocation => b'\x0D',
+            PictureType::DuringRecording => b'\x0E',
+            PictureType::DuringPerformance => b'\x0F',
+            PictureType::VideoScreenCapture => b'\x10',
+            PictureType::ABrightColouredFish => b'\x11',
+            PictureType::Illustration => b'\x12',
+            PictureType::BandLogoType => b'\x13',
+            PictureType::PublisherLogoType => b'\x14',
+        };
+        vec![byte]
+    }
 }
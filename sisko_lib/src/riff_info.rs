@@ -0,0 +1,71 @@
+use crate::iff_chunk::IffChunk;
+use crate::iff_format::IffFormat;
+use crate::riff_info_field::RiffInfoField;
+use anyhow::{anyhow, Result};
+
+/// Represents the `LIST` chunk of type `INFO` in a RIFF (WAV) file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RiffInfo {
+    /// The fields of the chunk (e.g. `INAM` for the title).
+    pub fields: Vec<RiffInfoField>,
+}
+
+impl RiffInfo {
+    /// Parses the info from the data of a `LIST` chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The data of the `LIST` chunk, starting with its `INFO` list type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::riff_info::*;
+    /// let bytes = [
+    ///     b'I', b'N', b'F', b'O',
+    ///     b'I', b'N', b'A', b'M', 5, 0, 0, 0, b'H', b'u', b'r', b't', 0, 0,
+    /// ];
+    ///
+    /// let info = RiffInfo::parse(&bytes)?;
+    ///
+    /// assert_eq!(info.get("INAM"), Some("Hurt"));
+    /// assert_eq!(info.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<RiffInfo> {
+        if bytes.get(0..4) != Some(b"INFO") {
+            return Err(anyhow!("LIST chunk is not of type INFO!"));
+        }
+        let fields = IffChunk::parse_all(&bytes[4..], IffFormat::Riff)?
+            .iter()
+            .map(RiffInfoField::from)
+            .collect();
+        Ok(RiffInfo { fields })
+    }
+
+    /// Returns the value of the first field with the given ID, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_id` - The four character ID of the field to get.
+    pub fn get(&self, field_id: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.field_id == field_id)
+            .map(|field| field.value.as_str())
+    }
+
+    /// Returns this info as a `LIST` chunk.
+    pub fn to_chunk(&self) -> IffChunk {
+        IffChunk::new("LIST", self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut info_bytes: Vec<u8> = vec![];
+        info_bytes.extend(b"INFO");
+        for field in &self.fields {
+            info_bytes.extend(field.to_chunk().to_bytes(IffFormat::Riff));
+        }
+        info_bytes
+    }
+}
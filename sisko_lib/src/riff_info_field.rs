@@ -0,0 +1,54 @@
+use crate::iff_chunk::IffChunk;
+
+/// Represents a field in the `LIST`/`INFO` chunk of a RIFF (WAV) file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiffInfoField {
+    /// The four character ID of the field (e.g. `IART` for the artist).
+    pub field_id: String,
+
+    /// The text of the field.
+    pub value: String,
+}
+
+impl RiffInfoField {
+    /// Returns a new info field with the given ID and value.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_id` - The four character ID of the field.
+    /// * `value` - The text of the field.
+    pub fn new(field_id: &str, value: &str) -> RiffInfoField {
+        RiffInfoField {
+            field_id: String::from(field_id),
+            value: String::from(value),
+        }
+    }
+
+    /// Returns this field as a null-terminated chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::riff_info_field::*;
+    /// let field = RiffInfoField::new("IART", "NIN");
+    ///
+    /// assert_eq!(field.to_chunk().data, vec![b'N', b'I', b'N', 0]);
+    /// ```
+    pub fn to_chunk(&self) -> IffChunk {
+        let mut data = self.value.clone().into_bytes();
+        data.push(0);
+        IffChunk::new(&self.field_id, data)
+    }
+}
+
+impl From<&IffChunk> for RiffInfoField {
+    fn from(chunk: &IffChunk) -> Self {
+        let value = String::from_utf8_lossy(&chunk.data)
+            .trim_end_matches('\0')
+            .to_string();
+        RiffInfoField {
+            field_id: chunk.chunk_id.clone(),
+            value,
+        }
+    }
+}
@@ -73,6 +73,10 @@ impl TextEncoding {
     /// let index = TextEncoding::Utf8.next_terminator(&bytes);
     ///
     /// assert_eq!(index, Some(4));
+    ///
+    /// let bytes = [b'\xff', b'\xfe', b'r', 0, 0, 0, b'x', 0];
+    ///
+    /// assert_eq!(TextEncoding::Utf16Bom.next_terminator(&bytes), Some(4));
     /// ```
     pub fn next_terminator(&self, bytes: &[u8]) -> Option<usize> {
         match self {
@@ -85,15 +89,12 @@ impl TextEncoding {
                     .collect();
                 all.first().map(|(index, _)| *index)
             }
-            TextEncoding::Utf16Bom | TextEncoding::Utf16Be => {
-                let all: Vec<(usize, &u8)> = bytes
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, &b)| b == 0 && bytes[i + 1] == 0)
-                    .take(1)
-                    .collect();
-                all.first().map(|(index, _)| *index)
-            }
+            // NOTE: the terminator is a whole code unit, so a null ending one character and
+            // starting the next isn't one
+            TextEncoding::Utf16Bom | TextEncoding::Utf16Be => bytes
+                .chunks_exact(2)
+                .position(|unit| unit == [0, 0])
+                .map(|index| index * 2),
         }
     }
 
@@ -0,0 +1,69 @@
+use anyhow::{anyhow, Result};
+
+/// The offset of a VBRI header from the start of its frame.
+pub const VBRI_HEADER_OFFSET: usize = 36;
+
+/// The size of the fixed part of a VBRI header, before its seek table.
+const VBRI_HEADER_SIZE: usize = 18;
+
+/// Represents a VBRI header, written by the Fraunhofer encoder in the first frame of a VBR stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VbriHeader {
+    /// The version of the header.
+    pub version: u16,
+
+    /// The encoder delay in samples.
+    pub delay: u16,
+
+    /// The encoder's quality indicator.
+    pub quality: u16,
+
+    /// The number of bytes in the stream.
+    pub byte_count: u32,
+
+    /// The number of frames in the stream.
+    pub frame_count: u32,
+}
+
+impl VbriHeader {
+    /// Parses the header from the start of the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the header from, starting at the "VBRI" ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::vbri_header::*;
+    /// let mut bytes = b"VBRI".to_vec();
+    /// bytes.extend(1u16.to_be_bytes());
+    /// bytes.extend(1105u16.to_be_bytes());
+    /// bytes.extend(75u16.to_be_bytes());
+    /// bytes.extend(417000u32.to_be_bytes());
+    /// bytes.extend(1000u32.to_be_bytes());
+    ///
+    /// let header = VbriHeader::parse(&bytes)?;
+    ///
+    /// assert_eq!(header.delay, 1105);
+    /// assert_eq!(header.byte_count, 417000);
+    /// assert_eq!(header.frame_count, 1000);
+    /// assert!(VbriHeader::parse(b"Xing").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<VbriHeader> {
+        if !bytes.starts_with(b"VBRI") {
+            return Err(anyhow!("Not a VBRI header!"));
+        }
+        let bytes = bytes
+            .get(0..VBRI_HEADER_SIZE)
+            .ok_or(anyhow!("VBRI header is truncated!"))?;
+        Ok(VbriHeader {
+            version: u16::from_be_bytes([bytes[4], bytes[5]]),
+            delay: u16::from_be_bytes([bytes[6], bytes[7]]),
+            quality: u16::from_be_bytes([bytes[8], bytes[9]]),
+            byte_count: u32::from_be_bytes(bytes[10..14].try_into()?),
+            frame_count: u32::from_be_bytes(bytes[14..18].try_into()?),
+        })
+    }
+}
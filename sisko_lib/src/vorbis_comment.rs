@@ -0,0 +1,95 @@
+use crate::vorbis_comment_field::VorbisCommentField;
+use anyhow::{anyhow, Result};
+
+/// Represents a Vorbis comment block, as used by Ogg Vorbis, Opus, and FLAC.
+/// See the [Vorbis I specification](https://xiph.org/vorbis/doc/v-comment.html) for more detail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VorbisComment {
+    /// The vendor string of the encoder that wrote the comment.
+    pub vendor: String,
+
+    /// The fields of the comment (the actual "tags").
+    pub fields: Vec<VorbisCommentField>,
+}
+
+impl VorbisComment {
+    /// Parses a Vorbis comment from the given bytes.
+    /// Returns the comment and the number of bytes it took up.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the comment from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::vorbis_comment::*;
+    /// let bytes = [
+    ///     3, 0, 0, 0, b'l', b'i', b'b',
+    ///     1, 0, 0, 0,
+    ///     7, 0, 0, 0, b'T', b'I', b'T', b'L', b'E', b'=', b'2',
+    /// ];
+    ///
+    /// let (comment, size) = VorbisComment::parse(&bytes)?;
+    ///
+    /// assert_eq!(comment.vendor, "lib");
+    /// assert_eq!(comment.fields.len(), 1);
+    /// assert_eq!(comment.fields[0].name, "TITLE");
+    /// assert_eq!(comment.fields[0].value, "2");
+    /// assert_eq!(size, bytes.len());
+    /// assert_eq!(comment.to_bytes(), bytes.to_vec());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(VorbisComment, usize)> {
+        let mut index = 0;
+        let vendor = String::from_utf8_lossy(read_length_prefixed(bytes, &mut index)?).to_string();
+        let field_count = read_u32(bytes, &mut index)?;
+        let fields = (0..field_count)
+            .map(|_| VorbisCommentField::parse(read_length_prefixed(bytes, &mut index)?))
+            .collect::<Result<Vec<VorbisCommentField>>>()?;
+        Ok((VorbisComment { vendor, fields }, index))
+    }
+
+    /// Returns the values of all of the fields with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The case-insensitive name of the fields to get.
+    pub fn get(&self, name: &str) -> Vec<&str> {
+        let key = name.to_uppercase();
+        self.fields
+            .iter()
+            .filter(|field| field.key() == key)
+            .map(|field| field.value.as_str())
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let vendor_bytes = self.vendor.as_bytes();
+        let mut comment_bytes: Vec<u8> = vec![];
+        comment_bytes.extend((vendor_bytes.len() as u32).to_le_bytes());
+        comment_bytes.extend(vendor_bytes);
+        comment_bytes.extend((self.fields.len() as u32).to_le_bytes());
+        for field in &self.fields {
+            comment_bytes.extend(field.to_bytes());
+        }
+        comment_bytes
+    }
+}
+
+fn read_u32(bytes: &[u8], index: &mut usize) -> Result<u32> {
+    let value = bytes
+        .get(*index..*index + 4)
+        .ok_or(anyhow!("Vorbis comment is truncated!"))?;
+    *index += 4;
+    Ok(u32::from_le_bytes(value.try_into()?))
+}
+
+fn read_length_prefixed<'a>(bytes: &'a [u8], index: &mut usize) -> Result<&'a [u8]> {
+    let length = read_u32(bytes, index)? as usize;
+    let value = bytes
+        .get(*index..*index + length)
+        .ok_or(anyhow!("Vorbis comment is truncated!"))?;
+    *index += length;
+    Ok(value)
+}
@@ -0,0 +1,120 @@
+use crate::flac_picture::FlacPicture;
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// The name of the field that holds base64-encoded FLAC picture blocks.
+pub const METADATA_BLOCK_PICTURE: &str = "METADATA_BLOCK_PICTURE";
+
+/// Represents a single `NAME=value` field in a Vorbis comment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VorbisCommentField {
+    /// The name of the field. Names are case-insensitive ASCII.
+    pub name: String,
+
+    /// The UTF-8 value of the field.
+    pub value: String,
+}
+
+impl VorbisCommentField {
+    /// Returns a new Vorbis comment field with the given name and value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    /// * `value` - The value of the field.
+    pub fn new(name: &str, value: &str) -> VorbisCommentField {
+        VorbisCommentField {
+            name: String::from(name),
+            value: String::from(value),
+        }
+    }
+
+    /// Parses a Vorbis comment field from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the field, without its length prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::vorbis_comment_field::*;
+    /// let field = VorbisCommentField::parse(b"ARTIST=Nine Inch Nails")?;
+    ///
+    /// assert_eq!(field.name, "ARTIST");
+    /// assert_eq!(field.value, "Nine Inch Nails");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<VorbisCommentField> {
+        let text = String::from_utf8_lossy(bytes);
+        let (name, value) = text
+            .split_once('=')
+            .ok_or(anyhow!("Vorbis comment field is missing a '=': {}!", text))?;
+        Ok(VorbisCommentField::new(name, value))
+    }
+
+    /// Returns a new `METADATA_BLOCK_PICTURE` field holding the given picture.
+    ///
+    /// # Arguments
+    ///
+    /// * `picture` - The picture to base64-encode into the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::flac_picture::*;
+    /// # use sisko_lib::picture_type::*;
+    /// # use sisko_lib::vorbis_comment_field::*;
+    /// let picture = FlacPicture {
+    ///     picture_type: PictureType::CoverFront,
+    ///     mime_type: String::from("image/jpeg"),
+    ///     description: String::new(),
+    ///     width: 0,
+    ///     height: 0,
+    ///     color_depth: 0,
+    ///     indexed_colors: 0,
+    ///     picture_data: vec![0xff, 0xd8],
+    /// };
+    ///
+    /// let field = VorbisCommentField::from_picture(&picture);
+    ///
+    /// assert_eq!(field.name, METADATA_BLOCK_PICTURE);
+    /// assert_eq!(field.picture()?, picture);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_picture(picture: &FlacPicture) -> VorbisCommentField {
+        VorbisCommentField {
+            name: String::from(METADATA_BLOCK_PICTURE),
+            value: STANDARD.encode(picture.to_bytes()),
+        }
+    }
+
+    /// Returns the name of this field normalized to upper case.
+    pub fn key(&self) -> String {
+        self.name.to_uppercase()
+    }
+
+    /// Decodes the picture in this `METADATA_BLOCK_PICTURE` field.
+    pub fn picture(&self) -> Result<FlacPicture> {
+        if self.key() != METADATA_BLOCK_PICTURE {
+            return Err(anyhow!("{} is not a picture field!", self.name));
+        }
+        FlacPicture::parse(&STANDARD.decode(&self.value)?)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::vorbis_comment_field::*;
+    /// let field = VorbisCommentField::new("TITLE", "2");
+    ///
+    /// assert_eq!(field.to_bytes(), vec![7, 0, 0, 0, b'T', b'I', b'T', b'L', b'E', b'=', b'2']);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let text = format!("{}={}", self.name, self.value).into_bytes();
+        let mut field_bytes: Vec<u8> = vec![];
+        field_bytes.extend((text.len() as u32).to_le_bytes());
+        field_bytes.extend(text);
+        field_bytes
+    }
+}
@@ -0,0 +1,116 @@
+use crate::lame_header::LameHeader;
+use anyhow::{anyhow, Result};
+
+/// The flag set when the frame count is present.
+const FRAMES_FLAG: u32 = 0x01;
+
+/// The flag set when the byte count is present.
+const BYTES_FLAG: u32 = 0x02;
+
+/// The flag set when the seek table is present.
+const TOC_FLAG: u32 = 0x04;
+
+/// The flag set when the quality indicator is present.
+const QUALITY_FLAG: u32 = 0x08;
+
+/// The size of the seek table.
+const TOC_SIZE: usize = 100;
+
+/// Represents a Xing (VBR) or Info (CBR) header in the first frame of an MPEG audio stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct XingHeader {
+    /// Whether or not the stream has a variable bitrate ("Xing") rather than a constant one ("Info").
+    pub vbr: bool,
+
+    /// The number of frames in the stream, excluding the one holding this header.
+    pub frame_count: Option<u32>,
+
+    /// The number of bytes in the stream.
+    pub byte_count: Option<u32>,
+
+    /// The seek table: the positions of each percent of the duration, in 256ths of the stream.
+    pub toc: Option<Vec<u8>>,
+
+    /// The encoder's quality indicator, from 0 (best) to 100 (worst).
+    pub quality: Option<u32>,
+
+    /// The LAME header that follows, if any.
+    pub lame_header: Option<LameHeader>,
+}
+
+impl XingHeader {
+    /// Parses the header from the start of the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to parse the header from, starting at the "Xing" or "Info" ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sisko_lib::xing_header::*;
+    /// let mut bytes = b"Xing".to_vec();
+    /// bytes.extend(3u32.to_be_bytes());
+    /// bytes.extend(1000u32.to_be_bytes());
+    /// bytes.extend(417000u32.to_be_bytes());
+    ///
+    /// let header = XingHeader::parse(&bytes)?;
+    ///
+    /// assert!(header.vbr);
+    /// assert_eq!(header.frame_count, Some(1000));
+    /// assert_eq!(header.byte_count, Some(417000));
+    /// assert_eq!(header.toc, None);
+    /// assert_eq!(header.lame_header, None);
+    /// assert!(XingHeader::parse(b"VBRI").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<XingHeader> {
+        let vbr = match bytes.get(0..4) {
+            Some(b"Xing") => true,
+            Some(b"Info") => false,
+            _ => return Err(anyhow!("Not a Xing or Info header!")),
+        };
+        let mut index = 4;
+        let flags = read_u32(bytes, &mut index)?;
+        let frame_count = match flags & FRAMES_FLAG {
+            0 => None,
+            _ => Some(read_u32(bytes, &mut index)?),
+        };
+        let byte_count = match flags & BYTES_FLAG {
+            0 => None,
+            _ => Some(read_u32(bytes, &mut index)?),
+        };
+        let toc = match flags & TOC_FLAG {
+            0 => None,
+            _ => Some(read_bytes(bytes, &mut index, TOC_SIZE)?.to_vec()),
+        };
+        let quality = match flags & QUALITY_FLAG {
+            0 => None,
+            _ => Some(read_u32(bytes, &mut index)?),
+        };
+        let lame_header = bytes
+            .get(index..)
+            .and_then(|bytes| LameHeader::parse(bytes).ok());
+        Ok(XingHeader {
+            vbr,
+            frame_count,
+            byte_count,
+            toc,
+            quality,
+            lame_header,
+        })
+    }
+}
+
+fn read_u32(bytes: &[u8], index: &mut usize) -> Result<u32> {
+    let value = read_bytes(bytes, index, 4)?;
+    Ok(u32::from_be_bytes(value.try_into()?))
+}
+
+fn read_bytes<'a>(bytes: &'a [u8], index: &mut usize, length: usize) -> Result<&'a [u8]> {
+    let value = bytes
+        .get(*index..*index + length)
+        .ok_or(anyhow!("Xing header is truncated!"))?;
+    *index += length;
+    Ok(value)
+}
@@ -1,7 +1,8 @@
 use super::Track;
 use crate::domain::events::DomainEvent;
-use crate::infrastructure::musicbrainz::Release;
+use crate::infrastructure::musicbrainz::{credited_name, credited_sort_name, Release};
 use crate::infrastructure::{Entity, EntityId};
+use itertools::Itertools;
 use std::cmp::{Eq, PartialEq};
 use std::time::Duration;
 
@@ -36,7 +37,9 @@ impl From<AlbumId> for String {
 
 #[derive(Clone, Debug)]
 pub struct Album {
+    /// The artists as they're credited, joined by their join phrases.
     pub artist: String,
+    /// The MusicBrainz IDs of the credited artists, separated by "; ".
     pub artist_id: String,
     pub asin: Option<String>,
     pub barcode: Option<String>,
@@ -50,6 +53,8 @@ pub struct Album {
     pub release_group_id: String,
     pub release_status: String,
     pub release_type: Option<String>,
+    /// How well the release matched the files' tags and fingerprints, from 0 to 1, once chosen.
+    pub score: Option<f64>,
     pub script: Option<String>,
     pub sort_artist: String,
     pub title: String,
@@ -68,14 +73,10 @@ impl From<&Release> for Album {
                     .map(|track| Track::new(&release.id.clone(), media, track))
             })
             .collect();
-        let album_artist = release.artist_credit.first();
+        let credits = &release.artist_credit;
         Self {
-            artist: album_artist
-                .map(|a| a.artist.name.clone())
-                .unwrap_or_default(),
-            artist_id: album_artist
-                .map(|a| a.artist.id.clone())
-                .unwrap_or_default(),
+            artist: credited_name(credits),
+            artist_id: credits.iter().map(|c| &c.artist.id).join("; "),
             asin: release.asin.clone(),
             barcode: release.barcode.clone(),
             catalog_number: release
@@ -94,10 +95,9 @@ impl From<&Release> for Album {
             release_group_id: release.release_group.id.clone(),
             release_status: release.status.clone(),
             release_type: release.release_group.primary_type.clone(),
+            score: None,
             script: release.text_representation.script.clone(),
-            sort_artist: album_artist
-                .map(|a| a.artist.sort_name.clone())
-                .unwrap_or_default(),
+            sort_artist: credited_sort_name(credits),
             title: release.title.clone(),
             total_discs: release.media.len(),
         }
@@ -132,9 +132,87 @@ impl PartialEq for Album {
             && self.release_group_id == other.release_group_id
             && self.release_status == other.release_status
             && self.release_type == other.release_type
+            && self.score == other.score
             && self.script == other.script
             && self.sort_artist == other.sort_artist
             && self.title == other.title
             && self.total_discs == other.total_discs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_release_credits_every_artist() {
+        let release: Release = serde_json::from_value(json!({
+            "disambiguation": "",
+            "barcode": null,
+            "id": "release",
+            "media": [],
+            "packaging": null,
+            "date": "2001-02-03",
+            "text-representation": {"language": "eng", "script": "Latn"},
+            "status-id": "4e304316-386d-3409-af2e-78857eec5cfe",
+            "asin": null,
+            "title": "Album",
+            "status": "Official",
+            "packaging-id": null,
+            "cover-art-archive": {
+                "count": 0,
+                "darkened": false,
+                "artwork": false,
+                "front": false,
+                "back": false
+            },
+            "quality": "normal",
+            "release-events": [],
+            "country": "XW",
+            "release-group": {
+                "artist-credit": [],
+                "disambiguation": "",
+                "first-release-date": "2001-02-03",
+                "id": "release-group",
+                "primary-type": "Album",
+                "primary-type-id": null,
+                "secondary-types": [],
+                "secondary-type-ids": [],
+                "title": "Album"
+            },
+            "label-info": [],
+            "artist-credit": [
+                {
+                    "name": "The Artist",
+                    "joinphrase": " vs. ",
+                    "artist": {
+                        "type-id": null,
+                        "sort-name": "Artist, The",
+                        "name": "The Artist",
+                        "id": "artist-a",
+                        "disambiguation": ""
+                    }
+                },
+                {
+                    "name": "Rival",
+                    "joinphrase": "",
+                    "artist": {
+                        "type-id": null,
+                        "sort-name": "Rival",
+                        "name": "Rival",
+                        "id": "artist-b",
+                        "disambiguation": ""
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let album = Album::from(&release);
+
+        assert_eq!(album.artist, "The Artist vs. Rival");
+        assert_eq!(album.artist_id, "artist-a; artist-b");
+        assert_eq!(album.sort_artist, "Artist, The vs. Rival");
+    }
+}
@@ -1,11 +1,24 @@
-use super::{Album, Track, TrackId};
+use super::{Album, ClusterId, Metadata, ScanStatus, Track, TrackId, MAX_LENGTH_DIFFERENCE};
 use crate::infrastructure::acoustid::Fingerprint;
 use crate::infrastructure::file::File;
-use crate::infrastructure::{EntityId, Value};
+use crate::infrastructure::{
+    linear_combination_of_weights, min_cost_assignment, similarity2, EntityId, Value,
+};
 use crate::{domain::events::DomainEvent, infrastructure::Entity};
 use anyhow::{anyhow, Result};
 use std::cmp::{Eq, PartialEq};
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// The lowest similarity a file may have to a track to be matched to it.
+const MIN_TRACK_SIMILARITY: f64 = 0.4;
+
+/// The weights of the comparisons of a file to a track.
+const DISC_NUMBER_WEIGHT: f64 = 4.0;
+const LENGTH_WEIGHT: f64 = 10.0;
+const RECORDING_WEIGHT: f64 = 30.0;
+const TITLE_WEIGHT: f64 = 22.0;
+const TRACK_NUMBER_WEIGHT: f64 = 10.0;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AudioFileId {
@@ -43,14 +56,51 @@ impl Value for AudioFileId {}
 pub struct AudioFile {
     pub acoust_id: Option<String>,
 
+    /// The checksum of only the audio payload of the file, which retagging doesn't change.
+    pub audio_checksum: Option<String>,
+
+    /// The average bitrate of the audio in bit/s, if known.
+    pub bitrate: Option<u32>,
+
+    /// The channel mode of the audio (e.g. Joint Stereo), if known.
+    pub channel_mode: Option<String>,
+
+    /// The cluster the file was put in, while it's unmatched.
+    pub cluster_id: Option<ClusterId>,
+
+    /// The duration of the audio as analysed from its stream, if known.
+    pub duration: Option<Duration>,
+
+    /// The encoder and its version (e.g. LAME3.100), if the stream names it.
+    pub encoder: Option<String>,
+
+    /// The samples of silence the encoder added to the start, for gapless playback, if known.
+    pub encoder_delay: Option<u16>,
+
+    /// The samples of silence the encoder added to the end, for gapless playback, if known.
+    pub encoder_padding: Option<u16>,
+
     pub events: Vec<DomainEvent>,
 
     pub fingerprint: Option<Fingerprint>,
 
+    /// The recording AcoustID identified the file's fingerprint as, if any. Unlike the
+    /// recording ID, it isn't replaced by the recording of the track the file is matched to.
+    pub fingerprint_recording_id: Option<String>,
+
     pub id: AudioFileId,
 
     pub recording_id: Option<String>,
 
+    /// The sample rate of the audio in Hz, if known.
+    pub sample_rate: Option<u32>,
+
+    /// The outcome of the last scan for a match.
+    pub scan_status: ScanStatus,
+
+    /// How similar the file is to the track it's matched to, from 0 to 1, if it's matched.
+    pub similarity: Option<f64>,
+
     pub track_id: Option<TrackId>,
 }
 
@@ -64,10 +114,22 @@ impl AudioFile {
         let id = AudioFileId::new(file.absolute_path.clone());
         Self {
             acoust_id: None,
+            audio_checksum: None,
+            bitrate: None,
+            channel_mode: None,
+            cluster_id: None,
+            duration: None,
+            encoder: None,
+            encoder_delay: None,
+            encoder_padding: None,
             events: vec![],
             fingerprint,
+            fingerprint_recording_id: None,
             id,
             recording_id: None,
+            sample_rate: None,
+            scan_status: ScanStatus::default(),
+            similarity: None,
             track_id: None,
         }
     }
@@ -78,20 +140,99 @@ impl AudioFile {
         todo!()
     }*/
 
+    /// Returns a summary of the analysed audio stream, e.g.
+    /// "192 kbps, 44.1 kHz, Joint Stereo, LAME3.100 (576/1152)", with the encoder delay and
+    /// padding in brackets. Only what's known is included.
+    pub fn audio_properties(&self) -> String {
+        let gapless = match (self.encoder_delay, self.encoder_padding) {
+            (Some(delay), Some(padding)) => Some(format!("({}/{})", delay, padding)),
+            _ => None,
+        };
+        let encoder = match (&self.encoder, gapless) {
+            (Some(encoder), Some(gapless)) => Some(format!("{} {}", encoder, gapless)),
+            (encoder, gapless) => encoder.clone().or(gapless),
+        };
+        [
+            self.bitrate
+                .map(|bitrate| format!("{} kbps", bitrate / 1000)),
+            self.sample_rate
+                .map(|sample_rate| format!("{} kHz", sample_rate as f64 / 1000.0)),
+            self.channel_mode.clone(),
+            encoder,
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(", ")
+    }
+
     /// Returns the length of the audio, if any.
+    /// The analysed duration is preferred over the duration fpcalc gave with the fingerprint.
     pub fn length(&self) -> Option<String> {
-        match &self.fingerprint {
-            None => None,
-            Some(f) => f.duration.parse::<u64>().ok().map(to_length_string),
+        match (&self.duration, &self.fingerprint) {
+            (Some(duration), _) => Some(to_length_string(duration.as_secs())),
+            (None, None) => None,
+            (None, Some(f)) => f.duration.parse::<u64>().ok().map(to_length_string),
         }
     }
 
-    pub fn match_to_album(&mut self, album: &Album, tracks: &[Track]) -> Result<Track> {
-        if let Some(recording_id) = &self.recording_id {
-            if let Some(track) = tracks.iter().find(|t| t.recording_id == *recording_id) {
-                self.track_id = Some(track.id.clone());
-                return Ok(track.clone());
-            }
+    /// Returns the index of the track each of the given files is matched to, with how similar
+    /// they are, or none for the files that don't fit any track well enough. The files are
+    /// assigned together so that the total similarity is as high as possible and no two files
+    /// get the same track.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The audio files to assign, with their tags.
+    /// * `tracks` - The tracks to assign the files to.
+    pub fn assign_tracks(
+        files: &[(&AudioFile, &Metadata)],
+        tracks: &[Track],
+    ) -> Vec<Option<(usize, f64)>> {
+        let similarities: Vec<Vec<f64>> = files
+            .iter()
+            .map(|(audio_file, metadata)| {
+                tracks
+                    .iter()
+                    .map(|track| audio_file.track_similarity(metadata, track))
+                    .collect()
+            })
+            .collect();
+        let costs: Vec<Vec<f64>> = similarities
+            .iter()
+            .map(|row| row.iter().map(|similarity| 1.0 - similarity).collect())
+            .collect();
+        min_cost_assignment(&costs)
+            .into_iter()
+            .enumerate()
+            .map(|(file, track)| {
+                track
+                    .map(|track| (track, similarities[file][track]))
+                    .filter(|(_, similarity)| *similarity >= MIN_TRACK_SIMILARITY)
+            })
+            .collect()
+    }
+
+    /// Matches the file to the track of the given album it's most similar to, and returns the
+    /// track.
+    ///
+    /// # Arguments
+    ///
+    /// * `album` - The album to match the file to.
+    /// * `tracks` - The tracks of the album.
+    /// * `metadata` - The tags of the file.
+    pub fn match_to_album(
+        &mut self,
+        album: &Album,
+        tracks: &[Track],
+        metadata: &Metadata,
+    ) -> Result<Track> {
+        if let Some(Some((index, similarity))) =
+            Self::assign_tracks(&[(self, metadata)], tracks).first()
+        {
+            let track = &tracks[*index];
+            self.match_to_track(track, *similarity);
+            return Ok(track.clone());
         }
 
         Err(anyhow!(
@@ -101,6 +242,53 @@ impl AudioFile {
         ))
     }
 
+    /// Sets the file as the recording of the given track.
+    ///
+    /// # Arguments
+    ///
+    /// * `track` - The track the file was matched to.
+    /// * `similarity` - How similar the file is to the track, from 0 to 1.
+    pub fn match_to_track(&mut self, track: &Track, similarity: f64) {
+        self.recording_id = Some(track.recording_id.clone());
+        self.similarity = Some(similarity);
+        self.track_id = Some(track.id.clone());
+    }
+
+    /// Returns how similar the file is to the given track, from 0 to 1, by its recording, title,
+    /// disc and track numbers, and length. Only what's known of the file is compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The tags of the file.
+    /// * `track` - The track to compare to.
+    pub fn track_similarity(&self, metadata: &Metadata, track: &Track) -> f64 {
+        let mut parts = vec![];
+        if let Some(recording_id) = &self.recording_id {
+            let similarity = (*recording_id == track.recording_id) as u8 as f64;
+            parts.push((similarity, RECORDING_WEIGHT));
+        }
+        if let Some(title) = metadata.title().filter(|title| !title.trim().is_empty()) {
+            parts.push((similarity2(&title, &track.title), TITLE_WEIGHT));
+        }
+        if let Some((disc_number, number)) = metadata.track_position() {
+            let similarity = (number == track.number) as u8 as f64;
+            parts.push((similarity, TRACK_NUMBER_WEIGHT));
+            let similarity = (disc_number == track.disc_number) as u8 as f64;
+            parts.push((similarity, DISC_NUMBER_WEIGHT));
+        }
+        if let (Some(duration), false) = (self.duration, track.length.is_zero()) {
+            let difference = duration.abs_diff(track.length);
+            let similarity = 1.0
+                - difference.min(MAX_LENGTH_DIFFERENCE).as_secs_f64()
+                    / MAX_LENGTH_DIFFERENCE.as_secs_f64();
+            parts.push((similarity, LENGTH_WEIGHT));
+        }
+        match parts.is_empty() {
+            true => 0.0,
+            false => linear_combination_of_weights(&parts),
+        }
+    }
+
     /*/// Returns the title of the title, if any.
     pub fn title(&self) -> Option<String> {
         // self.tags.iter().filter_map(|t| t.title()).next()
@@ -149,9 +337,21 @@ impl PartialEq for AudioFile {
     fn eq(&self, other: &Self) -> bool {
         // exclude events
         self.acoust_id == other.acoust_id
+            && self.audio_checksum == other.audio_checksum
+            && self.bitrate == other.bitrate
+            && self.channel_mode == other.channel_mode
+            && self.cluster_id == other.cluster_id
+            && self.duration == other.duration
+            && self.encoder == other.encoder
+            && self.encoder_delay == other.encoder_delay
+            && self.encoder_padding == other.encoder_padding
             && self.fingerprint == other.fingerprint
+            && self.fingerprint_recording_id == other.fingerprint_recording_id
             && self.id == other.id
             && self.recording_id == other.recording_id
+            && self.sample_rate == other.sample_rate
+            && self.scan_status == other.scan_status
+            && self.similarity == other.similarity
             && self.track_id == other.track_id
     }
 }
@@ -170,3 +370,228 @@ fn to_length_string(seconds: u64) -> String {
     let seconds = seconds % SECONDS_IN_MINUTE;
     format!("{}{}:{}", hour_part, minutes, seconds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{AlbumId, AudioFileId, FieldValue, TagFieldType};
+    use std::collections::HashMap;
+
+    fn audio_file(recording_id: Option<&str>, seconds: Option<u64>) -> AudioFile {
+        AudioFile {
+            acoust_id: None,
+            audio_checksum: None,
+            bitrate: None,
+            channel_mode: None,
+            cluster_id: None,
+            duration: seconds.map(Duration::from_secs),
+            encoder: None,
+            encoder_delay: None,
+            encoder_padding: None,
+            events: vec![],
+            fingerprint: None,
+            fingerprint_recording_id: None,
+            id: AudioFileId::new(PathBuf::from("/music/file.mp3")),
+            recording_id: recording_id.map(String::from),
+            sample_rate: None,
+            scan_status: ScanStatus::default(),
+            similarity: None,
+            track_id: None,
+        }
+    }
+
+    fn metadata(title: &str, disc_number: i32, number: i32) -> Metadata {
+        Metadata::new(
+            AudioFileId::new(PathBuf::from("/music/file.mp3")),
+            HashMap::from([
+                (TagFieldType::Title, FieldValue::Text(String::from(title))),
+                (
+                    TagFieldType::DiscNumber,
+                    FieldValue::Text(disc_number.to_string()),
+                ),
+                (
+                    TagFieldType::TrackNumber,
+                    FieldValue::Text(number.to_string()),
+                ),
+            ]),
+        )
+    }
+
+    fn track(number: i32, title: &str, recording_id: &str, seconds: u64) -> Track {
+        Track {
+            artist: String::from("Artist"),
+            artist_id: String::from("artist"),
+            artists: String::from("Artist"),
+            disc_number: 1,
+            disc_subtitle: String::new(),
+            events: vec![],
+            id: TrackId::new(AlbumId::new(String::from("album")), number.to_string()),
+            isrc: None,
+            length: Duration::from_secs(seconds),
+            media: String::from("CD"),
+            number,
+            original_release_date: String::from("2001-02-03"),
+            original_year: String::from("2001"),
+            recording_id: String::from(recording_id),
+            sort_artist: String::from("Artist"),
+            title: String::from(title),
+            total_tracks: 2,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "Expected {expected}, got {actual}!"
+        );
+    }
+
+    #[test]
+    fn track_similarity_weighs_each_comparison() {
+        let track = track(1, "Song", "recording", 180);
+        let total = RECORDING_WEIGHT
+            + TITLE_WEIGHT
+            + TRACK_NUMBER_WEIGHT
+            + DISC_NUMBER_WEIGHT
+            + LENGTH_WEIGHT;
+        let similarity = |audio_file: AudioFile, metadata: Metadata| {
+            audio_file.track_similarity(&metadata, &track)
+        };
+
+        assert_close(
+            similarity(
+                audio_file(Some("recording"), Some(180)),
+                metadata("Song", 1, 1),
+            ),
+            1.0,
+        );
+        assert_close(
+            similarity(audio_file(Some("other"), Some(180)), metadata("Song", 1, 1)),
+            1.0 - RECORDING_WEIGHT / total,
+        );
+        assert_close(
+            similarity(
+                audio_file(Some("recording"), Some(180)),
+                metadata("Song", 1, 2),
+            ),
+            1.0 - TRACK_NUMBER_WEIGHT / total,
+        );
+        assert_close(
+            similarity(
+                audio_file(Some("recording"), Some(180)),
+                metadata("Song", 2, 1),
+            ),
+            1.0 - DISC_NUMBER_WEIGHT / total,
+        );
+        // NOTE: half of the greatest length difference halves the length's similarity
+        assert_close(
+            similarity(
+                audio_file(Some("recording"), Some(195)),
+                metadata("Song", 1, 1),
+            ),
+            1.0 - LENGTH_WEIGHT / total / 2.0,
+        );
+        // NOTE: what isn't known of the file isn't compared
+        assert_close(
+            similarity(audio_file(None, None), metadata("Song", 1, 1)),
+            1.0,
+        );
+        assert_close(
+            audio_file(None, None).track_similarity(
+                &Metadata::new(AudioFileId::new(PathBuf::new()), HashMap::new()),
+                &track,
+            ),
+            0.0,
+        );
+    }
+
+    #[test]
+    fn assign_tracks_leaves_dissimilar_files_unmatched() {
+        let tracks = [track(1, "Song", "recording", 180)];
+        let audio_file = audio_file(Some("other"), Some(400));
+        let metadata = metadata("Completely Unrelated", 2, 9);
+
+        assert!(audio_file.track_similarity(&metadata, &tracks[0]) < MIN_TRACK_SIMILARITY);
+        assert_eq!(
+            AudioFile::assign_tracks(&[(&audio_file, &metadata)], &tracks),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn assign_tracks_never_gives_two_files_the_same_track() {
+        let tracks = [
+            track(1, "First Song", "first", 180),
+            track(2, "Second Song", "second", 200),
+        ];
+        // NOTE: both files' recordings are of the first track, but the second's tags fit the
+        // second track
+        let first = audio_file(Some("first"), Some(180));
+        let first_metadata = metadata("First Song", 1, 1);
+        let second = audio_file(Some("first"), Some(200));
+        let second_metadata = metadata("Second Song", 1, 2);
+        let third = audio_file(Some("first"), Some(180));
+        let third_metadata = metadata("First Song", 1, 1);
+
+        let assignment = AudioFile::assign_tracks(
+            &[
+                (&first, &first_metadata),
+                (&second, &second_metadata),
+                (&third, &third_metadata),
+            ],
+            &tracks,
+        );
+
+        let tracks = assignment
+            .iter()
+            .map(|assigned| assigned.map(|(track, _)| track))
+            .collect::<Vec<_>>();
+        assert_eq!(tracks[1], Some(1));
+        assert_eq!(tracks.iter().flatten().count(), 2);
+        assert!(tracks[0].is_none() || tracks[2].is_none());
+    }
+
+    #[test]
+    fn match_to_album_matches_the_most_similar_track() {
+        let album = Album {
+            artist: String::from("Artist"),
+            artist_id: String::from("artist"),
+            asin: None,
+            barcode: None,
+            catalog_number: None,
+            date: String::from("2001-02-03"),
+            events: vec![],
+            id: AlbumId::new(String::from("album")),
+            length: Duration::from_secs(380),
+            record_label: None,
+            release_country: String::from("XW"),
+            release_group_id: String::from("release-group"),
+            release_status: String::from("Official"),
+            release_type: None,
+            score: None,
+            script: None,
+            sort_artist: String::from("Artist"),
+            title: String::from("Album"),
+            total_discs: 1,
+        };
+        let tracks = [
+            track(1, "First Song", "first", 180),
+            track(2, "Second Song", "second", 200),
+        ];
+        let mut matched = audio_file(None, Some(200));
+
+        let track = matched
+            .match_to_album(&album, &tracks, &metadata("Second Song", 1, 2))
+            .unwrap();
+
+        assert_eq!(track.id, tracks[1].id);
+        assert_eq!(matched.track_id, Some(tracks[1].id.clone()));
+        assert_eq!(matched.recording_id.as_deref(), Some("second"));
+        assert_close(matched.similarity.unwrap(), 1.0);
+        let mut unrelated = audio_file(None, Some(600));
+        assert!(unrelated
+            .match_to_album(&album, &tracks, &metadata("Unrelated", 3, 9))
+            .is_err());
+        assert_eq!(unrelated.track_id, None);
+    }
+}
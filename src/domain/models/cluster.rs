@@ -0,0 +1,330 @@
+use super::{AudioFile, Metadata};
+use crate::domain::events::DomainEvent;
+use crate::infrastructure::musicbrainz::LuceneQuery;
+use crate::infrastructure::{similarity2, Entity, EntityId, Value};
+use std::cmp::{Eq, PartialEq, Reverse};
+use std::path::PathBuf;
+
+/// The lowest similarity between album titles, or between artists, for files to be clustered
+/// together.
+const CLUSTER_THRESHOLD: f64 = 0.7;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ClusterId {
+    pub value: String,
+}
+
+impl ClusterId {
+    pub fn new(value: String) -> Self {
+        Self { value }
+    }
+}
+
+impl EntityId for ClusterId {
+    fn to_string(&self) -> String {
+        String::from(self)
+    }
+}
+
+impl From<&ClusterId> for String {
+    fn from(id: &ClusterId) -> Self {
+        id.value.clone()
+    }
+}
+
+impl From<ClusterId> for String {
+    fn from(id: ClusterId) -> Self {
+        String::from(&id)
+    }
+}
+
+impl Value for ClusterId {}
+
+/// Represents a group of unmatched audio files that probably belong to the same album, so
+/// that they can be looked up together.
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    /// The album title most of the files are tagged with, or the name of their directory if
+    /// they have no album.
+    pub album: String,
+
+    /// The album artist, or else artist, most of the files are tagged with.
+    pub artist: String,
+
+    pub events: Vec<DomainEvent>,
+
+    pub id: ClusterId,
+}
+
+impl Cluster {
+    /// Groups the given audio files into clusters by their album, album artist and directory,
+    /// and sets the cluster ID of each of them. Files tagged with similar albums by similar
+    /// artists are clustered together wherever they are, and files without an album are
+    /// clustered by their directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The audio files to cluster, with their tags.
+    pub fn group(files: Vec<(AudioFile, Metadata)>) -> Vec<(Cluster, Vec<AudioFile>)> {
+        let mut groups: Vec<ClusterGroup> = vec![];
+        for (audio_file, metadata) in files {
+            let key = ClusterKey::new(&audio_file, &metadata);
+            match groups.iter_mut().find(|group| group.seed.accepts(&key)) {
+                Some(group) => group.files.push((audio_file, key)),
+                None => groups.push(ClusterGroup {
+                    seed: key.clone(),
+                    files: vec![(audio_file, key)],
+                }),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|group| {
+                let id = ClusterId::new(group.files[0].0.id.to_string());
+                let album = match group.seed.album.is_empty() {
+                    true => group
+                        .seed
+                        .directory
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    false => most_common(group.files.iter().map(|(_, key)| &key.album)),
+                };
+                let artist = most_common(group.files.iter().map(|(_, key)| &key.artist));
+                let audio_files = group
+                    .files
+                    .into_iter()
+                    .map(|(mut audio_file, _)| {
+                        audio_file.cluster_id = Some(id.clone());
+                        audio_file
+                    })
+                    .collect();
+                let cluster = Cluster {
+                    album,
+                    artist,
+                    events: vec![],
+                    id,
+                };
+                (cluster, audio_files)
+            })
+            .collect()
+    }
+
+    /// Returns a query for the MusicBrainz release search built from the cluster's album and
+    /// artist.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_count` - The number of files in the cluster.
+    pub fn release_query(&self, track_count: usize) -> LuceneQuery {
+        LuceneQuery::new()
+            .phrase("release", &self.album)
+            .phrase("artist", &self.artist)
+            .value("tracks", track_count)
+    }
+}
+
+impl Entity for Cluster {
+    type Id = ClusterId;
+
+    fn id(&self) -> &Self::Id
+    where
+        Self::Id: EntityId,
+    {
+        &self.id
+    }
+}
+
+impl Eq for Cluster {}
+
+impl PartialEq for Cluster {
+    fn eq(&self, other: &Self) -> bool {
+        // exclude events
+        self.album == other.album && self.artist == other.artist && self.id == other.id
+    }
+}
+
+/// Represents the files put in a cluster so far, and the file that started it.
+struct ClusterGroup {
+    seed: ClusterKey,
+    files: Vec<(AudioFile, ClusterKey)>,
+}
+
+/// Represents the values of a file that it's clustered by.
+#[derive(Clone)]
+struct ClusterKey {
+    album: String,
+    artist: String,
+    directory: PathBuf,
+}
+
+impl ClusterKey {
+    fn new(audio_file: &AudioFile, metadata: &Metadata) -> Self {
+        let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+        Self {
+            album: non_empty(metadata.album()).unwrap_or_default(),
+            artist: non_empty(metadata.album_artist())
+                .or_else(|| non_empty(metadata.artist()))
+                .unwrap_or_default(),
+            directory: audio_file
+                .id
+                .path
+                .parent()
+                .map(|directory| directory.to_path_buf())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns whether a file with the given key belongs in the cluster started by this key.
+    fn accepts(&self, other: &ClusterKey) -> bool {
+        match (self.album.is_empty(), other.album.is_empty()) {
+            (true, true) => self.directory == other.directory,
+            (false, false) => {
+                similarity2(&self.album, &other.album) >= CLUSTER_THRESHOLD
+                    && (self.artist.is_empty()
+                        || other.artist.is_empty()
+                        || similarity2(&self.artist, &other.artist) >= CLUSTER_THRESHOLD)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns the most common of the given non-empty values, preferring the first seen among
+/// equally common ones.
+fn most_common<'a>(values: impl Iterator<Item = &'a String>) -> String {
+    let values: Vec<&String> = values.filter(|value| !value.is_empty()).collect();
+    values
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, value)| {
+            let count = values.iter().filter(|other| other == value).count();
+            (count, Reverse(*index))
+        })
+        .map(|(_, value)| value.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{AudioFileId, FieldValue, ScanStatus, TagFieldType};
+    use std::collections::HashMap;
+
+    fn file(path: &str, tags: &[(TagFieldType, &str)]) -> (AudioFile, Metadata) {
+        let id = AudioFileId::new(PathBuf::from(path));
+        let audio_file = AudioFile {
+            acoust_id: None,
+            audio_checksum: None,
+            bitrate: None,
+            channel_mode: None,
+            cluster_id: None,
+            duration: None,
+            encoder: None,
+            encoder_delay: None,
+            encoder_padding: None,
+            events: vec![],
+            fingerprint: None,
+            fingerprint_recording_id: None,
+            id: id.clone(),
+            recording_id: None,
+            sample_rate: None,
+            scan_status: ScanStatus::default(),
+            similarity: None,
+            track_id: None,
+        };
+        let fields = tags
+            .iter()
+            .map(|(field_type, value)| (field_type.clone(), FieldValue::Text(value.to_string())))
+            .collect::<HashMap<_, _>>();
+        (audio_file, Metadata::new(id, fields))
+    }
+
+    fn paths(files: &[AudioFile]) -> Vec<&str> {
+        files
+            .iter()
+            .map(|audio_file| audio_file.id.path.to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn group_clusters_files_by_album_and_artist_ignoring_case_and_whitespace() {
+        let clusters = Cluster::group(vec![
+            file(
+                "/music/a/1.mp3",
+                &[
+                    (TagFieldType::Album, "Abbey Road"),
+                    (TagFieldType::Artist, "The Beatles"),
+                ],
+            ),
+            file(
+                "/music/b/2.mp3",
+                &[
+                    (TagFieldType::Album, "  abbey road "),
+                    (TagFieldType::Artist, "the beatles"),
+                ],
+            ),
+            file(
+                "/music/a/3.mp3",
+                &[
+                    (TagFieldType::Album, "Abbey Road"),
+                    (TagFieldType::Artist, "The Rolling Stones"),
+                ],
+            ),
+            file(
+                "/music/c/4.mp3",
+                &[
+                    (TagFieldType::Album, "Abbey Road"),
+                    (TagFieldType::AlbumArtist, "The Beatles"),
+                    (TagFieldType::Artist, "Paul McCartney"),
+                ],
+            ),
+        ]);
+
+        assert_eq!(clusters.len(), 2);
+        let (beatles, beatles_files) = &clusters[0];
+        assert_eq!(beatles.album, "Abbey Road");
+        assert_eq!(beatles.artist, "The Beatles");
+        assert_eq!(
+            paths(beatles_files),
+            ["/music/a/1.mp3", "/music/b/2.mp3", "/music/c/4.mp3"]
+        );
+        let (stones, stones_files) = &clusters[1];
+        assert_eq!(stones.artist, "The Rolling Stones");
+        assert_eq!(paths(stones_files), ["/music/a/3.mp3"]);
+    }
+
+    #[test]
+    fn group_clusters_files_without_an_album_by_directory() {
+        let clusters = Cluster::group(vec![
+            file("/music/a/1.mp3", &[(TagFieldType::Artist, "Artist")]),
+            file("/music/b/2.mp3", &[(TagFieldType::Album, " ")]),
+            file("/music/a/3.mp3", &[]),
+            file("/music/b/4.mp3", &[(TagFieldType::Album, "Album")]),
+        ]);
+
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].0.album, "a");
+        assert_eq!(clusters[0].0.artist, "Artist");
+        assert_eq!(paths(&clusters[0].1), ["/music/a/1.mp3", "/music/a/3.mp3"]);
+        assert_eq!(clusters[1].0.album, "b");
+        assert_eq!(paths(&clusters[1].1), ["/music/b/2.mp3"]);
+        assert_eq!(clusters[2].0.album, "Album");
+        assert_eq!(paths(&clusters[2].1), ["/music/b/4.mp3"]);
+    }
+
+    #[test]
+    fn group_sets_the_cluster_id_of_each_file() {
+        let clusters = Cluster::group(vec![
+            file("/music/a/1.mp3", &[(TagFieldType::Album, "Album")]),
+            file("/music/a/2.mp3", &[(TagFieldType::Album, "Album")]),
+        ]);
+
+        let (cluster, audio_files) = &clusters[0];
+        assert_eq!(cluster.id.value, "/music/a/1.mp3");
+        assert!(audio_files
+            .iter()
+            .all(|audio_file| audio_file.cluster_id.as_ref() == Some(&cluster.id)));
+    }
+}
@@ -0,0 +1,33 @@
+use super::Album;
+
+/// Represents a release found by looking up files by their tags, for the user to choose from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LookupResult {
+    pub album: Album,
+
+    /// The MusicBrainz ID of the recording found for a single file, or none if the release was
+    /// found for a whole cluster.
+    pub recording_id: Option<String>,
+
+    /// How well the release matches the files, from 0 to 1.
+    pub score: f64,
+}
+
+impl LookupResult {
+    /// Returns the given results ordered from best to worst by their scores, keeping only the
+    /// best result of each release.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The results to rank.
+    pub fn rank(mut results: Vec<LookupResult>) -> Vec<LookupResult> {
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let mut ranked: Vec<LookupResult> = vec![];
+        for result in results {
+            if !ranked.iter().any(|other| other.album.id == result.album.id) {
+                ranked.push(result);
+            }
+        }
+        ranked
+    }
+}
@@ -1,22 +1,84 @@
 use super::{Album, AudioFile, AudioFileId, FieldValue, TagFieldType, Track};
-use crate::infrastructure::{multi_map, MappingType};
+use crate::infrastructure::config::ReleaseWeights;
+use crate::infrastructure::musicbrainz::{credited_name, LuceneQuery, Release};
+use crate::infrastructure::{multi_map, similarity2, MappingType};
 use crate::{domain::events::DomainEvent, infrastructure::Entity};
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use regex::Regex;
+use sisko_lib::asf_content_description::AsfContentDescription;
+use sisko_lib::asf_content_descriptor::{AsfContentDescriptor, WM_PICTURE};
+use sisko_lib::asf_descriptor_value::AsfDescriptorValue;
+use sisko_lib::asf_extended_content_description::AsfExtendedContentDescription;
+use sisko_lib::asf_tag::AsfTag;
+use sisko_lib::flac_picture::FlacPicture;
 use sisko_lib::id3v2_frame::ID3v2Frame;
 use sisko_lib::id3v2_frame_fields::ID3v2FrameFields;
 use sisko_lib::id3v2_frame_flags::ID3v2FrameFlags;
 use sisko_lib::id3v2_frame_format_description::ID3v2FrameFormatDescription;
 use sisko_lib::id3v2_frame_header::ID3v2FrameHeader;
 use sisko_lib::id3v2_frame_status_messages::ID3v2FrameStatusMessages;
+use sisko_lib::matroska_simple_tag::MatroskaSimpleTag;
+use sisko_lib::matroska_tag::{
+    MatroskaTag, ALBUM_TARGET_TYPE_VALUE, PART_TARGET_TYPE_VALUE, TRACK_TARGET_TYPE_VALUE,
+};
+use sisko_lib::matroska_tags::MatroskaTags;
+use sisko_lib::mp4_data::Mp4Data;
+use sisko_lib::mp4_data_type::Mp4DataType;
+use sisko_lib::mp4_tag::Mp4Tag;
+use sisko_lib::mp4_tag_item::{Mp4TagItem, FREEFORM, ITUNES_MEAN};
+use sisko_lib::picture_type::PictureType;
+use sisko_lib::riff_info::RiffInfo;
+use sisko_lib::riff_info_field::RiffInfoField;
 use sisko_lib::text_encoding::TextEncoding;
+use sisko_lib::vorbis_comment::VorbisComment;
+use sisko_lib::vorbis_comment_field::VorbisCommentField;
 use sisko_lib::{
     id3v2_header::ID3v2Header, id3v2_header_flags::ID3v2HeaderFlags, id3v2_tag::ID3v2Tag,
     id3v2_version_number::ID3v2VersionNumber,
 };
-use std::cmp::{Eq, PartialEq};
+use std::cmp::{Eq, Ordering, PartialEq};
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// The difference in length at which a track's length stops counting towards a match.
+pub const MAX_LENGTH_DIFFERENCE: Duration = Duration::from_secs(30);
+
+/// The fields that matching a file to a track may set.
+const MATCH_FIELD_TYPES: [TagFieldType; 32] = [
+    TagFieldType::AcoustId,
+    TagFieldType::Album,
+    TagFieldType::AlbumArtist,
+    TagFieldType::AlbumArtistSortOrder,
+    TagFieldType::Artist,
+    TagFieldType::Artists,
+    TagFieldType::ArtistSortOrder,
+    TagFieldType::Asin,
+    TagFieldType::Barcode,
+    TagFieldType::CatalogNumber,
+    TagFieldType::Date,
+    TagFieldType::DiscNumber,
+    TagFieldType::DiscSubtitle,
+    TagFieldType::Isrc,
+    TagFieldType::Media,
+    TagFieldType::MusicBrainzArtistId,
+    TagFieldType::MusicBrainzRecordingId,
+    TagFieldType::MusicBrainzReleaseArtistId,
+    TagFieldType::MusicBrainzReleaseGroupId,
+    TagFieldType::MusicBrainzReleaseId,
+    TagFieldType::MusicBrainzTrackId,
+    TagFieldType::OriginalReleaseDate,
+    TagFieldType::OriginalYear,
+    TagFieldType::RecordLabel,
+    TagFieldType::ReleaseCountry,
+    TagFieldType::ReleaseStatus,
+    TagFieldType::ReleaseType,
+    TagFieldType::Script,
+    TagFieldType::Title,
+    TagFieldType::TotalDiscs,
+    TagFieldType::TotalTracks,
+    TagFieldType::TrackNumber,
+];
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MetadataField {
@@ -84,12 +146,175 @@ impl Metadata {
         }
     }
 
+    pub fn from_asf(audio_file_id: AudioFileId, tag: &AsfTag) -> Self {
+        let description_values = tag
+            .content_description
+            .iter()
+            .flat_map(|description| {
+                [
+                    (TagFieldType::Title, &description.title),
+                    (TagFieldType::Artist, &description.author),
+                ]
+            })
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(field_type, value)| (field_type, FieldValue::Text(value.clone())));
+        let descriptor_values = tag
+            .extended_content_description
+            .iter()
+            .flat_map(|description| description.descriptors.iter())
+            .map(|descriptor| (TagFieldType::from(descriptor), descriptor))
+            .into_group_map()
+            .into_iter()
+            .map(|(field_type, descriptors)| {
+                let texts = descriptors
+                    .iter()
+                    .filter_map(|descriptor| descriptor.value.text())
+                    .collect_vec();
+                let field_value = match (&field_type, texts.is_empty()) {
+                    (TagFieldType::CoverArt, _) => descriptors[0]
+                        .picture()
+                        .map(|picture| FieldValue::Binary(picture.to_bytes()))
+                        .unwrap_or(FieldValue::Unknown),
+                    (_, false) => FieldValue::Text(texts.join("; ")),
+                    (_, true) => FieldValue::Binary(descriptors[0].value.to_bytes()),
+                };
+                (field_type, field_value)
+            });
+        let values = description_values
+            .chain(descriptor_values)
+            .unique_by(|(field_type, _)| field_type.clone())
+            .collect();
+        Self::new(audio_file_id, values)
+    }
+
     pub fn from_id3v2(audio_file_id: AudioFileId, frames: &[ID3v2Frame]) -> Self {
         // NOTE: unique keeps first occurrence, so higher priority goes first
         let values = Self::parse_all(frames).into_iter().unique().collect();
         Self::new(audio_file_id, values)
     }
 
+    pub fn from_iff(
+        audio_file_id: AudioFileId,
+        frames: &[ID3v2Frame],
+        info: Option<&RiffInfo>,
+    ) -> Self {
+        // NOTE: unique keeps first occurrence, so the ID3v2 tag takes priority over the info
+        let info_values = info
+            .iter()
+            .flat_map(|info| info.fields.iter())
+            .map(|field| {
+                (
+                    TagFieldType::from(field),
+                    FieldValue::Text(field.value.clone()),
+                )
+            });
+        let values = Self::parse_all(frames)
+            .into_iter()
+            .chain(info_values)
+            .unique_by(|(field_type, _)| field_type.clone())
+            .collect();
+        Self::new(audio_file_id, values)
+    }
+
+    pub fn from_matroska(audio_file_id: AudioFileId, tags: &MatroskaTags) -> Self {
+        // NOTE: unique keeps first occurrence, so the first tag at a level takes priority
+        let values = tags
+            .tags
+            .iter()
+            .flat_map(|tag| {
+                tag.simple_tags
+                    .iter()
+                    .map(|simple_tag| {
+                        let field_type =
+                            TagFieldType::from_matroska(tag.target_type_value, &simple_tag.name);
+                        (field_type, simple_tag)
+                    })
+                    .into_group_map()
+                    .into_iter()
+                    .filter_map(|(field_type, simple_tags)| {
+                        let texts = simple_tags
+                            .iter()
+                            .filter_map(|simple_tag| simple_tag.string.clone())
+                            .collect_vec();
+                        let field_value = match (texts.is_empty(), &simple_tags[0].binary) {
+                            (false, _) => FieldValue::Text(texts.join("; ")),
+                            (true, Some(binary)) => FieldValue::Binary(binary.clone()),
+                            (true, None) => return None,
+                        };
+                        Some((field_type, field_value))
+                    })
+                    .collect_vec()
+            })
+            .unique_by(|(field_type, _)| field_type.clone())
+            .collect();
+        Self::new(audio_file_id, values)
+    }
+
+    pub fn from_mp4(audio_file_id: AudioFileId, tag: &Mp4Tag) -> Self {
+        let values = tag
+            .items
+            .iter()
+            .flat_map(Self::parse_mp4_item)
+            .into_group_map()
+            .into_iter()
+            .map(|(field_type, values)| {
+                let texts = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        FieldValue::Text(s) => Some(s),
+                        _ => None,
+                    })
+                    .collect_vec();
+                // NOTE: repeated items are joined like the values within an item
+                let field_value = match texts.len() == values.len() {
+                    true => FieldValue::Text(texts.into_iter().join("; ")),
+                    false => values[0].clone(),
+                };
+                (field_type, field_value)
+            })
+            .collect();
+        Self::new(audio_file_id, values)
+    }
+
+    pub fn from_vorbis(audio_file_id: AudioFileId, comment: &VorbisComment) -> Self {
+        let values = comment
+            .fields
+            .iter()
+            .map(|field| (TagFieldType::from(field), field))
+            .into_group_map()
+            .into_iter()
+            .map(|(field_type, fields)| {
+                let field_value = match field_type {
+                    TagFieldType::CoverArt => fields[0]
+                        .picture()
+                        .map(|picture| FieldValue::Binary(picture.to_bytes()))
+                        .unwrap_or(FieldValue::Unknown),
+                    _ => FieldValue::Text(fields.iter().map(|field| &field.value).join("; ")),
+                };
+                (field_type, field_value)
+            })
+            .collect();
+        Self::new(audio_file_id, values)
+    }
+
+    /// Returns the value of the album field, if there is one.
+    pub fn album(&self) -> Option<String> {
+        self.current_value(&TagFieldType::Album)
+            .and_then(|value| match value {
+                FieldValue::Text(s) => Some(s.clone()),
+                _ => None,
+            })
+    }
+
+    /// Returns the value of the album artist field, if there is one.
+    pub fn album_artist(&self) -> Option<String> {
+        self.current_value(&TagFieldType::AlbumArtist)
+            .and_then(|value| match value {
+                FieldValue::Text(s) => Some(s.clone()),
+                _ => None,
+            })
+    }
+
     /// Returns the value of the artist field, if there is one.
     pub fn artist(&self) -> Option<String> {
         self.current_value(&TagFieldType::Artist)
@@ -99,6 +324,100 @@ impl Metadata {
             })
     }
 
+    /// Returns the types of the fields whose values differ from the given tags, which were read
+    /// from the file about to be saved. Fields added or removed since are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - The tags as they are in the file.
+    pub fn changed_field_types(&self, original: &Metadata) -> Vec<TagFieldType> {
+        self.fields
+            .keys()
+            .chain(original.fields.keys())
+            .unique()
+            .filter(|field_type| self.current_value(field_type) != original.value(field_type))
+            .cloned()
+            .collect()
+    }
+
+    // This function is ripped from picard:
+    // https://github.com/metabrainz/picard/blob/95c8b72be586379f86bb853ed3b251e99b23f687/picard/metadata.py#L8
+    /// Returns the (similarity, weight) parts of comparing the file's original tags to the given
+    /// release. Only fields the tags have are compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `release` - The release to compare to.
+    /// * `recording_id` - The ID of the recording the file is thought to be, to find its track.
+    /// * `length` - The length of the file's audio, if known.
+    /// * `weights` - How much each comparison counts.
+    pub fn compare_to_release_parts(
+        &self,
+        release: &Release,
+        recording_id: &str,
+        length: Option<Duration>,
+        weights: &ReleaseWeights,
+    ) -> Vec<(f64, f64)> {
+        let mut parts = vec![];
+        let (media, track) = release
+            .media
+            .iter()
+            .flat_map(|media| media.tracks.iter().map(move |track| (media, track)))
+            .find(|(_, track)| track.recording.id == recording_id)
+            .map_or((release.media.first(), None), |(media, track)| {
+                (Some(media), Some(track))
+            });
+
+        if let Some(album) = self.original_text(&TagFieldType::Album) {
+            parts.push((similarity2(&album, &release.title), weights.album));
+        }
+        if let Some(date) = self.original_text(&TagFieldType::Date) {
+            let similarity = match (date == release.date, date.get(..4) == release.date.get(..4)) {
+                (true, _) => 1.0,
+                (false, true) => 0.8,
+                (false, false) => 0.0,
+            };
+            parts.push((similarity, weights.date));
+        }
+        if let Some(country) = self.original_text(&TagFieldType::ReleaseCountry) {
+            let similarity = (country.eq_ignore_ascii_case(&release.country)) as u8 as f64;
+            parts.push((similarity, weights.country));
+        }
+        if let (Some(format), Some(media)) = (self.original_text(&TagFieldType::Media), media) {
+            let similarity = (format.eq_ignore_ascii_case(&media.format)) as u8 as f64;
+            parts.push((similarity, weights.format));
+        }
+        if let (Some(total_tracks), Some(media)) = (
+            self.original_text(&TagFieldType::TotalTracks)
+                .and_then(|total_tracks| total_tracks.trim().parse::<i32>().ok()),
+            media,
+        ) {
+            let similarity = match total_tracks.cmp(&media.track_count) {
+                Ordering::Equal => 1.0,
+                Ordering::Less => 0.3,
+                Ordering::Greater => 0.0,
+            };
+            parts.push((similarity, weights.total_tracks));
+        }
+        if let Some(track) = track {
+            if let Some(title) = self.original_text(&TagFieldType::Title) {
+                parts.push((similarity2(&title, &track.title), weights.title));
+            }
+            if let Some(artist) = self.original_text(&TagFieldType::Artist) {
+                let track_artist = credited_name(&track.recording.artist_credit);
+                parts.push((similarity2(&artist, &track_artist), weights.artist));
+            }
+            if let (Some(length), Some(track_length)) = (length, track.length) {
+                let difference = length.abs_diff(Duration::from_millis(track_length));
+                let similarity = 1.0
+                    - difference.min(MAX_LENGTH_DIFFERENCE).as_secs_f64()
+                        / MAX_LENGTH_DIFFERENCE.as_secs_f64();
+                parts.push((similarity, weights.length));
+            }
+        }
+        parts
+    }
+
     pub fn current_value(&self, field_type: &TagFieldType) -> Option<&FieldValue> {
         self.new_value(field_type)
             .or_else(|| self.value(field_type))
@@ -114,7 +433,33 @@ impl Metadata {
             .any(|(_, field)| field.new_value.is_some())
     }
 
-    pub fn into_id3v2(&self) -> Vec<ID3v2Frame> {
+    pub fn into_asf(&self) -> AsfTag {
+        let text = |field_type: TagFieldType| match self.current_value(&field_type) {
+            Some(FieldValue::Text(s)) => s.clone(),
+            _ => String::new(),
+        };
+        let descriptors = self
+            .fields
+            .values()
+            .sorted_by_key(|field| field.field_type.to_string())
+            .filter_map(|field| Self::convert_asf_descriptor(field).ok())
+            .collect();
+        AsfTag {
+            content_description: Some(AsfContentDescription {
+                title: text(TagFieldType::Title),
+                author: text(TagFieldType::Artist),
+                ..Default::default()
+            }),
+            extended_content_description: Some(AsfExtendedContentDescription { descriptors }),
+        }
+    }
+
+    /// Returns the ID3v2 frames for these fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of ID3v2 to make the frames for.
+    pub fn into_id3v2(&self, version: &ID3v2VersionNumber) -> Result<Vec<ID3v2Frame>> {
         #[derive(Clone, Debug)]
         enum MultiFieldType {
             Date,
@@ -142,9 +487,16 @@ impl Metadata {
             }
         }
 
-        multi_map(
-            &self.fields.values().cloned().collect_vec(),
-            |f| Ok(Self::convert_frame(f)),
+        // NOTE: unknown fields, e.g. the ICMT of a WAV file, have no frame to be written as
+        let fields = self
+            .fields
+            .values()
+            .filter(|field| !matches!(field.field_type, TagFieldType::Unknown(_)))
+            .cloned()
+            .collect_vec();
+        Ok(multi_map(
+            &fields,
+            |f| Self::convert_frame(f, version),
             |g, v| -> Result<ID3v2Frame> {
                 Ok(match g {
                     PartialFieldGroup::Ipls => Self::convert_ipls(v),
@@ -157,8 +509,173 @@ impl Metadata {
                     MultiFieldType::Date => Self::convert_date(field)?,
                 })
             },
-        )
-        .unwrap()
+        )?
+        .into_iter()
+        .map(|frame| match version.major_number {
+            4 => frame,
+            // NOTE: ID3v2.3 has no UTF-8, so text is written as UTF-16 instead
+            _ => reencode_frame(frame, TextEncoding::Utf16Bom),
+        })
+        .collect())
+    }
+
+    /// Returns an ID3v2 tag of these fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `major_version` - The major version of ID3v2 to make the tag for, 3 or 4.
+    pub fn into_id3v2_tag(&self, major_version: u8) -> Result<ID3v2Tag> {
+        let version = ID3v2VersionNumber::new(major_version, 0);
+        let frames = self.into_id3v2(&version)?;
+        Ok(new_id3v2_tag(version, frames))
+    }
+
+    /// Returns an ID3v2 tag that keeps the given frames, which were read from the file about to
+    /// be saved, and replaces only the frames of the fields that changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames as they are in the file.
+    /// * `major_version` - The major version of ID3v2 to make the tag for, 3 or 4.
+    pub fn merge_into_id3v2_tag(
+        &self,
+        frames: &[ID3v2Frame],
+        major_version: u8,
+    ) -> Result<ID3v2Tag> {
+        let version = ID3v2VersionNumber::new(major_version, 0);
+        let original = Metadata::from_id3v2(self.audio_file_id.clone(), frames);
+        // NOTE: frames sisko has no field for, e.g. pictures and comments, are always kept
+        let mut changed = self
+            .changed_field_types(&original)
+            .into_iter()
+            .filter(|field_type| !matches!(field_type, TagFieldType::Unknown(_)))
+            .collect_vec();
+        // NOTE: fields that share a frame are all written when any of them changed
+        for group in [
+            vec![TagFieldType::TrackNumber, TagFieldType::TotalTracks],
+            vec![TagFieldType::DiscNumber, TagFieldType::TotalDiscs],
+            vec![
+                TagFieldType::Engineer,
+                TagFieldType::Mixer,
+                TagFieldType::Performer,
+                TagFieldType::Producer,
+            ],
+        ] {
+            if group.iter().any(|field_type| changed.contains(field_type)) {
+                changed.extend(group);
+            }
+        }
+        let kept = frames
+            .iter()
+            .filter(|frame| {
+                frame_field_types(frame)
+                    .iter()
+                    .all(|field_type| !changed.contains(field_type))
+            })
+            .cloned()
+            .map(|frame| match version.major_number {
+                4 => frame,
+                _ => reencode_frame(frame, TextEncoding::Utf16Bom),
+            });
+        let frames = kept
+            .chain(self.subset(&changed).into_id3v2(&version)?)
+            .collect_vec();
+        Ok(new_id3v2_tag(version, frames))
+    }
+
+    pub fn into_matroska(&self) -> Vec<MatroskaTag> {
+        let mut tags = [
+            ALBUM_TARGET_TYPE_VALUE,
+            PART_TARGET_TYPE_VALUE,
+            TRACK_TARGET_TYPE_VALUE,
+        ]
+        .map(MatroskaTag::new);
+        for field in self
+            .fields
+            .values()
+            .sorted_by_key(|field| field.field_type.to_string())
+        {
+            if let Ok((target_type_value, simple_tags)) = Self::convert_matroska_simple_tags(field)
+            {
+                if let Some(tag) = tags
+                    .iter_mut()
+                    .find(|tag| tag.target_type_value == target_type_value)
+                {
+                    tag.simple_tags.extend(simple_tags);
+                }
+            }
+        }
+        tags.into_iter()
+            .filter(|tag| !tag.simple_tags.is_empty())
+            .collect()
+    }
+
+    pub fn into_mp4(&self) -> Result<Vec<Mp4TagItem>> {
+        let (number_fields, other_fields): (Vec<&MetadataField>, Vec<&MetadataField>) =
+            self.fields.values().partition(|field| {
+                matches!(
+                    field.field_type,
+                    TagFieldType::DiscNumber
+                        | TagFieldType::TotalDiscs
+                        | TagFieldType::TrackNumber
+                        | TagFieldType::TotalTracks
+                )
+            });
+        let number_items = [
+            ("trkn", TagFieldType::TrackNumber, TagFieldType::TotalTracks),
+            ("disk", TagFieldType::DiscNumber, TagFieldType::TotalDiscs),
+        ]
+        .into_iter()
+        .filter_map(|(name, number_type, total_type)| {
+            let number = Self::parse_number(&number_fields, &number_type);
+            let total = Self::parse_number(&number_fields, &total_type);
+            match (number, total) {
+                (None, None) => None,
+                (number, total) => Some(Mp4TagItem::new_number_pair(
+                    name,
+                    number.unwrap_or(0),
+                    total.unwrap_or(0),
+                )),
+            }
+        });
+        other_fields
+            .into_iter()
+            .sorted_by_key(|field| field.field_type.to_string())
+            .map(Self::convert_mp4_item)
+            .chain(number_items.map(Ok))
+            .collect()
+    }
+
+    pub fn into_riff_info(&self) -> Vec<RiffInfoField> {
+        self.fields
+            .values()
+            .sorted_by_key(|field| field.field_type.to_string())
+            .filter_map(|field| {
+                let field_id = match field.field_type {
+                    TagFieldType::Album => "IPRD",
+                    TagFieldType::Artist => "IART",
+                    TagFieldType::Date => "ICRD",
+                    TagFieldType::Engineer => "IENG",
+                    TagFieldType::Media => "IMED",
+                    TagFieldType::Title => "INAM",
+                    TagFieldType::TrackNumber => "ITRK",
+                    _ => return None,
+                };
+                match field.value() {
+                    FieldValue::Text(s) => Some(RiffInfoField::new(field_id, s)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn into_vorbis(&self) -> Result<Vec<VorbisCommentField>> {
+        self.fields
+            .values()
+            .sorted_by_key(|field| field.field_type.to_string())
+            .map(Self::convert_vorbis_field)
+            .flatten_ok()
+            .collect()
     }
 
     pub fn iter<'a>(&'a self) -> MetadataIterator<'a> {
@@ -167,16 +684,80 @@ impl Metadata {
         }
     }
 
+    /// Returns the MusicBrainz recording ID the file is tagged with, if it has one.
+    pub fn musicbrainz_recording_id(&self) -> Option<String> {
+        self.current_id(&TagFieldType::MusicBrainzRecordingId)
+    }
+
+    /// Returns the MusicBrainz release ID the file is tagged with, if it has one.
+    pub fn musicbrainz_release_id(&self) -> Option<String> {
+        self.current_id(&TagFieldType::MusicBrainzReleaseId)
+    }
+
+    /// Returns the MusicBrainz release track ID the file is tagged with, if it has one.
+    pub fn musicbrainz_track_id(&self) -> Option<String> {
+        self.current_id(&TagFieldType::MusicBrainzTrackId)
+    }
+
     pub fn new_value(&self, field_type: &TagFieldType) -> Option<&FieldValue> {
         self.fields
             .get(field_type)
             .and_then(|field| field.new_value.as_ref())
     }
 
-    pub fn new_value_mut(&mut self, field_type: &TagFieldType) -> Option<&mut FieldValue> {
-        self.fields
-            .get_mut(field_type)
-            .and_then(|field| field.old_value.as_mut())
+    /// Returns a query for the MusicBrainz recording search built from the file's tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The length of the file's audio, if known.
+    pub fn recording_query(&self, length: Option<Duration>) -> LuceneQuery {
+        let mut query = LuceneQuery::new()
+            .phrase("recording", &self.title().unwrap_or_default())
+            .phrase("artist", &self.artist().unwrap_or_default())
+            .phrase("release", &self.album().unwrap_or_default());
+        if let Some((_, number)) = self.track_position() {
+            query = query.value("tnum", number);
+        }
+        if let Some(total_tracks) = self
+            .current_text(&TagFieldType::TotalTracks)
+            .and_then(|total_tracks| total_tracks.trim().parse::<i32>().ok())
+        {
+            query = query.value("tracksrelease", total_tracks);
+        }
+        if let Some(length) = length {
+            // MusicBrainz indexes lengths quantized to 2 seconds
+            query = query.value("qdur", length.as_millis() / 2000);
+        }
+        query
+    }
+
+    /// Rolls back the new values that matching the file to a track set, so that the fields
+    /// have their original values again. Fields the file didn't have are removed.
+    pub fn revert_match(&mut self) {
+        for field_type in &MATCH_FIELD_TYPES {
+            if let Some(field) = self.fields.get_mut(field_type) {
+                field.new_value = None;
+                if field.old_value.is_none() {
+                    self.fields.remove(field_type);
+                }
+            }
+        }
+    }
+
+    /// Returns the fields of the given types.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_types` - The types of the fields to keep.
+    pub fn subset(&self, field_types: &[TagFieldType]) -> Metadata {
+        Self::from_fields(
+            self.audio_file_id.clone(),
+            self.fields
+                .values()
+                .filter(|field| field_types.contains(&field.field_type))
+                .cloned()
+                .collect(),
+        )
     }
 
     /// Returns the value of the track title field, if there is one.
@@ -188,18 +769,60 @@ impl Metadata {
             })
     }
 
+    /// Returns the disc and track numbers the file is tagged with, if it has a track number.
+    /// The disc number is 1 if it isn't tagged. Numbers tagged as "3/12" are read as 3.
+    pub fn track_position(&self) -> Option<(i32, i32)> {
+        let number = |field_type: &TagFieldType| {
+            self.current_text(field_type)
+                .and_then(|text| text.split('/').next()?.trim().parse::<i32>().ok())
+        };
+        let track_number = number(&TagFieldType::TrackNumber)?;
+        let disc_number = number(&TagFieldType::DiscNumber).unwrap_or(1);
+        Some((disc_number, track_number))
+    }
+
+    /// Returns the current text value of the given field, if it has one.
+    fn current_text(&self, field_type: &TagFieldType) -> Option<String> {
+        match self.current_value(field_type) {
+            Some(FieldValue::Text(s)) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the current value of the given ID field, trimmed, if it has one that isn't empty.
+    fn current_id(&self, field_type: &TagFieldType) -> Option<String> {
+        self.current_text(field_type)
+            .map(|text| text.trim().to_owned())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Returns the original text value of the given field, if it has one that isn't empty.
+    fn original_text(&self, field_type: &TagFieldType) -> Option<String> {
+        match self.value(field_type) {
+            Some(FieldValue::Text(s)) if !s.trim().is_empty() => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    /// Sets the new value of the given field, keeping its original value.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_type` - The type of the field to update.
+    /// * `field_value` - The new value of the field.
     pub fn update(&mut self, field_type: &TagFieldType, field_value: FieldValue) {
-        if let Some(value) = self.new_value_mut(field_type) {
-            *value = field_value;
-        } else {
-            self.fields.insert(
-                field_type.clone(),
-                MetadataField {
-                    field_type: field_type.clone(),
-                    new_value: Some(field_value),
-                    old_value: None,
-                },
-            );
+        match self.fields.get_mut(field_type) {
+            Some(field) => field.new_value = Some(field_value),
+            None => {
+                self.fields.insert(
+                    field_type.clone(),
+                    MetadataField {
+                        field_type: field_type.clone(),
+                        new_value: Some(field_value),
+                        old_value: None,
+                    },
+                );
+            }
         }
     }
 
@@ -210,6 +833,10 @@ impl Metadata {
             &TagFieldType::Artist,
             FieldValue::Text(track.artist.clone()),
         );
+        self.update(
+            &TagFieldType::Artists,
+            FieldValue::Text(track.artists.clone()),
+        );
         self.update(&TagFieldType::Album, FieldValue::Text(album.title.clone()));
         self.update(
             &TagFieldType::TrackNumber,
@@ -331,27 +958,38 @@ impl Metadata {
     fn convert_date(field: &MetadataField) -> Result<Vec<ID3v2Frame>> {
         let date = match field.value() {
             FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            _ => return Err(anyhow!("Can't make TDAT and TYER frames of a binary date!")),
         };
-        let (year, month, day) = date
-            .split('-')
-            .collect_tuple()
-            .ok_or(anyhow!("Failed to parse date field into TDAT and TYER!"))?;
-        let year = vec![format!("{:04}", year)];
-        let dat = vec![format!("{:02}{:02}", month, day)];
-        Ok(vec![
-            new_text_frame("TDAT", dat),
-            new_text_frame("TYER", year),
-        ])
-    }
-
-    fn convert_frame(field: &MetadataField) -> ID3v2Frame {
+        let parts = date.split('-').collect_vec();
+        match parts[..] {
+            [year, month, day] => Ok(vec![
+                new_text_frame("TDAT", vec![format!("{:02}{:02}", month, day)]),
+                new_text_frame("TYER", vec![format!("{:04}", year)]),
+            ]),
+            // NOTE: dates are often only a year or a year and month, which TYER holds alone
+            [year] | [year, _] => Ok(vec![new_text_frame("TYER", vec![format!("{:04}", year)])]),
+            _ => Err(anyhow!("Failed to parse date field into TDAT and TYER!")),
+        }
+    }
+
+    fn convert_frame(field: &MetadataField, version: &ID3v2VersionNumber) -> Result<ID3v2Frame> {
         let text = match field.value() {
             FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            _ => {
+                return Err(anyhow!(
+                    "Can't convert a binary value of {} to an ID3v2 frame!",
+                    field.field_type
+                ))
+            }
         };
-        let text = vec![text];
-        match &field.field_type {
+        // NOTE: repeated values were joined when read, so ID3v2.4 splits them back into separate
+        // strings, while ID3v2.3 only allows one and separates them with a slash
+        let text = match (field.field_type.is_multi_valued(), version.major_number) {
+            (true, 4) => text.split("; ").map(String::from).collect_vec(),
+            (true, _) => vec![text.replace("; ", "/")],
+            (false, _) => vec![text],
+        };
+        Ok(match &field.field_type {
             TagFieldType::AcoustId => new_user_text_frame("ACOUSTID ID", text),
             TagFieldType::Album => new_text_frame("TALB", text),
             TagFieldType::AlbumArtist => new_text_frame("TPE2", text),
@@ -389,9 +1027,293 @@ impl Metadata {
             TagFieldType::Script => new_user_text_frame("SCRIPT", text),
             TagFieldType::Title => new_text_frame("TIT2", text),
             TagFieldType::Ufid(owner_id) => new_ufid_frame(owner_id.as_str(), text),
-            TagFieldType::Unknown(_) => todo!(),
-            _ => todo!(),
-        }
+            _ => {
+                return Err(anyhow!(
+                    "Can't convert {} to an ID3v2 frame!",
+                    field.field_type
+                ))
+            }
+        })
+    }
+
+    fn convert_asf_descriptor(field: &MetadataField) -> Result<AsfContentDescriptor> {
+        let name = match &field.field_type {
+            TagFieldType::AcoustId => "Acoustid/Id",
+            TagFieldType::Album => "WM/AlbumTitle",
+            TagFieldType::AlbumArtist => "WM/AlbumArtist",
+            TagFieldType::AlbumArtistSortOrder => "WM/AlbumArtistSortOrder",
+            TagFieldType::Artists => "WM/ARTISTS",
+            TagFieldType::ArtistSortOrder => "WM/ArtistSortOrder",
+            TagFieldType::Asin => "ASIN",
+            TagFieldType::Barcode => "WM/Barcode",
+            TagFieldType::CatalogNumber => "WM/CatalogNo",
+            TagFieldType::CoverArt => WM_PICTURE,
+            TagFieldType::Date => "WM/Year",
+            TagFieldType::DiscNumber => "WM/PartOfSet",
+            TagFieldType::DiscSubtitle => "WM/SetSubTitle",
+            TagFieldType::Isrc => "WM/ISRC",
+            TagFieldType::Lyricist => "WM/Writer",
+            TagFieldType::Media => "WM/Media",
+            TagFieldType::MusicBrainzArtistId => "MusicBrainz/Artist Id",
+            TagFieldType::MusicBrainzRecordingId => "MusicBrainz/Track Id",
+            TagFieldType::MusicBrainzReleaseArtistId => "MusicBrainz/Album Artist Id",
+            TagFieldType::MusicBrainzReleaseGroupId => "MusicBrainz/Release Group Id",
+            TagFieldType::MusicBrainzReleaseId => "MusicBrainz/Album Id",
+            TagFieldType::MusicBrainzTrackId => "MusicBrainz/Release Track Id",
+            TagFieldType::OriginalReleaseDate => "WM/OriginalReleaseTime",
+            TagFieldType::OriginalYear => "WM/OriginalReleaseYear",
+            TagFieldType::Producer => "WM/Producer",
+            TagFieldType::RecordLabel => "WM/Publisher",
+            TagFieldType::ReleaseCountry => "MusicBrainz/Album Release Country",
+            TagFieldType::ReleaseStatus => "MusicBrainz/Album Status",
+            TagFieldType::ReleaseType => "MusicBrainz/Album Type",
+            TagFieldType::Script => "WM/Script",
+            TagFieldType::TotalDiscs => "TotalDiscs",
+            TagFieldType::TotalTracks => "TotalTracks",
+            TagFieldType::TrackNumber => "WM/TrackNumber",
+            TagFieldType::Unknown(name) => name.as_str(),
+            field_type => {
+                return Err(anyhow!(
+                    "Can't convert {} to an ASF descriptor!",
+                    field_type
+                ))
+            }
+        };
+        // NOTE: descriptor names should be unique, so repeated values stay joined
+        let value = match (&field.field_type, field.value()) {
+            (TagFieldType::CoverArt, FieldValue::Binary(bytes)) => {
+                return Ok(AsfContentDescriptor::from_picture(&FlacPicture::parse(
+                    bytes,
+                )?))
+            }
+            (TagFieldType::CoverArt, _) => {
+                return Err(anyhow!("Cover art must be a binary FLAC picture block!"))
+            }
+            (_, FieldValue::Binary(bytes)) => AsfDescriptorValue::Bytes(bytes.clone()),
+            (_, FieldValue::Text(s)) => AsfDescriptorValue::Unicode(s.clone()),
+            (_, FieldValue::Unknown) => {
+                return Err(anyhow!(
+                    "Can't convert an unknown value to an ASF descriptor!"
+                ))
+            }
+        };
+        Ok(AsfContentDescriptor {
+            name: String::from(name),
+            value,
+        })
+    }
+
+    fn convert_matroska_simple_tags(
+        field: &MetadataField,
+    ) -> Result<(u64, Vec<MatroskaSimpleTag>)> {
+        let target_type_value = match field.field_type {
+            TagFieldType::Album
+            | TagFieldType::AlbumArtist
+            | TagFieldType::AlbumArtistSortOrder
+            | TagFieldType::Asin
+            | TagFieldType::Barcode
+            | TagFieldType::CatalogNumber
+            | TagFieldType::Date
+            | TagFieldType::MusicBrainzReleaseArtistId
+            | TagFieldType::MusicBrainzReleaseGroupId
+            | TagFieldType::MusicBrainzReleaseId
+            | TagFieldType::OriginalReleaseDate
+            | TagFieldType::OriginalYear
+            | TagFieldType::RecordLabel
+            | TagFieldType::ReleaseCountry
+            | TagFieldType::ReleaseStatus
+            | TagFieldType::ReleaseType
+            | TagFieldType::Script
+            | TagFieldType::TotalTracks => ALBUM_TARGET_TYPE_VALUE,
+            TagFieldType::DiscNumber | TagFieldType::DiscSubtitle | TagFieldType::TotalDiscs => {
+                PART_TARGET_TYPE_VALUE
+            }
+            _ => TRACK_TARGET_TYPE_VALUE,
+        };
+        let name = match &field.field_type {
+            TagFieldType::Album | TagFieldType::DiscSubtitle | TagFieldType::Title => Some("TITLE"),
+            TagFieldType::AlbumArtist | TagFieldType::Artist => Some("ARTIST"),
+            TagFieldType::CoverArt => {
+                return Err(anyhow!("Matroska cover art is an attachment, not a tag!"))
+            }
+            TagFieldType::Date => Some("DATE_RELEASED"),
+            TagFieldType::DiscNumber | TagFieldType::TrackNumber => Some("PART_NUMBER"),
+            TagFieldType::TotalDiscs | TagFieldType::TotalTracks => Some("TOTAL_PARTS"),
+            TagFieldType::Unknown(name) => {
+                if let FieldValue::Binary(bytes) = field.value() {
+                    let simple_tag = MatroskaSimpleTag {
+                        name: name.clone(),
+                        language: None,
+                        string: None,
+                        binary: Some(bytes.clone()),
+                        children: vec![],
+                    };
+                    return Ok((target_type_value, vec![simple_tag]));
+                }
+                None
+            }
+            _ => None,
+        };
+        // NOTE: the other fields share their names with Vorbis comments
+        let simple_tags = Self::convert_vorbis_field(field)?
+            .iter()
+            .map(|vorbis_field| {
+                MatroskaSimpleTag::new(name.unwrap_or(&vorbis_field.name), &vorbis_field.value)
+            })
+            .collect();
+        Ok((target_type_value, simple_tags))
+    }
+
+    fn convert_mp4_item(field: &MetadataField) -> Result<Mp4TagItem> {
+        let text = match field.value() {
+            FieldValue::Binary(bytes) => match field.field_type {
+                TagFieldType::CoverArt => {
+                    let picture = FlacPicture::parse(bytes)?;
+                    let data_type = Mp4DataType::from_mime_type(&picture.mime_type)
+                        .ok_or(anyhow!("MP4 cover art can't be {}!", picture.mime_type))?;
+                    return Ok(Mp4TagItem {
+                        name: String::from("covr"),
+                        data: vec![Mp4Data::new(data_type, picture.picture_data)],
+                    });
+                }
+                // NOTE: the data type of a binary item isn't kept, so it can't be written back
+                _ => {
+                    return Err(anyhow!(
+                        "Can't convert a binary value of {} to an MP4 item!",
+                        field.field_type
+                    ))
+                }
+            },
+            FieldValue::Text(s) => s.clone(),
+            FieldValue::Unknown => {
+                return Err(anyhow!("Can't convert an unknown value to an MP4 item!"))
+            }
+        };
+        let name = match &field.field_type {
+            TagFieldType::Album => String::from("\u{a9}alb"),
+            TagFieldType::AlbumArtist => String::from("aART"),
+            TagFieldType::AlbumArtistSortOrder => String::from("soaa"),
+            TagFieldType::Artist => String::from("\u{a9}ART"),
+            TagFieldType::ArtistSortOrder => String::from("soar"),
+            TagFieldType::Date => String::from("\u{a9}day"),
+            TagFieldType::Title => String::from("\u{a9}nam"),
+            TagFieldType::Unknown(name) => name.clone(),
+            field_type => {
+                let name = match field_type {
+                    TagFieldType::AcoustId => "Acoustid Id",
+                    TagFieldType::Artists => "ARTISTS",
+                    TagFieldType::Asin => "ASIN",
+                    TagFieldType::Barcode => "BARCODE",
+                    TagFieldType::CatalogNumber => "CATALOGNUMBER",
+                    TagFieldType::DiscSubtitle => "DISCSUBTITLE",
+                    TagFieldType::Engineer => "ENGINEER",
+                    TagFieldType::Isrc => "ISRC",
+                    TagFieldType::Lyricist => "LYRICIST",
+                    TagFieldType::Media => "MEDIA",
+                    TagFieldType::Mixer => "MIXER",
+                    TagFieldType::MusicBrainzArtistId => "MusicBrainz Artist Id",
+                    TagFieldType::MusicBrainzRecordingId => "MusicBrainz Track Id",
+                    TagFieldType::MusicBrainzReleaseArtistId => "MusicBrainz Album Artist Id",
+                    TagFieldType::MusicBrainzReleaseGroupId => "MusicBrainz Release Group Id",
+                    TagFieldType::MusicBrainzReleaseId => "MusicBrainz Album Id",
+                    TagFieldType::MusicBrainzTrackId => "MusicBrainz Release Track Id",
+                    TagFieldType::OriginalReleaseDate => "originaldate",
+                    TagFieldType::OriginalYear => "originalyear",
+                    TagFieldType::Performer => "PERFORMER",
+                    TagFieldType::Producer => "PRODUCER",
+                    TagFieldType::RecordLabel => "LABEL",
+                    TagFieldType::ReleaseCountry => "MusicBrainz Album Release Country",
+                    TagFieldType::ReleaseStatus => "MusicBrainz Album Status",
+                    TagFieldType::ReleaseType => "MusicBrainz Album Type",
+                    TagFieldType::Script => "SCRIPT",
+                    field_type => {
+                        return Err(anyhow!("Can't convert {} to an MP4 item!", field_type))
+                    }
+                };
+                format!("{}:{}:{}", FREEFORM, ITUNES_MEAN, name)
+            }
+        };
+        // NOTE: repeated values were joined when read, so they are split back apart
+        let values = match field.field_type.is_multi_valued() {
+            true => text.split("; ").map(String::from).collect_vec(),
+            false => vec![text],
+        };
+        Ok(Mp4TagItem::new_text(&name, &values))
+    }
+
+    fn convert_vorbis_field(field: &MetadataField) -> Result<Vec<VorbisCommentField>> {
+        let text = match field.value() {
+            FieldValue::Binary(bytes) => match field.field_type {
+                TagFieldType::CoverArt => {
+                    let picture = FlacPicture::parse(bytes)?;
+                    return Ok(vec![VorbisCommentField::from_picture(&picture)]);
+                }
+                _ => String::from_utf8(bytes.clone())?,
+            },
+            FieldValue::Text(s) => s.clone(),
+            FieldValue::Unknown => {
+                return Err(anyhow!(
+                    "Can't convert an unknown value to a Vorbis comment!"
+                ))
+            }
+        };
+        let name = match &field.field_type {
+            TagFieldType::AcoustId => "ACOUSTID_ID",
+            TagFieldType::Album => "ALBUM",
+            TagFieldType::AlbumArtist => "ALBUMARTIST",
+            TagFieldType::AlbumArtistSortOrder => "ALBUMARTISTSORT",
+            TagFieldType::Artist => "ARTIST",
+            TagFieldType::Artists => "ARTISTS",
+            TagFieldType::ArtistSortOrder => "ARTISTSORT",
+            TagFieldType::Asin => "ASIN",
+            TagFieldType::Barcode => "BARCODE",
+            TagFieldType::CatalogNumber => "CATALOGNUMBER",
+            TagFieldType::CoverArt => {
+                return Err(anyhow!("Cover art must be a binary FLAC picture block!"))
+            }
+            TagFieldType::Date => "DATE",
+            TagFieldType::DiscNumber => "DISCNUMBER",
+            TagFieldType::DiscSubtitle => "DISCSUBTITLE",
+            TagFieldType::Engineer => "ENGINEER",
+            TagFieldType::Isrc => "ISRC",
+            TagFieldType::Lyricist => "LYRICIST",
+            TagFieldType::Media => "MEDIA",
+            TagFieldType::Mixer => "MIXER",
+            TagFieldType::MusicBrainzArtistId => "MUSICBRAINZ_ARTISTID",
+            TagFieldType::MusicBrainzRecordingId => "MUSICBRAINZ_TRACKID",
+            TagFieldType::MusicBrainzReleaseArtistId => "MUSICBRAINZ_ALBUMARTISTID",
+            TagFieldType::MusicBrainzReleaseGroupId => "MUSICBRAINZ_RELEASEGROUPID",
+            TagFieldType::MusicBrainzReleaseId => "MUSICBRAINZ_ALBUMID",
+            TagFieldType::MusicBrainzTrackId => "MUSICBRAINZ_RELEASETRACKID",
+            TagFieldType::OriginalReleaseDate => "ORIGINALDATE",
+            TagFieldType::OriginalYear => "ORIGINALYEAR",
+            TagFieldType::Performer => "PERFORMER",
+            TagFieldType::Producer => "PRODUCER",
+            TagFieldType::RecordLabel => "LABEL",
+            TagFieldType::ReleaseCountry => "RELEASECOUNTRY",
+            TagFieldType::ReleaseStatus => "RELEASESTATUS",
+            TagFieldType::ReleaseType => "RELEASETYPE",
+            TagFieldType::Script => "SCRIPT",
+            TagFieldType::TotalDiscs => "TOTALDISCS",
+            TagFieldType::TotalTracks => "TOTALTRACKS",
+            TagFieldType::TrackNumber => "TRACKNUMBER",
+            TagFieldType::Title => "TITLE",
+            TagFieldType::Ufid(owner_id) => {
+                return Err(anyhow!(
+                    "Can't convert UFID:{} to a Vorbis comment!",
+                    owner_id
+                ))
+            }
+            TagFieldType::Unknown(name) => name.as_str(),
+        };
+        // NOTE: repeated fields were joined when read, so they are split back apart
+        Ok(match field.field_type.is_multi_valued() {
+            true => text
+                .split("; ")
+                .map(|value| VorbisCommentField::new(name, value))
+                .collect(),
+            false => vec![VorbisCommentField::new(name, &text)],
+        })
     }
 
     fn convert_ipls(fields: Vec<&MetadataField>) -> ID3v2Frame {
@@ -417,19 +1339,20 @@ impl Metadata {
             ))?;
         let disc = match disc.value() {
             FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            _ => return Err(anyhow!("Can't make a TPOS frame of a binary disc number!")),
         };
         let total = fields
             .iter()
             .find(|field| field.field_type == TagFieldType::TotalDiscs)
-            .ok_or(anyhow!(
-                "Failed to find total discs field to make TPOS frame!"
-            ))?;
-        let total = match total.value() {
-            FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            .map(|total| match total.value() {
+                FieldValue::Text(s) => Ok(s.clone()),
+                _ => Err(anyhow!("Can't make a TPOS frame of binary total discs!")),
+            })
+            .transpose()?;
+        let text = match total {
+            Some(total) => vec![format!("{}/{}", disc, total)],
+            None => vec![disc],
         };
-        let text = vec![format!("{}/{}", disc, total)];
         Ok(new_text_frame("TPOS", text))
     }
 
@@ -442,22 +1365,81 @@ impl Metadata {
             ))?;
         let track = match track.value() {
             FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            _ => return Err(anyhow!("Can't make a TRCK frame of a binary track number!")),
         };
         let total = fields
             .iter()
             .find(|field| field.field_type == TagFieldType::TotalTracks)
-            .ok_or(anyhow!(
-                "Failed to find total tracks field to make TRCK frame!"
-            ))?;
-        let total = match total.value() {
-            FieldValue::Text(s) => s.clone(),
-            _ => todo!(),
+            .map(|total| match total.value() {
+                FieldValue::Text(s) => Ok(s.clone()),
+                _ => Err(anyhow!("Can't make a TRCK frame of binary total tracks!")),
+            })
+            .transpose()?;
+        // NOTE: the total is optional, e.g. a WAV file's ITRK only has the track number
+        let text = match total {
+            Some(total) => vec![format!("{}/{}", track, total)],
+            None => vec![track],
         };
-        let text = vec![format!("{}/{}", track, total)];
         Ok(new_text_frame("TRCK", text))
     }
 
+    fn parse_mp4_item(item: &Mp4TagItem) -> Vec<(TagFieldType, FieldValue)> {
+        let field_type = TagFieldType::from(item);
+        match item.name.as_str() {
+            "disk" | "trkn" => {
+                let total_type = match field_type {
+                    TagFieldType::DiscNumber => TagFieldType::TotalDiscs,
+                    _ => TagFieldType::TotalTracks,
+                };
+                match item.number_pair() {
+                    Some((number, 0)) => vec![(field_type, FieldValue::Text(number.to_string()))],
+                    Some((number, total)) => vec![
+                        (field_type, FieldValue::Text(number.to_string())),
+                        (total_type, FieldValue::Text(total.to_string())),
+                    ],
+                    None => vec![],
+                }
+            }
+            "covr" => item
+                .data
+                .iter()
+                .find_map(|data| {
+                    data.data_type.mime_type().map(|mime_type| FlacPicture {
+                        picture_type: PictureType::CoverFront,
+                        mime_type: String::from(mime_type),
+                        description: String::new(),
+                        width: 0,
+                        height: 0,
+                        color_depth: 0,
+                        indexed_colors: 0,
+                        picture_data: data.value.clone(),
+                    })
+                })
+                .map(|picture| vec![(field_type, FieldValue::Binary(picture.to_bytes()))])
+                .unwrap_or_default(),
+            _ => {
+                let texts = item.texts();
+                match (texts.is_empty(), item.data.first()) {
+                    (false, _) => vec![(field_type, FieldValue::Text(texts.join("; ")))],
+                    (true, Some(data)) => {
+                        vec![(field_type, FieldValue::Binary(data.value.clone()))]
+                    }
+                    (true, None) => vec![],
+                }
+            }
+        }
+    }
+
+    fn parse_number(fields: &[&MetadataField], field_type: &TagFieldType) -> Option<u16> {
+        fields
+            .iter()
+            .find(|field| &field.field_type == field_type)
+            .and_then(|field| match field.value() {
+                FieldValue::Text(s) => s.trim().parse().ok(),
+                _ => None,
+            })
+    }
+
     fn parse_all(frames: &[ID3v2Frame]) -> Vec<(TagFieldType, FieldValue)> {
         #[derive(Clone, Debug)]
         enum MultiFrameType {
@@ -503,8 +1485,9 @@ impl Metadata {
 
     fn parse_frame(frame: &ID3v2Frame) -> (TagFieldType, FieldValue) {
         match &frame.fields {
+            // NOTE: ID3v2.4 separates repeated values with nulls, which are joined like the others
             ID3v2FrameFields::TextFields { encoding: _, text } => {
-                (TagFieldType::from(frame), FieldValue::Text(text[0].clone()))
+                (TagFieldType::from(frame), FieldValue::Text(text.join("; ")))
             }
             ID3v2FrameFields::UserDefinedTextFields {
                 encoding: _,
@@ -512,7 +1495,7 @@ impl Metadata {
                 value,
             } => (
                 TagFieldType::from(frame),
-                FieldValue::Text(value[0].clone()),
+                FieldValue::Text(value.join("; ")),
             ),
             ID3v2FrameFields::UniqueFileIdentifierFields { owner_id: _, id } => {
                 match String::from_utf8(id.clone()) {
@@ -649,32 +1632,6 @@ impl PartialEq for Metadata {
     }
 }
 
-impl From<&Metadata> for ID3v2Tag {
-    fn from(value: &Metadata) -> Self {
-        let frames = value.into_id3v2();
-        let header = ID3v2Header {
-            file_identifier: String::from("ID3"),
-            version: ID3v2VersionNumber::new(4, 0),
-            flags: ID3v2HeaderFlags {
-                unsynchronisation: false,
-                has_extended_header: false,
-                is_experimental: false,
-                has_footer: false,
-            },
-            size: frames.iter().map(|frame| frame.header.size).sum(),
-        };
-        ID3v2Tag {
-            header,
-            // TODO: save extended header with restriction settings
-            extended_header: None,
-            frames,
-            // padding and footer determined when saving
-            padding: 0,
-            footer: None,
-        }
-    }
-}
-
 fn new_text_frame(id: &str, text: Vec<String>) -> ID3v2Frame {
     let encoding_size: u32 = 1;
     let info_size: u32 = text.iter().map(|s| s.len() as u32 + 1).sum();
@@ -767,6 +1724,100 @@ fn new_ufid_frame(owner_id: &str, text: Vec<String>) -> ID3v2Frame {
     }
 }
 
+/// Returns the types of the fields the given frame holds, several for the frames that combine
+/// fields, e.g. TRCK.
+///
+/// # Arguments
+///
+/// * `frame` - The frame to get the field types of.
+fn frame_field_types(frame: &ID3v2Frame) -> Vec<TagFieldType> {
+    match frame.header.frame_id.as_str() {
+        "IPLS" => vec![
+            TagFieldType::Engineer,
+            TagFieldType::Mixer,
+            TagFieldType::Performer,
+            TagFieldType::Producer,
+        ],
+        "TDAT" | "TYER" => vec![TagFieldType::Date],
+        "TPOS" => vec![TagFieldType::DiscNumber, TagFieldType::TotalDiscs],
+        "TRCK" => vec![TagFieldType::TrackNumber, TagFieldType::TotalTracks],
+        _ => vec![TagFieldType::from(frame)],
+    }
+}
+
+fn new_id3v2_tag(version: ID3v2VersionNumber, frames: Vec<ID3v2Frame>) -> ID3v2Tag {
+    let header = ID3v2Header {
+        file_identifier: String::from("ID3"),
+        size: frames.iter().map(|frame| frame.header.size + 10).sum(),
+        version,
+        flags: ID3v2HeaderFlags {
+            unsynchronisation: false,
+            has_extended_header: false,
+            is_experimental: false,
+            has_footer: false,
+        },
+    };
+    ID3v2Tag {
+        header,
+        // TODO: save extended header with restriction settings
+        extended_header: None,
+        frames,
+        // padding and footer determined when saving
+        padding: 0,
+        footer: None,
+    }
+}
+
+fn reencode_frame(frame: ID3v2Frame, encoding: TextEncoding) -> ID3v2Frame {
+    let fields = match frame.fields {
+        ID3v2FrameFields::AttachedPictureFields {
+            encoding: _,
+            mime_type,
+            picture_type,
+            description,
+            picture_data,
+        } => ID3v2FrameFields::AttachedPictureFields {
+            encoding,
+            mime_type,
+            picture_type,
+            description,
+            picture_data,
+        },
+        ID3v2FrameFields::CommentsFields {
+            encoding: _,
+            language,
+            description,
+            text,
+        } => ID3v2FrameFields::CommentsFields {
+            encoding,
+            language,
+            description,
+            text,
+        },
+        ID3v2FrameFields::TextFields { encoding: _, text } => {
+            ID3v2FrameFields::TextFields { encoding, text }
+        }
+        ID3v2FrameFields::UserDefinedTextFields {
+            encoding: _,
+            description,
+            value,
+        } => ID3v2FrameFields::UserDefinedTextFields {
+            encoding,
+            description,
+            value,
+        },
+        fields => return ID3v2Frame { fields, ..frame },
+    };
+    let size = fields.to_bytes().len() as u32;
+    ID3v2Frame {
+        header: ID3v2FrameHeader {
+            size,
+            ..frame.header
+        },
+        fields,
+    }
+}
+
 /*
 TALB = Album
 UFID:http://musicbrainz.org = MusicBrainz Recording Id
@@ -802,3 +1853,280 @@ TYER+TDAT = Date
 TDAT = Date of the Recording = Month-day part of Date
 TYER = Year part of Date
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::linear_combination_of_weights;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn text_field(field_type: TagFieldType, text: &str) -> MetadataField {
+        MetadataField {
+            field_type,
+            new_value: None,
+            old_value: Some(FieldValue::Text(String::from(text))),
+        }
+    }
+
+    fn frame_text(frame: &ID3v2Frame) -> Vec<String> {
+        match &frame.fields {
+            ID3v2FrameFields::TextFields { text, .. } => text.clone(),
+            fields => panic!("Expected text fields, got {:?}!", fields),
+        }
+    }
+
+    #[test]
+    fn convert_frame_separates_artists_with_a_slash_in_id3v23() {
+        let field = text_field(TagFieldType::Artist, "Artist A; Artist B");
+
+        let frame = Metadata::convert_frame(&field, &ID3v2VersionNumber::new(3, 0)).unwrap();
+
+        assert_eq!(frame.header.frame_id, "TPE1");
+        assert_eq!(frame_text(&frame), ["Artist A/Artist B"]);
+    }
+
+    #[test]
+    fn convert_frame_splits_artists_into_strings_in_id3v24() {
+        let field = text_field(TagFieldType::Artist, "Artist A; Artist B");
+
+        let frame = Metadata::convert_frame(&field, &ID3v2VersionNumber::new(4, 0)).unwrap();
+
+        assert_eq!(frame.header.frame_id, "TPE1");
+        assert_eq!(frame_text(&frame), ["Artist A", "Artist B"]);
+    }
+
+    #[test]
+    fn convert_frame_keeps_single_valued_text() {
+        let field = text_field(TagFieldType::Title, "Foo; Bar");
+
+        for major_version in [3, 4] {
+            let frame = Metadata::convert_frame(&field, &ID3v2VersionNumber::new(major_version, 0))
+                .unwrap();
+
+            assert_eq!(frame.header.frame_id, "TIT2");
+            assert_eq!(frame_text(&frame), ["Foo; Bar"]);
+        }
+    }
+
+    #[test]
+    fn convert_trck_writes_the_total_only_if_there_is_one() {
+        let track = text_field(TagFieldType::TrackNumber, "3");
+        let total = text_field(TagFieldType::TotalTracks, "12");
+
+        let frame = Metadata::convert_trck(vec![&track, &total]).unwrap();
+        assert_eq!(frame_text(&frame), ["3/12"]);
+
+        let frame = Metadata::convert_trck(vec![&track]).unwrap();
+        assert_eq!(frame_text(&frame), ["3"]);
+    }
+
+    #[test]
+    fn convert_tpos_fails_on_a_binary_total() {
+        let disc = text_field(TagFieldType::DiscNumber, "1");
+        let total = MetadataField {
+            field_type: TagFieldType::TotalDiscs,
+            new_value: None,
+            old_value: Some(FieldValue::Binary(vec![2])),
+        };
+
+        assert!(Metadata::convert_tpos(vec![&disc, &total]).is_err());
+    }
+
+    fn release(title: &str, date: &str, country: &str, format: &str, track_title: &str) -> Release {
+        let artist_credit = json!([{
+            "name": "The Artist",
+            "joinphrase": "",
+            "artist": {
+                "type-id": null,
+                "sort-name": "Artist, The",
+                "name": "The Artist",
+                "id": "artist",
+                "disambiguation": ""
+            }
+        }]);
+        let track = json!({
+            "length": 180000,
+            "id": "track",
+            "number": "1",
+            "title": track_title,
+            "position": 1,
+            "recording": {
+                "title": track_title,
+                "id": "recording",
+                "length": 180000,
+                "first-release-date": date,
+                "video": false,
+                "disambiguation": "",
+                "isrcs": [],
+                "artist-credit": artist_credit
+            }
+        });
+        let media = json!({
+            "format-id": "",
+            "position": 1,
+            "track-count": 10,
+            "track-offset": 0,
+            "title": "",
+            "format": format,
+            "tracks": [track]
+        });
+        let release_group = json!({
+            "artist-credit": [],
+            "disambiguation": "",
+            "first-release-date": date,
+            "id": "release-group",
+            "primary-type": "Album",
+            "primary-type-id": null,
+            "secondary-types": [],
+            "secondary-type-ids": [],
+            "title": title
+        });
+        serde_json::from_value(json!({
+            "disambiguation": "",
+            "barcode": null,
+            "artist-credit": artist_credit,
+            "id": title,
+            "media": [media],
+            "packaging": null,
+            "date": date,
+            "text-representation": {"language": null, "script": null},
+            "status-id": "",
+            "asin": null,
+            "title": title,
+            "status": "Official",
+            "packaging-id": null,
+            "cover-art-archive": {
+                "count": 0,
+                "darkened": false,
+                "artwork": false,
+                "front": false,
+                "back": false
+            },
+            "quality": "normal",
+            "release-events": [],
+            "country": country,
+            "release-group": release_group,
+            "label-info": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_to_release_ranks_the_release_the_tags_fit_first() {
+        let metadata = Metadata::new(
+            AudioFileId::new(std::path::PathBuf::from("/music/file.mp3")),
+            HashMap::from([
+                (TagFieldType::Album, "Abbey Road"),
+                (TagFieldType::Artist, "The Artist"),
+                (TagFieldType::Date, "1969-09-26"),
+                (TagFieldType::Media, "CD"),
+                (TagFieldType::ReleaseCountry, "GB"),
+                (TagFieldType::Title, "Come Together"),
+                (TagFieldType::TotalTracks, "10"),
+            ])
+            .into_iter()
+            .map(|(field_type, text)| (field_type, FieldValue::Text(String::from(text))))
+            .collect(),
+        );
+        let weights = ReleaseWeights::default();
+        let score = |release: &Release| {
+            linear_combination_of_weights(&metadata.compare_to_release_parts(
+                release,
+                "recording",
+                Some(Duration::from_secs(181)),
+                &weights,
+            ))
+        };
+
+        let original = release("Abbey Road", "1969-09-26", "GB", "CD", "Come Together");
+        let reissue = release(
+            "Abbey Road (Super Deluxe)",
+            "2019-09-27",
+            "US",
+            "Digital Media",
+            "Come Together (2019 mix)",
+        );
+
+        assert!(score(&original) > 0.95);
+        assert!(score(&original) > score(&reissue));
+    }
+
+    fn tagged(tags: &[(TagFieldType, &str)]) -> Metadata {
+        Metadata::new(
+            AudioFileId::new(PathBuf::from("/music/file.mp3")),
+            tags.iter()
+                .map(|(field_type, text)| (field_type.clone(), FieldValue::Text(text.to_string())))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn update_keeps_the_original_value() {
+        let mut metadata = tagged(&[(TagFieldType::Title, "Original")]);
+
+        metadata.update(&TagFieldType::Title, FieldValue::Text(String::from("New")));
+        metadata.update(
+            &TagFieldType::Album,
+            FieldValue::Text(String::from("Album")),
+        );
+
+        assert_eq!(metadata.title(), Some(String::from("New")));
+        assert_eq!(
+            metadata.value(&TagFieldType::Title),
+            Some(&FieldValue::Text(String::from("Original")))
+        );
+        assert_eq!(metadata.album(), Some(String::from("Album")));
+        assert_eq!(metadata.value(&TagFieldType::Album), None);
+    }
+
+    #[test]
+    fn revert_match_restores_the_original_tags() {
+        let mut metadata = tagged(&[
+            (TagFieldType::Title, "Original"),
+            (TagFieldType::Lyricist, "Lyricist"),
+        ]);
+        metadata.update(&TagFieldType::Title, FieldValue::Text(String::from("New")));
+        metadata.update(
+            &TagFieldType::Album,
+            FieldValue::Text(String::from("Album")),
+        );
+        metadata.update(
+            &TagFieldType::Lyricist,
+            FieldValue::Text(String::from("Edited")),
+        );
+
+        metadata.revert_match();
+
+        assert_eq!(metadata.title(), Some(String::from("Original")));
+        assert!(!metadata.fields.contains_key(&TagFieldType::Album));
+        assert_eq!(
+            metadata.current_value(&TagFieldType::Lyricist),
+            Some(&FieldValue::Text(String::from("Edited")))
+        );
+    }
+
+    #[test]
+    fn musicbrainz_ids_are_trimmed_and_empty_ids_are_none() {
+        let mut metadata = tagged(&[
+            (TagFieldType::MusicBrainzRecordingId, " recording "),
+            (TagFieldType::MusicBrainzReleaseId, "old-release"),
+            (TagFieldType::MusicBrainzTrackId, "  "),
+        ]);
+        metadata.update(
+            &TagFieldType::MusicBrainzReleaseId,
+            FieldValue::Text(String::from("release")),
+        );
+
+        assert_eq!(
+            metadata.musicbrainz_recording_id(),
+            Some(String::from("recording"))
+        );
+        assert_eq!(
+            metadata.musicbrainz_release_id(),
+            Some(String::from("release"))
+        );
+        assert_eq!(metadata.musicbrainz_track_id(), None);
+        assert_eq!(tagged(&[]).musicbrainz_release_id(), None);
+    }
+}
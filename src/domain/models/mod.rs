@@ -1,6 +1,11 @@
 mod album;
 mod audio_file;
+mod cluster;
+mod lookup_result;
 mod metadata;
+mod recording_candidate;
+mod release_version;
+mod scan_status;
 mod tag_field;
 mod tag_field_type;
 mod tag_type;
@@ -8,7 +13,12 @@ mod track;
 
 pub use album::*;
 pub use audio_file::*;
+pub use cluster::*;
+pub use lookup_result::*;
 pub use metadata::*;
+pub use recording_candidate::*;
+pub use release_version::*;
+pub use scan_status::*;
 pub use tag_field::*;
 pub use tag_field_type::*;
 pub use tag_type::*;
@@ -0,0 +1,118 @@
+use crate::infrastructure::acoustid::AcoustIdResult;
+
+/// Represents a recording that an audio file's fingerprint may be of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordingCandidate {
+    /// The ID of the AcoustID result the recording was found in.
+    pub acoust_id: String,
+
+    /// The MusicBrainz ID of the recording.
+    pub recording_id: String,
+
+    /// How closely the fingerprint matched, from 0 to 1.
+    pub score: f64,
+}
+
+impl RecordingCandidate {
+    /// Returns the recordings of the given AcoustID results with at least the given score, best
+    /// first. A recording found in several results is kept once, with its best score.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The results of an AcoustID lookup.
+    /// * `min_score` - The lowest score a result may have to be a candidate.
+    pub fn from_results(results: &[AcoustIdResult], min_score: f64) -> Vec<RecordingCandidate> {
+        let mut candidates: Vec<RecordingCandidate> = vec![];
+        for result in results.iter().filter(|result| result.score >= min_score) {
+            for recording in &result.recordings {
+                match candidates
+                    .iter_mut()
+                    .find(|candidate| candidate.recording_id == recording.id)
+                {
+                    Some(candidate) if candidate.score < result.score => {
+                        candidate.acoust_id = result.id.clone();
+                        candidate.score = result.score;
+                    }
+                    Some(_) => {}
+                    None => candidates.push(RecordingCandidate {
+                        acoust_id: result.id.clone(),
+                        recording_id: recording.id.clone(),
+                        score: result.score,
+                    }),
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::acoustid::AcoustIdRecording;
+
+    fn result(id: &str, score: f64, recording_ids: &[&str]) -> AcoustIdResult {
+        AcoustIdResult {
+            id: String::from(id),
+            score,
+            recordings: recording_ids
+                .iter()
+                .map(|recording_id| AcoustIdRecording {
+                    id: String::from(*recording_id),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn from_results_orders_candidates_by_score() {
+        let results = [
+            result("weak", 0.6, &["a"]),
+            result("strong", 0.9, &["b", "c"]),
+        ];
+
+        let candidates = RecordingCandidate::from_results(&results, 0.5);
+
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| candidate.recording_id.as_str())
+                .collect::<Vec<_>>(),
+            ["b", "c", "a"]
+        );
+        assert_eq!(candidates[0].acoust_id, "strong");
+        assert_eq!(candidates[2].score, 0.6);
+    }
+
+    #[test]
+    fn from_results_drops_results_below_the_threshold() {
+        let results = [
+            result("strong", 0.9, &["a"]),
+            result("weak", 0.4, &["b"]),
+            result("empty", 0.95, &[]),
+        ];
+
+        let candidates = RecordingCandidate::from_results(&results, 0.5);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].recording_id, "a");
+        assert!(RecordingCandidate::from_results(&results, 0.99).is_empty());
+    }
+
+    #[test]
+    fn from_results_keeps_a_recording_once_with_its_best_score() {
+        let results = [result("weak", 0.7, &["a"]), result("strong", 0.8, &["a"])];
+
+        let candidates = RecordingCandidate::from_results(&results, 0.5);
+
+        assert_eq!(
+            candidates,
+            vec![RecordingCandidate {
+                acoust_id: String::from("strong"),
+                recording_id: String::from("a"),
+                score: 0.8,
+            }]
+        );
+    }
+}
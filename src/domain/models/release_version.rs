@@ -0,0 +1,135 @@
+use super::Album;
+use crate::infrastructure::musicbrainz::Release;
+
+/// Represents a release of the release group of a matched album, for the user to switch the
+/// album to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseVersion {
+    pub album: Album,
+
+    /// The formats of the release's media, such as "2×CD".
+    pub formats: String,
+
+    /// The number of tracks on all of the release's media.
+    pub track_count: i32,
+}
+
+impl ReleaseVersion {
+    /// Returns the version of the given release, loaded as the given album.
+    ///
+    /// # Arguments
+    ///
+    /// * `release` - The release to describe.
+    /// * `album` - The album the release was loaded as.
+    pub fn new(release: &Release, album: Album) -> Self {
+        let mut formats: Vec<(&str, usize)> = vec![];
+        for media in &release.media {
+            let format = match media.format.is_empty() {
+                true => "(unknown)",
+                false => media.format.as_str(),
+            };
+            match formats.last_mut() {
+                Some((last, count)) if *last == format => *count += 1,
+                _ => formats.push((format, 1)),
+            }
+        }
+        let formats = formats
+            .into_iter()
+            .map(|(format, count)| match count {
+                1 => format.to_owned(),
+                _ => format!("{}×{}", count, format),
+            })
+            .collect::<Vec<String>>()
+            .join(" + ");
+        Self {
+            album,
+            formats,
+            track_count: release.media.iter().map(|media| media.track_count).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn release(media: &[(&str, i32)]) -> Release {
+        let media: Vec<Value> = media
+            .iter()
+            .enumerate()
+            .map(|(index, (format, track_count))| {
+                json!({
+                    "format-id": "",
+                    "position": index + 1,
+                    "track-count": track_count,
+                    "track-offset": 0,
+                    "tracks": [],
+                    "title": "",
+                    "format": format
+                })
+            })
+            .collect();
+        let release_group = json!({
+            "artist-credit": [],
+            "disambiguation": "",
+            "first-release-date": "2001-02-03",
+            "id": "release-group",
+            "primary-type": "Album",
+            "primary-type-id": null,
+            "secondary-types": [],
+            "secondary-type-ids": [],
+            "title": "Album"
+        });
+        serde_json::from_value(json!({
+            "disambiguation": "",
+            "barcode": null,
+            "id": "release",
+            "media": media,
+            "packaging": null,
+            "date": "2001-02-03",
+            "text-representation": {"language": "eng", "script": "Latn"},
+            "status-id": "4e304316-386d-3409-af2e-78857eec5cfe",
+            "asin": null,
+            "title": "Album",
+            "status": "Official",
+            "packaging-id": null,
+            "cover-art-archive": {
+                "count": 0,
+                "darkened": false,
+                "artwork": false,
+                "front": false,
+                "back": false
+            },
+            "quality": "normal",
+            "release-events": [],
+            "country": "XW",
+            "release-group": release_group,
+            "label-info": [],
+            "artist-credit": []
+        }))
+        .unwrap()
+    }
+
+    fn version(media: &[(&str, i32)]) -> ReleaseVersion {
+        let release = release(media);
+        ReleaseVersion::new(&release, Album::from(&release))
+    }
+
+    #[test]
+    fn new_counts_consecutive_media_of_a_format() {
+        let version = version(&[("CD", 10), ("CD", 12), ("DVD", 3)]);
+
+        assert_eq!(version.formats, "2×CD + DVD");
+        assert_eq!(version.track_count, 25);
+    }
+
+    #[test]
+    fn new_names_media_without_a_format_unknown() {
+        assert_eq!(
+            version(&[("Vinyl", 6), ("", 1), ("Vinyl", 6)]).formats,
+            "Vinyl + (unknown) + Vinyl"
+        );
+        assert_eq!(version(&[]).formats, "");
+    }
+}
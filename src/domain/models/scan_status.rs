@@ -0,0 +1,69 @@
+use crate::infrastructure::Value;
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+
+/// Represents the outcome of scanning an audio file for a match.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ScanStatus {
+    /// The file hasn't been scanned.
+    #[default]
+    Unscanned,
+
+    /// The file was scanned, but no candidate matched a track.
+    NoMatch,
+
+    /// The file was scanned and matched to a track.
+    Matched,
+}
+
+impl ScanStatus {
+    pub fn as_str(&self) -> &str {
+        match &self {
+            ScanStatus::Unscanned => "Unscanned",
+            ScanStatus::NoMatch => "NoMatch",
+            ScanStatus::Matched => "Matched",
+        }
+    }
+}
+
+impl Display for ScanStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl TryFrom<&str> for ScanStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        match s {
+            "Unscanned" => Ok(ScanStatus::Unscanned),
+            "NoMatch" => Ok(ScanStatus::NoMatch),
+            "Matched" => Ok(ScanStatus::Matched),
+            _ => Err(anyhow!("Unknown scan status: {}!", s)),
+        }
+    }
+}
+
+impl Value for ScanStatus {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_reads_back_every_status() {
+        for status in [
+            ScanStatus::Unscanned,
+            ScanStatus::NoMatch,
+            ScanStatus::Matched,
+        ] {
+            assert_eq!(ScanStatus::try_from(status.as_str()).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn try_from_fails_on_an_unknown_status() {
+        assert!(ScanStatus::try_from("Scanning").is_err());
+    }
+}
@@ -1,5 +1,10 @@
 use crate::infrastructure::Value;
 use regex::Regex;
+use sisko_lib::asf_content_descriptor::AsfContentDescriptor;
+use sisko_lib::matroska_tag::{ALBUM_TARGET_TYPE_VALUE, PART_TARGET_TYPE_VALUE};
+use sisko_lib::mp4_tag_item::{Mp4TagItem, FREEFORM, ITUNES_MEAN};
+use sisko_lib::riff_info_field::RiffInfoField;
+use sisko_lib::vorbis_comment_field::VorbisCommentField;
 use sisko_lib::{id3v2_frame::ID3v2Frame, id3v2_frame_fields::ID3v2FrameFields};
 use std::fmt::Display;
 
@@ -16,6 +21,7 @@ pub enum TagFieldType {
     Asin,
     Barcode,
     CatalogNumber,
+    CoverArt,
     Date,
     DiscNumber,
     DiscSubtitle,
@@ -76,6 +82,7 @@ impl TagFieldType {
             TagFieldType::Artists => String::from("Artists"),
             TagFieldType::Barcode => String::from("Barcode"),
             TagFieldType::CatalogNumber => String::from("Catalog Number"),
+            TagFieldType::CoverArt => String::from("Cover Art"),
             TagFieldType::Date => String::from("Date"),
             TagFieldType::DiscNumber => String::from("Disc Number"),
             TagFieldType::MusicBrainzArtistId => String::from("MusicBrainz Artist Id"),
@@ -94,6 +101,43 @@ impl TagFieldType {
             TagFieldType::TrackNumber => String::from("Track Number"),
         }
     }
+
+    /// Returns whether the field may hold several values, which are joined by "; " when read.
+    /// Other fields are written as one value even if it contains "; ".
+    pub fn is_multi_valued(&self) -> bool {
+        matches!(
+            self,
+            TagFieldType::Artist
+                | TagFieldType::Artists
+                | TagFieldType::Engineer
+                | TagFieldType::Mixer
+                | TagFieldType::MusicBrainzArtistId
+                | TagFieldType::MusicBrainzReleaseArtistId
+                | TagFieldType::Performer
+                | TagFieldType::Producer
+        )
+    }
+
+    /// Returns the field type of a Matroska simple tag with the given name at the given target level.
+    /// The names that are the same at every level follow the Vorbis comment names.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_type_value` - The logical level of the tag's target (e.g. 50 for an album).
+    /// * `name` - The name of the simple tag.
+    pub fn from_matroska(target_type_value: u64, name: &str) -> Self {
+        match (target_type_value, name) {
+            (ALBUM_TARGET_TYPE_VALUE, "ARTIST") => Self::AlbumArtist,
+            (ALBUM_TARGET_TYPE_VALUE, "TITLE") => Self::Album,
+            (ALBUM_TARGET_TYPE_VALUE, "TOTAL_PARTS") => Self::TotalTracks,
+            (PART_TARGET_TYPE_VALUE, "PART_NUMBER") => Self::DiscNumber,
+            (PART_TARGET_TYPE_VALUE, "TITLE") => Self::DiscSubtitle,
+            (PART_TARGET_TYPE_VALUE, "TOTAL_PARTS") => Self::TotalDiscs,
+            (_, "DATE_RELEASED") => Self::Date,
+            (_, "PART_NUMBER") => Self::TrackNumber,
+            (_, name) => Self::from(&VorbisCommentField::new(name, "")),
+        }
+    }
 }
 
 impl Display for TagFieldType {
@@ -102,6 +146,47 @@ impl Display for TagFieldType {
     }
 }
 
+impl From<&AsfContentDescriptor> for TagFieldType {
+    fn from(descriptor: &AsfContentDescriptor) -> Self {
+        match descriptor.name.as_str() {
+            "Acoustid/Id" => Self::AcoustId,
+            "ASIN" => Self::Asin,
+            "MusicBrainz/Album Artist Id" => Self::MusicBrainzReleaseArtistId,
+            "MusicBrainz/Album Id" => Self::MusicBrainzReleaseId,
+            "MusicBrainz/Album Release Country" => Self::ReleaseCountry,
+            "MusicBrainz/Album Status" => Self::ReleaseStatus,
+            "MusicBrainz/Album Type" => Self::ReleaseType,
+            "MusicBrainz/Artist Id" => Self::MusicBrainzArtistId,
+            "MusicBrainz/Release Group Id" => Self::MusicBrainzReleaseGroupId,
+            "MusicBrainz/Release Track Id" => Self::MusicBrainzTrackId,
+            "MusicBrainz/Track Id" => Self::MusicBrainzRecordingId,
+            "TotalDiscs" => Self::TotalDiscs,
+            "TotalTracks" => Self::TotalTracks,
+            "WM/AlbumArtist" => Self::AlbumArtist,
+            "WM/AlbumArtistSortOrder" => Self::AlbumArtistSortOrder,
+            "WM/AlbumTitle" => Self::Album,
+            "WM/ARTISTS" => Self::Artists,
+            "WM/ArtistSortOrder" => Self::ArtistSortOrder,
+            "WM/Barcode" => Self::Barcode,
+            "WM/CatalogNo" => Self::CatalogNumber,
+            "WM/ISRC" => Self::Isrc,
+            "WM/Media" => Self::Media,
+            "WM/OriginalReleaseTime" => Self::OriginalReleaseDate,
+            "WM/OriginalReleaseYear" => Self::OriginalYear,
+            "WM/PartOfSet" => Self::DiscNumber,
+            "WM/Picture" => Self::CoverArt,
+            "WM/Producer" => Self::Producer,
+            "WM/Publisher" => Self::RecordLabel,
+            "WM/Script" => Self::Script,
+            "WM/SetSubTitle" => Self::DiscSubtitle,
+            "WM/TrackNumber" => Self::TrackNumber,
+            "WM/Writer" => Self::Lyricist,
+            "WM/Year" => Self::Date,
+            name => Self::Unknown(String::from(name)),
+        }
+    }
+}
+
 impl From<&ID3v2Frame> for TagFieldType {
     fn from(frame: &ID3v2Frame) -> Self {
         match frame.header.frame_id.as_str() {
@@ -156,6 +241,117 @@ impl From<&ID3v2Frame> for TagFieldType {
     }
 }
 
+impl From<&Mp4TagItem> for TagFieldType {
+    fn from(item: &Mp4TagItem) -> Self {
+        let freeform_prefix = format!("{}:{}:", FREEFORM, ITUNES_MEAN);
+        match item.name.strip_prefix(&freeform_prefix) {
+            Some(name) => match name.to_uppercase().as_str() {
+                "ACOUSTID ID" => Self::AcoustId,
+                "ARTISTS" => Self::Artists,
+                "ASIN" => Self::Asin,
+                "BARCODE" => Self::Barcode,
+                "CATALOGNUMBER" => Self::CatalogNumber,
+                "DISCSUBTITLE" => Self::DiscSubtitle,
+                "ENGINEER" => Self::Engineer,
+                "ISRC" => Self::Isrc,
+                "LABEL" => Self::RecordLabel,
+                "LYRICIST" => Self::Lyricist,
+                "MEDIA" => Self::Media,
+                "MIXER" => Self::Mixer,
+                "MUSICBRAINZ ALBUM ARTIST ID" => Self::MusicBrainzReleaseArtistId,
+                "MUSICBRAINZ ALBUM ID" => Self::MusicBrainzReleaseId,
+                "MUSICBRAINZ ALBUM RELEASE COUNTRY" => Self::ReleaseCountry,
+                "MUSICBRAINZ ALBUM STATUS" => Self::ReleaseStatus,
+                "MUSICBRAINZ ALBUM TYPE" => Self::ReleaseType,
+                "MUSICBRAINZ ARTIST ID" => Self::MusicBrainzArtistId,
+                "MUSICBRAINZ RELEASE GROUP ID" => Self::MusicBrainzReleaseGroupId,
+                "MUSICBRAINZ RELEASE TRACK ID" => Self::MusicBrainzTrackId,
+                "MUSICBRAINZ TRACK ID" => Self::MusicBrainzRecordingId,
+                "ORIGINALDATE" => Self::OriginalReleaseDate,
+                "ORIGINALYEAR" => Self::OriginalYear,
+                "PERFORMER" => Self::Performer,
+                "PRODUCER" => Self::Producer,
+                "SCRIPT" => Self::Script,
+                _ => Self::Unknown(item.name.clone()),
+            },
+            None => match item.name.as_str() {
+                "aART" => Self::AlbumArtist,
+                "covr" => Self::CoverArt,
+                "disk" => Self::DiscNumber,
+                "soaa" => Self::AlbumArtistSortOrder,
+                "soar" => Self::ArtistSortOrder,
+                "trkn" => Self::TrackNumber,
+                "\u{a9}alb" => Self::Album,
+                "\u{a9}ART" => Self::Artist,
+                "\u{a9}day" => Self::Date,
+                "\u{a9}nam" => Self::Title,
+                _ => Self::Unknown(item.name.clone()),
+            },
+        }
+    }
+}
+
+impl From<&RiffInfoField> for TagFieldType {
+    fn from(field: &RiffInfoField) -> Self {
+        match field.field_id.as_str() {
+            "IART" => Self::Artist,
+            "ICRD" => Self::Date,
+            "IENG" => Self::Engineer,
+            "IMED" => Self::Media,
+            "INAM" => Self::Title,
+            "IPRD" => Self::Album,
+            "IPRT" | "ITRK" => Self::TrackNumber,
+            field_id => Self::Unknown(String::from(field_id)),
+        }
+    }
+}
+
+impl From<&VorbisCommentField> for TagFieldType {
+    fn from(field: &VorbisCommentField) -> Self {
+        match field.key().as_str() {
+            "ACOUSTID_ID" => Self::AcoustId,
+            "ALBUM" => Self::Album,
+            "ALBUMARTIST" => Self::AlbumArtist,
+            "ALBUMARTISTSORT" => Self::AlbumArtistSortOrder,
+            "ARTIST" => Self::Artist,
+            "ARTISTS" => Self::Artists,
+            "ARTISTSORT" => Self::ArtistSortOrder,
+            "ASIN" => Self::Asin,
+            "BARCODE" => Self::Barcode,
+            "CATALOGNUMBER" => Self::CatalogNumber,
+            "DATE" => Self::Date,
+            "DISCNUMBER" => Self::DiscNumber,
+            "DISCSUBTITLE" => Self::DiscSubtitle,
+            "DISCTOTAL" | "TOTALDISCS" => Self::TotalDiscs,
+            "ENGINEER" => Self::Engineer,
+            "ISRC" => Self::Isrc,
+            "LABEL" => Self::RecordLabel,
+            "LYRICIST" => Self::Lyricist,
+            "MEDIA" => Self::Media,
+            "METADATA_BLOCK_PICTURE" => Self::CoverArt,
+            "MIXER" => Self::Mixer,
+            "MUSICBRAINZ_ALBUMARTISTID" => Self::MusicBrainzReleaseArtistId,
+            "MUSICBRAINZ_ALBUMID" => Self::MusicBrainzReleaseId,
+            "MUSICBRAINZ_ARTISTID" => Self::MusicBrainzArtistId,
+            "MUSICBRAINZ_RELEASEGROUPID" => Self::MusicBrainzReleaseGroupId,
+            "MUSICBRAINZ_RELEASETRACKID" => Self::MusicBrainzTrackId,
+            "MUSICBRAINZ_TRACKID" => Self::MusicBrainzRecordingId,
+            "ORIGINALDATE" => Self::OriginalReleaseDate,
+            "ORIGINALYEAR" => Self::OriginalYear,
+            "PERFORMER" => Self::Performer,
+            "PRODUCER" => Self::Producer,
+            "RELEASECOUNTRY" => Self::ReleaseCountry,
+            "RELEASESTATUS" => Self::ReleaseStatus,
+            "RELEASETYPE" => Self::ReleaseType,
+            "SCRIPT" => Self::Script,
+            "TITLE" => Self::Title,
+            "TRACKNUMBER" => Self::TrackNumber,
+            "TRACKTOTAL" | "TOTALTRACKS" => Self::TotalTracks,
+            key => Self::Unknown(String::from(key)),
+        }
+    }
+}
+
 impl From<String> for TagFieldType {
     fn from(s: String) -> Self {
         match s.as_str() {
@@ -182,6 +378,7 @@ impl From<String> for TagFieldType {
             "Artists" => TagFieldType::Artists,
             "Barcode" => TagFieldType::Barcode,
             "Catalog Number" => TagFieldType::CatalogNumber,
+            "Cover Art" => TagFieldType::CoverArt,
             "Date" => TagFieldType::Date,
             "Disc Number" => TagFieldType::DiscNumber,
             "MusicBrainz Artist Id" => TagFieldType::MusicBrainzArtistId,
@@ -1,6 +1,8 @@
 use super::AlbumId;
 use crate::domain::events::DomainEvent;
-use crate::infrastructure::{musicbrainz, Entity, EntityId};
+use crate::infrastructure::musicbrainz::{self, credited_name, credited_sort_name};
+use crate::infrastructure::{Entity, EntityId};
+use itertools::Itertools;
 use std::cmp::{Eq, PartialEq};
 use std::time::Duration;
 
@@ -36,8 +38,12 @@ impl From<TrackId> for String {
 
 #[derive(Clone, Debug)]
 pub struct Track {
+    /// The artists as they're credited, joined by their join phrases.
     pub artist: String,
+    /// The MusicBrainz IDs of the credited artists, separated by "; ".
     pub artist_id: String,
+    /// The names of the credited artists, separated by "; ".
+    pub artists: String,
     pub disc_number: i32,
     pub disc_subtitle: String,
     pub events: Vec<DomainEvent>,
@@ -61,14 +67,11 @@ impl Track {
         track: &musicbrainz::MediaTrack,
     ) -> Self {
         let length = track.length.map(Duration::from_millis).unwrap_or_default();
-        let track_artist = track.recording.artist_credit.first();
+        let credits = &track.recording.artist_credit;
         Self {
-            artist: track_artist
-                .map(|a| a.artist.name.clone())
-                .unwrap_or_default(),
-            artist_id: track_artist
-                .map(|a| a.artist.id.clone())
-                .unwrap_or_default(),
+            artist: credited_name(credits),
+            artist_id: credits.iter().map(|c| &c.artist.id).join("; "),
+            artists: credits.iter().map(|c| &c.name).join("; "),
             disc_number: media.position,
             disc_subtitle: media.title.clone(),
             events: vec![],
@@ -80,9 +83,7 @@ impl Track {
             original_release_date: track.recording.first_release_date.clone(),
             original_year: track.recording.first_release_date[0..4].to_string(),
             recording_id: track.recording.id.clone(),
-            sort_artist: track_artist
-                .map(|a| a.artist.sort_name.clone())
-                .unwrap_or_default(),
+            sort_artist: credited_sort_name(credits),
             title: track.title.clone(),
             total_tracks: media.track_count,
         }
@@ -125,6 +126,7 @@ impl PartialEq for Track {
     fn eq(&self, other: &Self) -> bool {
         self.artist == other.artist
             && self.artist_id == other.artist_id
+            && self.artists == other.artists
             && self.disc_number == other.disc_number
             && self.disc_subtitle == other.disc_subtitle
             && self.id == other.id
@@ -140,3 +142,69 @@ impl PartialEq for Track {
             && self.total_tracks == other.total_tracks
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn new_credits_every_artist() {
+        let media: musicbrainz::Media = serde_json::from_value(json!({
+            "format-id": "9712d52a-4509-3d4b-a1a2-67c88c643e31",
+            "position": 1,
+            "track-count": 1,
+            "track-offset": 0,
+            "title": "",
+            "format": "CD",
+            "tracks": [{
+                "length": 180000,
+                "id": "track",
+                "number": "1",
+                "title": "Song",
+                "position": 1,
+                "recording": {
+                    "title": "Song",
+                    "id": "recording",
+                    "length": 180000,
+                    "first-release-date": "2001-02-03",
+                    "video": false,
+                    "disambiguation": "",
+                    "isrcs": [],
+                    "artist-credit": [
+                        {
+                            "name": "The Artist",
+                            "joinphrase": " & ",
+                            "artist": {
+                                "type-id": null,
+                                "sort-name": "Artist, The",
+                                "name": "The Artist",
+                                "id": "artist-a",
+                                "disambiguation": ""
+                            }
+                        },
+                        {
+                            "name": "Guest",
+                            "joinphrase": "",
+                            "artist": {
+                                "type-id": null,
+                                "sort-name": "Guest",
+                                "name": "Guest",
+                                "id": "artist-b",
+                                "disambiguation": ""
+                            }
+                        }
+                    ]
+                }
+            }]
+        }))
+        .unwrap();
+
+        let track = Track::new("album", &media, &media.tracks[0]);
+
+        assert_eq!(track.artist, "The Artist & Guest");
+        assert_eq!(track.artist_id, "artist-a; artist-b");
+        assert_eq!(track.artists, "The Artist; Guest");
+        assert_eq!(track.sort_artist, "Artist, The & Guest");
+    }
+}
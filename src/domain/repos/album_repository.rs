@@ -80,7 +80,8 @@ impl AlbumRepository {
                 script,
                 sort_artist,
                 title,
-                total_discs)
+                total_discs,
+                score)
             VALUES (
                 :id,
                 :artist,
@@ -98,7 +99,8 @@ impl AlbumRepository {
                 :script,
                 :sort_artist,
                 :title,
-                :total_discs)
+                :total_discs,
+                :score)
         "#;
         Database::instance()
             .connection
@@ -123,6 +125,7 @@ impl AlbumRepository {
                         ":sort_artist": album.sort_artist,
                         ":title": album.title,
                         ":total_discs": album.total_discs,
+                        ":score": album.score,
                     },
                 )
             })
@@ -150,7 +153,8 @@ impl AlbumRepository {
                 script,
                 sort_artist,
                 title,
-                total_discs
+                total_discs,
+                score
             FROM albums
             WHERE id = :id
         "#;
@@ -186,7 +190,8 @@ impl AlbumRepository {
                 script,
                 sort_artist,
                 title,
-                total_discs
+                total_discs,
+                score
             FROM albums
         "#;
         Database::instance()
@@ -219,7 +224,8 @@ impl AlbumRepository {
                 script = :script,
                 sort_artist = :sort_artist,
                 title = :title,
-                total_discs = :total_discs
+                total_discs = :total_discs,
+                score = :score
             WHERE id = :id
         "#;
         Database::instance()
@@ -244,6 +250,7 @@ impl AlbumRepository {
                         ":sort_artist": album.sort_artist,
                         ":title": album.title,
                         ":total_discs": album.total_discs,
+                        ":score": album.score,
                         ":id": album.id.value,
                     },
                 )
@@ -290,6 +297,7 @@ impl<'a> TryFrom<&Row<'a>> for Album {
             sort_artist: row.get_unwrap(14),
             title: row.get_unwrap(15),
             total_discs: row.get_unwrap(16),
+            score: row.get_unwrap(17),
         })
     }
 }
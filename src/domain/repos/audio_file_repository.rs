@@ -1,12 +1,15 @@
 use crate::domain::events::DomainEvent;
-use crate::domain::models::{AlbumId, AudioFile, AudioFileId, Track, TrackId};
+use crate::domain::models::{
+    AlbumId, AudioFile, AudioFileId, ClusterId, ScanStatus, Track, TrackId,
+};
 use crate::domain::services::MediatorService;
 use crate::infrastructure::{acoustid::Fingerprint, database::Database, EntityId};
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
-use rusqlite::{named_params, Error, Row};
+use rusqlite::{named_params, types::Type, Error, Row};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::Duration;
 
 /// Represents a service for working with audio files.
 /// An audio file is a file that contains audio data that sisko recognizes.
@@ -38,6 +41,35 @@ impl AudioFileRepository {
         Self::select_all().await
     }
 
+    /// Returns the audio files in the given cluster.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster.
+    pub async fn get_by_cluster(&self, cluster_id: &ClusterId) -> Result<Vec<AudioFile>> {
+        Self::select_by_cluster(cluster_id).await
+    }
+
+    /// Returns the groups of audio files that have the same audio payload.
+    pub async fn get_duplicates(&self) -> Result<Vec<Vec<AudioFile>>> {
+        let audio_files = Self::select_duplicates().await?;
+        Ok(audio_files
+            .into_iter()
+            .chunk_by(|audio_file| audio_file.audio_checksum.clone())
+            .into_iter()
+            .map(|(_, group)| group.collect())
+            .collect())
+    }
+
+    /// Returns the audio files with the given audio payload checksum.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_checksum` - The checksum of the audio payload.
+    pub async fn get_by_audio_checksum(&self, audio_checksum: &str) -> Result<Vec<AudioFile>> {
+        Self::select_by_audio_checksum(audio_checksum).await
+    }
+
     pub async fn get_matched(&self, track: &Track) -> Result<Vec<AudioFile>> {
         Self::select_matched(track).await
     }
@@ -73,8 +105,48 @@ impl AudioFileRepository {
 
     async fn insert(audio_file: AudioFile) -> Result<()> {
         const COMMAND: &str = r#"
-            INSERT INTO audio_files (id, acoust_id, duration, fingerprint, recording_id, album_id, track_id)
-            VALUES (:id, :acoust_id, :duration, :fingerprint, :recording_id, :album_id, :track_id)
+            INSERT INTO audio_files (
+                id,
+                acoust_id,
+                audio_checksum,
+                bitrate,
+                duration,
+                fingerprint,
+                stream_duration,
+                recording_id,
+                album_id,
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
+            )
+            VALUES (
+                :id,
+                :acoust_id,
+                :audio_checksum,
+                :bitrate,
+                :duration,
+                :fingerprint,
+                :stream_duration,
+                :recording_id,
+                :album_id,
+                :track_id,
+                :scan_status,
+                :cluster_id,
+                :similarity,
+                :sample_rate,
+                :channel_mode,
+                :encoder,
+                :encoder_delay,
+                :encoder_padding,
+                :fingerprint_recording_id
+            )
         "#;
         Database::instance()
             .connection
@@ -90,11 +162,25 @@ impl AudioFileRepository {
                     named_params! {
                         ":id": audio_file.id.to_string(),
                         ":acoust_id": audio_file.acoust_id,
+                        ":audio_checksum": audio_file.audio_checksum,
+                        ":bitrate": audio_file.bitrate,
                         ":duration": duration,
                         ":fingerprint": fingerprint,
+                        ":stream_duration": audio_file
+                            .duration
+                            .map(|duration| duration.as_secs_f64()),
                         ":recording_id": audio_file.recording_id,
                         ":album_id": audio_file.track_id.clone().map(|id| id.album_id.value),
                         ":track_id": audio_file.track_id.map(|id| id.track_id),
+                        ":scan_status": audio_file.scan_status.as_str(),
+                        ":cluster_id": audio_file.cluster_id.map(|id| id.value),
+                        ":similarity": audio_file.similarity,
+                        ":sample_rate": audio_file.sample_rate,
+                        ":channel_mode": audio_file.channel_mode,
+                        ":encoder": audio_file.encoder,
+                        ":encoder_delay": audio_file.encoder_delay,
+                        ":encoder_padding": audio_file.encoder_padding,
+                        ":fingerprint_recording_id": audio_file.fingerprint_recording_id,
                     },
                 )
             })
@@ -105,7 +191,26 @@ impl AudioFileRepository {
     async fn select(path: &Path) -> Result<AudioFile> {
         let path = path.to_path_buf();
         const COMMAND: &str = r#"
-            SELECT id, acoust_id, duration, fingerprint, recording_id, album_id, track_id
+            SELECT
+                id,
+                acoust_id,
+                audio_checksum,
+                bitrate,
+                duration,
+                fingerprint,
+                stream_duration,
+                recording_id,
+                album_id,
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
             FROM audio_files
             WHERE id = :id
         "#;
@@ -134,11 +239,23 @@ impl AudioFileRepository {
             SELECT
                 id,
                 acoust_id,
+                audio_checksum,
+                bitrate,
                 duration,
                 fingerprint,
+                stream_duration,
                 recording_id,
                 album_id,
-                track_id
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
             FROM audio_files
         "#;
         Database::instance()
@@ -153,6 +270,131 @@ impl AudioFileRepository {
             .await
     }
 
+    async fn select_by_audio_checksum(audio_checksum: &str) -> Result<Vec<AudioFile>> {
+        let audio_checksum = audio_checksum.to_string();
+        const COMMAND: &str = r#"
+            SELECT
+                id,
+                acoust_id,
+                audio_checksum,
+                bitrate,
+                duration,
+                fingerprint,
+                stream_duration,
+                recording_id,
+                album_id,
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
+            FROM audio_files
+            WHERE audio_checksum = :audio_checksum
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<Vec<AudioFile>> {
+                let mut statement = connection.prepare(COMMAND)?;
+                let audio_files: Vec<AudioFile> = statement
+                    .query_map(named_params! {":audio_checksum": audio_checksum}, |row| {
+                        AudioFile::try_from(row)
+                    })?
+                    .try_collect()?;
+                Ok(audio_files)
+            })
+            .await
+    }
+
+    async fn select_by_cluster(cluster_id: &ClusterId) -> Result<Vec<AudioFile>> {
+        let cluster_id = cluster_id.value.clone();
+        const COMMAND: &str = r#"
+            SELECT
+                id,
+                acoust_id,
+                audio_checksum,
+                bitrate,
+                duration,
+                fingerprint,
+                stream_duration,
+                recording_id,
+                album_id,
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
+            FROM audio_files
+            WHERE cluster_id = :cluster_id
+            ORDER BY id
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<Vec<AudioFile>> {
+                let mut statement = connection.prepare(COMMAND)?;
+                let audio_files: Vec<AudioFile> = statement
+                    .query_map(named_params! {":cluster_id": cluster_id}, |row| {
+                        AudioFile::try_from(row)
+                    })?
+                    .try_collect()?;
+                Ok(audio_files)
+            })
+            .await
+    }
+
+    async fn select_duplicates() -> Result<Vec<AudioFile>> {
+        const COMMAND: &str = r#"
+            SELECT
+                id,
+                acoust_id,
+                audio_checksum,
+                bitrate,
+                duration,
+                fingerprint,
+                stream_duration,
+                recording_id,
+                album_id,
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
+            FROM audio_files
+            WHERE audio_checksum IN (
+                SELECT audio_checksum
+                FROM audio_files
+                WHERE audio_checksum IS NOT NULL
+                GROUP BY audio_checksum
+                HAVING COUNT(*) > 1
+            )
+            ORDER BY audio_checksum, id
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<Vec<AudioFile>> {
+                let mut statement = connection.prepare(COMMAND)?;
+                let audio_files: Vec<AudioFile> = statement
+                    .query_map((), |row| AudioFile::try_from(row))?
+                    .try_collect()?;
+                Ok(audio_files)
+            })
+            .await
+    }
+
     async fn select_matched(track: &Track) -> Result<Vec<AudioFile>> {
         let album_id = track.id.album_id.value.clone();
         let track_id = track.id.track_id.clone();
@@ -160,11 +402,23 @@ impl AudioFileRepository {
             SELECT
                 id,
                 acoust_id,
+                audio_checksum,
+                bitrate,
                 duration,
                 fingerprint,
+                stream_duration,
                 recording_id,
                 album_id,
-                track_id
+                track_id,
+                scan_status,
+                cluster_id,
+                similarity,
+                sample_rate,
+                channel_mode,
+                encoder,
+                encoder_delay,
+                encoder_padding,
+                fingerprint_recording_id
             FROM audio_files
             WHERE
                 album_id = :album_id
@@ -189,11 +443,23 @@ impl AudioFileRepository {
         const COMMAND: &str = r#"
             UPDATE audio_files
             SET acoust_id = :acoust_id,
+                audio_checksum = :audio_checksum,
+                bitrate = :bitrate,
                 duration = :duration,
                 fingerprint = :fingerprint,
+                stream_duration = :stream_duration,
                 recording_id = :recording_id,
                 album_id = :album_id,
-                track_id = :track_id
+                track_id = :track_id,
+                scan_status = :scan_status,
+                cluster_id = :cluster_id,
+                similarity = :similarity,
+                sample_rate = :sample_rate,
+                channel_mode = :channel_mode,
+                encoder = :encoder,
+                encoder_delay = :encoder_delay,
+                encoder_padding = :encoder_padding,
+                fingerprint_recording_id = :fingerprint_recording_id
             WHERE id = :id
         "#;
         Database::instance()
@@ -209,11 +475,25 @@ impl AudioFileRepository {
                     COMMAND,
                     named_params! {
                         ":acoust_id": audio_file.acoust_id,
+                        ":audio_checksum": audio_file.audio_checksum,
+                        ":bitrate": audio_file.bitrate,
                         ":duration": duration,
                         ":fingerprint": fingerprint,
+                        ":stream_duration": audio_file
+                            .duration
+                            .map(|duration| duration.as_secs_f64()),
                         ":recording_id": audio_file.recording_id,
                         ":album_id": audio_file.track_id.clone().map(|id| id.album_id.value),
                         ":track_id": audio_file.track_id.map(|id| id.track_id),
+                        ":scan_status": audio_file.scan_status.as_str(),
+                        ":cluster_id": audio_file.cluster_id.map(|id| id.value),
+                        ":similarity": audio_file.similarity,
+                        ":sample_rate": audio_file.sample_rate,
+                        ":channel_mode": audio_file.channel_mode,
+                        ":encoder": audio_file.encoder,
+                        ":encoder_delay": audio_file.encoder_delay,
+                        ":encoder_padding": audio_file.encoder_padding,
+                        ":fingerprint_recording_id": audio_file.fingerprint_recording_id,
                         ":id": audio_file.id.to_string(),
                     },
                 )
@@ -243,8 +523,8 @@ impl<'a> TryFrom<&Row<'a>> for AudioFile {
     fn try_from(row: &Row<'a>) -> std::prelude::v1::Result<Self, Self::Error> {
         let id: String = row.get_unwrap(0);
         let id = AudioFileId::new(PathBuf::from(id));
-        let duration: Option<String> = row.get_unwrap(2);
-        let fingerprint: Option<String> = row.get_unwrap(3);
+        let duration: Option<String> = row.get_unwrap(4);
+        let fingerprint: Option<String> = row.get_unwrap(5);
         let fingerprint = match (duration, fingerprint) {
             (Some(duration), Some(fingerprint)) => Some(Fingerprint {
                 duration,
@@ -252,21 +532,108 @@ impl<'a> TryFrom<&Row<'a>> for AudioFile {
             }),
             _ => None,
         };
-        let album_id: Option<String> = row.get_unwrap(5);
-        let track_id: Option<String> = row.get_unwrap(6);
+        let stream_duration: Option<f64> = row.get_unwrap(6);
+        let album_id: Option<String> = row.get_unwrap(8);
+        let track_id: Option<String> = row.get_unwrap(9);
         let track_id = match (album_id, track_id) {
             (Some(album_id), Some(track_id)) => {
                 Some(TrackId::new(AlbumId::new(album_id), track_id))
             }
             _ => None,
         };
+        let scan_status: String = row.get_unwrap(10);
+        let scan_status = ScanStatus::try_from(scan_status.as_str())
+            .map_err(|e| Error::FromSqlConversionFailure(10, Type::Text, e.into()))?;
+        let cluster_id: Option<String> = row.get_unwrap(11);
         Ok(AudioFile {
             acoust_id: row.get_unwrap(1),
+            audio_checksum: row.get_unwrap(2),
+            bitrate: row.get_unwrap(3),
+            channel_mode: row.get_unwrap(14),
+            cluster_id: cluster_id.map(ClusterId::new),
+            duration: stream_duration.map(Duration::from_secs_f64),
+            encoder: row.get_unwrap(15),
+            encoder_delay: row.get_unwrap(16),
+            encoder_padding: row.get_unwrap(17),
             events: vec![],
             fingerprint,
+            fingerprint_recording_id: row.get_unwrap(18),
             id,
-            recording_id: row.get_unwrap(4),
+            recording_id: row.get_unwrap(7),
+            sample_rate: row.get_unwrap(13),
+            scan_status,
+            similarity: row.get_unwrap(12),
             track_id,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn update_saves_every_column() {
+        let repository = AudioFileRepository::instance();
+        let id = AudioFileId::new(PathBuf::from(format!(
+            "/sisko-test/{}/update.mp3",
+            std::process::id()
+        )));
+        let mut audio_file = AudioFile {
+            acoust_id: None,
+            audio_checksum: Some(String::from("checksum")),
+            bitrate: Some(128_000),
+            channel_mode: None,
+            cluster_id: None,
+            duration: Some(Duration::from_secs(200)),
+            encoder: None,
+            encoder_delay: None,
+            encoder_padding: None,
+            events: vec![],
+            fingerprint: None,
+            fingerprint_recording_id: None,
+            id: id.clone(),
+            recording_id: None,
+            sample_rate: Some(44_100),
+            scan_status: ScanStatus::Unscanned,
+            similarity: None,
+            track_id: None,
+        };
+        repository.add(audio_file.clone()).await.unwrap();
+
+        audio_file.acoust_id = Some(String::from("acoust-id"));
+        audio_file.channel_mode = Some(String::from("Joint Stereo"));
+        audio_file.duration = Some(Duration::from_secs(201));
+        audio_file.encoder = Some(String::from("LAME3.100"));
+        audio_file.encoder_delay = Some(576);
+        audio_file.encoder_padding = Some(1152);
+        audio_file.fingerprint = Some(Fingerprint {
+            duration: String::from("201"),
+            fingerprint: String::from("AQAA"),
+        });
+        audio_file.fingerprint_recording_id = Some(String::from("recording"));
+        audio_file.recording_id = Some(String::from("recording"));
+        audio_file.scan_status = ScanStatus::NoMatch;
+        audio_file.similarity = Some(0.5);
+        repository.save(audio_file).await.unwrap();
+
+        let saved = repository.get(&id).await.unwrap();
+        repository.remove(saved.clone()).await.unwrap();
+        assert_eq!(saved.acoust_id.as_deref(), Some("acoust-id"));
+        assert_eq!(saved.audio_checksum.as_deref(), Some("checksum"));
+        assert_eq!(saved.channel_mode.as_deref(), Some("Joint Stereo"));
+        assert_eq!(saved.duration, Some(Duration::from_secs(201)));
+        assert_eq!(saved.encoder.as_deref(), Some("LAME3.100"));
+        assert_eq!(saved.encoder_delay, Some(576));
+        assert_eq!(saved.encoder_padding, Some(1152));
+        assert_eq!(
+            saved.fingerprint.map(|f| f.duration).as_deref(),
+            Some("201")
+        );
+        assert_eq!(saved.fingerprint_recording_id.as_deref(), Some("recording"));
+        assert_eq!(saved.recording_id.as_deref(), Some("recording"));
+        assert_eq!(saved.sample_rate, Some(44_100));
+        assert_eq!(saved.scan_status, ScanStatus::NoMatch);
+        assert_eq!(saved.similarity, Some(0.5));
+    }
+}
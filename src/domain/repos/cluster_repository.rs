@@ -0,0 +1,162 @@
+use crate::domain::events::DomainEvent;
+use crate::domain::models::{Cluster, ClusterId};
+use crate::domain::services::MediatorService;
+use crate::infrastructure::database::Database;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use rusqlite::{named_params, Error, Row};
+use std::sync::OnceLock;
+
+/// Represents a service for working with clusters of unmatched audio files.
+pub struct ClusterRepository {}
+
+impl ClusterRepository {
+    pub fn instance() -> &'static Self {
+        static INSTANCE: OnceLock<ClusterRepository> = OnceLock::new();
+        INSTANCE.get_or_init(Self::new)
+    }
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub async fn add(&self, cluster: Cluster) -> Result<()> {
+        let events = cluster.events.clone();
+        Self::insert(cluster).await?;
+        Self::publish_events(events)?;
+        Ok(())
+    }
+
+    pub async fn get(&self, id: &ClusterId) -> Result<Cluster> {
+        Self::select(&id.value).await
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Cluster>> {
+        Self::select_all().await
+    }
+
+    pub async fn remove(&self, cluster: Cluster) -> Result<()> {
+        let events = cluster.events.clone();
+        Self::delete(cluster).await?;
+        Self::publish_events(events)?;
+        Ok(())
+    }
+
+    async fn delete(cluster: Cluster) -> Result<()> {
+        let id = cluster.id.value;
+        const UNCLUSTER_COMMAND: &str = r#"
+            UPDATE audio_files
+            SET cluster_id = NULL
+            WHERE cluster_id = :id
+        "#;
+        const COMMAND: &str = r#"
+            DELETE FROM clusters
+            WHERE id = :id
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<()> {
+                connection.execute(UNCLUSTER_COMMAND, named_params! {":id": id})?;
+                connection.execute(COMMAND, named_params! {":id": id})?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn insert(cluster: Cluster) -> Result<()> {
+        const COMMAND: &str = r#"
+            INSERT INTO clusters (
+                id,
+                album,
+                artist)
+            VALUES (
+                :id,
+                :album,
+                :artist)
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| {
+                connection.execute(
+                    COMMAND,
+                    named_params! {
+                        ":id": cluster.id.value,
+                        ":album": cluster.album,
+                        ":artist": cluster.artist,
+                    },
+                )
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn select(id: &str) -> Result<Cluster> {
+        let id = id.to_owned();
+        const COMMAND: &str = r#"
+            SELECT
+                id,
+                album,
+                artist
+            FROM clusters
+            WHERE id = :id
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<Cluster> {
+                let mut statement = connection.prepare(COMMAND)?;
+                let cluster = statement
+                    .query_map(named_params! {":id": id}, |row| Cluster::try_from(row))?
+                    .next()
+                    .ok_or_else(|| anyhow!("Failed to find cluster with id {}!", id))??;
+                Ok(cluster)
+            })
+            .await
+    }
+
+    async fn select_all() -> Result<Vec<Cluster>> {
+        const COMMAND: &str = r#"
+            SELECT
+                id,
+                album,
+                artist
+            FROM clusters
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<Vec<Cluster>> {
+                let mut statement = connection.prepare(COMMAND)?;
+                let clusters: Vec<Cluster> = statement
+                    .query_map((), |row| Cluster::try_from(row))?
+                    .try_collect()?;
+                Ok(clusters)
+            })
+            .await
+    }
+
+    fn publish_events(events: Vec<DomainEvent>) -> Result<()> {
+        let mediator_service = MediatorService::instance();
+        for event in events {
+            mediator_service.publish(&event)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ClusterRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Cluster {
+    type Error = Error;
+
+    fn try_from(row: &Row<'a>) -> std::prelude::v1::Result<Self, Self::Error> {
+        Ok(Cluster {
+            album: row.get_unwrap(1),
+            artist: row.get_unwrap(2),
+            events: vec![],
+            id: ClusterId::new(row.get_unwrap(0)),
+        })
+    }
+}
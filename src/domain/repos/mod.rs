@@ -1,9 +1,11 @@
 mod album_repository;
 mod audio_file_repository;
+mod cluster_repository;
 mod tag_repository;
 mod track_repository;
 
 pub use album_repository::*;
 pub use audio_file_repository::*;
+pub use cluster_repository::*;
 pub use tag_repository::*;
 pub use track_repository::*;
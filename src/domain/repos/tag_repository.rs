@@ -59,7 +59,7 @@ impl TagRepository {
     async fn delete(metadata: Metadata) -> Result<()> {
         let audio_file_id = metadata.audio_file_id.path.clone();
         const COMMAND: &str = r#"
-            DELETE FROM metadat_fields
+            DELETE FROM metadata_fields
             WHERE audio_file_id = :audio_file_id
         "#;
         Database::instance()
@@ -74,6 +74,50 @@ impl TagRepository {
         Ok(())
     }
 
+    /// Deletes the stored fields of the file that the given tags no longer have.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The tags of the file.
+    async fn delete_removed_fields(metadata: &Metadata) -> Result<()> {
+        let audio_file_id = metadata.audio_file_id.path.to_string_lossy().to_string();
+        let field_types = metadata
+            .iter()
+            .map(|field| field.field_type.display_name())
+            .collect_vec();
+        const SELECT_COMMAND: &str = r#"
+            SELECT field_type
+            FROM metadata_fields
+            WHERE audio_file_id = :audio_file_id
+        "#;
+        const DELETE_COMMAND: &str = r#"
+            DELETE FROM metadata_fields
+            WHERE audio_file_id = :audio_file_id
+            AND field_type = :field_type
+        "#;
+        Database::instance()
+            .connection
+            .call_unwrap(move |connection| -> Result<()> {
+                let mut statement = connection.prepare(SELECT_COMMAND)?;
+                let stored: Vec<String> = statement
+                    .query_map(named_params! {":audio_file_id": audio_file_id}, |row| {
+                        row.get(0)
+                    })?
+                    .try_collect()?;
+                for field_type in stored.iter().filter(|f| !field_types.contains(f)) {
+                    connection.execute(
+                        DELETE_COMMAND,
+                        named_params! {
+                            ":audio_file_id": audio_file_id,
+                            ":field_type": field_type,
+                        },
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
     async fn insert(metadata: Metadata) -> Result<()> {
         let fields = metadata
             .iter()
@@ -104,7 +148,7 @@ impl TagRepository {
                 field_type,
                 value_discriminator,
                 value,
-                new_value_discriminator
+                new_value_discriminator,
                 new_value)
             VALUES (
                 :audio_file_id,
@@ -166,6 +210,7 @@ impl TagRepository {
     }
 
     async fn update(metadata: Metadata) -> Result<()> {
+        Self::delete_removed_fields(&metadata).await?;
         let fields = metadata
             .iter()
             .map(|field| {
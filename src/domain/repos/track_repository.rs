@@ -88,6 +88,7 @@ impl TrackRepository {
                 id,
                 artist,
                 artist_id,
+                artists,
                 disc_number,
                 disc_subtitle,
                 isrc,
@@ -105,6 +106,7 @@ impl TrackRepository {
                 :id,
                 :artist,
                 :artist_id,
+                :artists,
                 :disc_number,
                 :disc_subtitle,
                 :isrc,
@@ -128,6 +130,7 @@ impl TrackRepository {
                         ":id": track.id.track_id,
                         ":artist": track.artist,
                         ":artist_id": track.artist_id,
+                        ":artists": track.artists,
                         ":disc_number": track.disc_number,
                         ":disc_subtitle": track.disc_subtitle,
                         ":isrc": track.isrc,
@@ -151,11 +154,12 @@ impl TrackRepository {
         let values = tracks
             .iter()
             .map(|track| {
-                format!("('{}', '{}', '{}', '{}', '{}', '{}', {}, '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
+                format!("('{}', '{}', '{}', '{}', '{}', '{}', '{}', {}, '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}')",
                     track.id.album_id.value,
                     track.id.track_id,
                     track.artist,
                     track.artist_id,
+                    track.artists,
                     track.disc_number,
                     track.disc_subtitle,
                     track.isrc.as_ref().map(|s| format!("'{}'", s)).unwrap_or(String::from("NULL")),
@@ -178,6 +182,7 @@ impl TrackRepository {
                 id,
                 artist,
                 artist_id,
+                artists,
                 disc_number,
                 disc_subtitle,
                 isrc,
@@ -210,6 +215,7 @@ impl TrackRepository {
                 id,
                 artist,
                 artist_id,
+                artists,
                 disc_number,
                 disc_subtitle,
                 isrc,
@@ -257,6 +263,7 @@ impl TrackRepository {
                 id,
                 artist,
                 artist_id,
+                artists,
                 disc_number,
                 disc_subtitle,
                 isrc,
@@ -291,6 +298,7 @@ impl TrackRepository {
             UPDATE tracks
             SET artist = :artist,
                 artist_id = :artist_id,
+                artists = :artists,
                 disc_number = :disc_number,
                 disc_subtitle = :disc_subtitle,
                 isrc = :isrc,
@@ -315,6 +323,7 @@ impl TrackRepository {
                     named_params! {
                         ":artist": track.artist,
                         ":artist_id": track.artist_id,
+                        ":artists": track.artists,
                         ":disc_number": track.disc_number,
                         ":disc_subtitle": track.disc_subtitle,
                         ":isrc": track.isrc,
@@ -358,20 +367,21 @@ impl<'a> TryFrom<&Row<'a>> for Track {
         Ok(Track {
             artist: row.get_unwrap(2),
             artist_id: row.get_unwrap(3),
-            disc_number: row.get_unwrap(4),
-            disc_subtitle: row.get_unwrap(5),
+            artists: row.get_unwrap(4),
+            disc_number: row.get_unwrap(5),
+            disc_subtitle: row.get_unwrap(6),
             events: vec![],
             id: TrackId::new(AlbumId::new(row.get_unwrap(0)), row.get_unwrap(1)),
-            isrc: row.get_unwrap(6),
-            length: Duration::from_secs_f64(row.get_unwrap(7)),
-            media: row.get_unwrap(8),
-            number: row.get_unwrap(9),
-            original_release_date: row.get_unwrap(10),
-            original_year: row.get_unwrap(11),
-            recording_id: row.get_unwrap(12),
-            sort_artist: row.get_unwrap(13),
-            title: row.get_unwrap(14),
-            total_tracks: row.get_unwrap(15),
+            isrc: row.get_unwrap(7),
+            length: Duration::from_secs_f64(row.get_unwrap(8)),
+            media: row.get_unwrap(9),
+            number: row.get_unwrap(10),
+            original_release_date: row.get_unwrap(11),
+            original_year: row.get_unwrap(12),
+            recording_id: row.get_unwrap(13),
+            sort_artist: row.get_unwrap(14),
+            title: row.get_unwrap(15),
+            total_tracks: row.get_unwrap(16),
         })
     }
 }
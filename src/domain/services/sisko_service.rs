@@ -1,20 +1,49 @@
 use crate::domain::events::DomainEvent;
-use crate::domain::models::{Album, AudioFile, AudioFileId, Metadata, Track};
-use crate::domain::repos::{AlbumRepository, AudioFileRepository, TagRepository, TrackRepository};
+use crate::domain::models::{
+    Album, AlbumId, AudioFile, AudioFileId, Cluster, ClusterId, LookupResult, Metadata,
+    RecordingCandidate, ReleaseVersion, ScanStatus, TagFieldType, Track, TrackId,
+};
+use crate::domain::repos::{
+    AlbumRepository, AudioFileRepository, ClusterRepository, TagRepository, TrackRepository,
+};
 use crate::domain::services::LogHistory;
-use crate::infrastructure::acoustid::AcoustIdService;
-use crate::infrastructure::file::{File, FileService};
-use crate::infrastructure::musicbrainz::{MusicBrainzService, Release, ReleaseLookup};
-use crate::infrastructure::spawn;
+use crate::infrastructure::acoustid::{AcoustIdService, AcoustIdSubmission};
+use crate::infrastructure::config::{ConfigService, ReleasePreferences, ReleaseWeights};
+use crate::infrastructure::file::{File, FileService, FileType};
+use crate::infrastructure::musicbrainz::{
+    parse_release_id, MusicBrainzService, Release, ReleaseLookup,
+};
+use crate::infrastructure::{linear_combination_of_weights, spawn, EntityId};
 use crate::ui::models::MatchState;
 use crate::ui::services::Ui;
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
+use log::{info, warn};
+use sisko_lib::asf_tag::AsfTag;
+use sisko_lib::audio_checksum::AudioChecksum;
+use sisko_lib::disc_toc::{decode_rip_text, DiscToc, SECTORS_PER_SECOND};
 use sisko_lib::id3v2_tag::ID3v2Tag;
+use sisko_lib::iff_file::IffFile;
+use sisko_lib::iff_format::IffFormat;
+use sisko_lib::matroska_tags::MatroskaTags;
+use sisko_lib::mp4_tag::Mp4Tag;
+use sisko_lib::mpeg_audio_info::MpegAudioInfo;
+use sisko_lib::ogg_comment_header::OggCommentHeader;
 use std::fs::File as FsFile;
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// The most results asked for when searching MusicBrainz.
+const SEARCH_LIMIT: usize = 5;
+
+/// How well a release found by a similar table of contents, rather than the disc ID, matches.
+const SIMILAR_DISC_SCORE: f64 = 0.5;
 
 /// Represents a service for application actions.
 pub struct SiskoService {}
@@ -32,6 +61,22 @@ impl SiskoService {
 
     pub async fn add_file(&self, file: Arc<File>) -> Result<()> {
         let mut audio_file = AudioFile::from(file.as_ref());
+        self.load_audio_properties(&mut audio_file, file.as_ref())
+            .await;
+        if let Some(audio_checksum) = &audio_file.audio_checksum {
+            for duplicate in AudioFileRepository::instance()
+                .get_by_audio_checksum(audio_checksum)
+                .await?
+                .iter()
+                .filter(|duplicate| duplicate.id != audio_file.id)
+            {
+                warn!(
+                    "{} has the same audio as {}",
+                    audio_file.id.to_string(),
+                    duplicate.id.to_string()
+                );
+            }
+        }
         AudioFileRepository::instance()
             .add(audio_file.clone())
             .await?;
@@ -52,7 +97,13 @@ impl SiskoService {
 
     pub async fn calculate_fingerprint(&self, audio_file: &AudioFile) -> Result<()> {
         let path = audio_file.id.path.clone();
-        let fingerprint = AcoustIdService::instance().get_fingerprint(&path).ok();
+        let fingerprint = match AcoustIdService::instance().get_fingerprint(&path).await {
+            Ok(fingerprint) => Some(fingerprint),
+            Err(e) => {
+                warn!("Failed to fingerprint {}: {}", audio_file.id.to_string(), e);
+                None
+            }
+        };
         let mut audio_file = audio_file.clone();
         audio_file.fingerprint = fingerprint.or(audio_file.fingerprint.clone());
         audio_file
@@ -61,23 +112,135 @@ impl SiskoService {
         AudioFileRepository::instance().save(audio_file).await
     }
 
+    /// Groups the unmatched files into clusters by their tags and directories, replacing any
+    /// previous clusters.
+    pub async fn cluster_files(&self) -> Result<()> {
+        for cluster in ClusterRepository::instance().get_all().await? {
+            ClusterRepository::instance().remove(cluster).await?;
+        }
+        let mut files = vec![];
+        for audio_file in AudioFileRepository::instance().get_all().await? {
+            if audio_file.track_id.is_none() {
+                let metadata = TagRepository::instance().get(&audio_file).await?;
+                files.push((audio_file, metadata));
+            }
+        }
+
+        let clusters = Cluster::group(files);
+        info!(
+            "Grouped the unmatched files into {} clusters",
+            clusters.len()
+        );
+        for (cluster, audio_files) in clusters {
+            ClusterRepository::instance().add(cluster).await?;
+            for mut audio_file in audio_files {
+                audio_file
+                    .events
+                    .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
+                AudioFileRepository::instance().save(audio_file).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the groups of loaded files that have the same audio payload.
+    pub async fn find_duplicates(&self) -> Result<()> {
+        let groups = AudioFileRepository::instance().get_duplicates().await?;
+        let duplicates = match groups.is_empty() {
+            true => String::from("No loaded files have the same audio."),
+            false => groups
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|audio_file| audio_file.id.to_string())
+                        .join("\n")
+                })
+                .join("\n\n"),
+        };
+        Ui::instance().menu.open_duplicates(&duplicates)
+    }
+
+    /// Submits the fingerprints of the matched files to AcoustID, waits for them to be imported
+    /// and stores the AcoustIDs they were imported as.
+    pub async fn submit_acoustids(&self) -> Result<()> {
+        let audio_files: Vec<AudioFile> = AudioFileRepository::instance()
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|audio_file| audio_file.track_id.is_some())
+            .collect();
+        let mut submitted = vec![];
+        let mut submissions = vec![];
+        for audio_file in audio_files {
+            if let (Some(fingerprint), Some(recording_id)) =
+                (&audio_file.fingerprint, &audio_file.recording_id)
+            {
+                submissions.push(AcoustIdSubmission {
+                    bitrate: audio_file.bitrate,
+                    duration: fingerprint.duration.clone(),
+                    file_format: audio_file
+                        .id
+                        .path
+                        .extension()
+                        .map(|extension| extension.to_string_lossy().to_uppercase()),
+                    fingerprint: fingerprint.fingerprint.clone(),
+                    recording_id: recording_id.clone(),
+                });
+                submitted.push(audio_file);
+            }
+        }
+        if submissions.is_empty() {
+            warn!("No matched files with fingerprints to submit to AcoustID");
+            return Ok(());
+        }
+        let acoustid_service = AcoustIdService::instance();
+        let statuses = acoustid_service.submit(&submissions).await?;
+        info!("Submitted {} fingerprints to AcoustID", statuses.len());
+        let statuses = acoustid_service.poll_submissions(statuses).await?;
+        for status in statuses {
+            let mut audio_file = match status.index.and_then(|index| submitted.get(index)) {
+                Some(audio_file) => audio_file.clone(),
+                None => {
+                    warn!(
+                        "AcoustID submission {} isn't of a submitted file",
+                        status.id
+                    );
+                    continue;
+                }
+            };
+            match status.result {
+                Some(result) => {
+                    audio_file.acoust_id = Some(result.id);
+                    audio_file
+                        .events
+                        .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
+                    AudioFileRepository::instance().save(audio_file).await?;
+                }
+                None => warn!(
+                    "AcoustID submission {} for {} is still {}",
+                    status.id,
+                    audio_file.id.to_string(),
+                    status.status
+                ),
+            }
+        }
+        Ok(())
+    }
+
     pub fn handle_audio_file_added(&self, audio_file: &AudioFile) {
         let copy = audio_file.clone();
         spawn(async move { SiskoService::instance().calculate_fingerprint(&copy).await });
         let copy = audio_file.clone();
-        spawn(async move {
-            let tag = TagRepository::instance().get(&copy).await?;
-            Ui::instance().cluster_table.add_cluster_file(copy, &tag)
-        });
+        spawn(async move { SiskoService::instance().update_cluster_table(copy).await });
     }
 
     pub fn handle_audio_file_updated(&self, audio_file: &AudioFile) {
         let audio_file = audio_file.clone();
         spawn(async move {
-            let tag = TagRepository::instance().get(&audio_file).await?;
-            Ui::instance()
-                .cluster_table
-                .add_cluster_file(audio_file, &tag)
+            SiskoService::instance()
+                .update_cluster_table(audio_file)
+                .await
         });
     }
 
@@ -90,25 +253,521 @@ impl SiskoService {
                 .await?;
             let metadata = TagRepository::instance().get(&audio_file).await?;
             // TODO: this doesn't seem right
-            ui.cluster_table
-                .add_cluster_file(audio_file.clone(), &metadata)?;
+            SiskoService::instance()
+                .update_cluster_table(audio_file.clone())
+                .await?;
             ui.album_table.update_audio_file(&audio_file, &metadata)?;
             ui.metadata_table.update_metadata_table(&metadata)
         });
     }
 
+    /// Analyses the audio stream of the file: checksums its audio payload and, for MPEG audio,
+    /// finds its duration and bitrate so that its length is known before fingerprinting.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to set the properties of.
+    /// * `file` - The file that contains the audio data.
+    pub async fn load_audio_properties(&self, audio_file: &mut AudioFile, file: &File) {
+        let mut loaded = audio_file.clone();
+        let file = file.clone();
+        // NOTE: the whole file is read and hashed, which mustn't block the async runtime's worker
+        match tokio::task::spawn_blocking(move || {
+            Self::read_audio_properties(&mut loaded, &file);
+            loaded
+        })
+        .await
+        {
+            Ok(loaded) => *audio_file = loaded,
+            Err(e) => warn!(
+                "Failed to analyse the audio of {}: {e}",
+                audio_file.id.to_string()
+            ),
+        }
+    }
+
+    fn read_audio_properties(audio_file: &mut AudioFile, file: &File) {
+        let path = file.absolute_path.to_string_lossy();
+        let bytes = match std::fs::read(&file.absolute_path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to read the audio of {path}: {e}");
+                return;
+            }
+        };
+        match AudioChecksum::compute(&bytes) {
+            Ok(audio_checksum) => audio_file.audio_checksum = Some(audio_checksum.to_string()),
+            Err(e) => warn!("Failed to checksum the audio of {path}: {e}"),
+        }
+        if file.file_type == Some(FileType::Mp3File) {
+            match MpegAudioInfo::parse(&bytes) {
+                Ok(info) => {
+                    audio_file.bitrate = Some(info.bitrate());
+                    audio_file.channel_mode = Some(info.header.channel_mode.to_string());
+                    audio_file.duration = Some(info.duration());
+                    audio_file.encoder = info.encoder().map(String::from);
+                    audio_file.encoder_delay = info.lame_header().map(|lame| lame.encoder_delay);
+                    audio_file.encoder_padding =
+                        info.lame_header().map(|lame| lame.encoder_padding);
+                    audio_file.sample_rate = Some(info.header.sample_rate);
+                }
+                Err(e) => warn!("Failed to analyse the audio of {path}: {e}"),
+            }
+        }
+    }
+
     pub async fn load_tags(&self, file: &File) -> Result<()> {
-        // TODO: check if has tag before reading
-        if let Ok(id3v2) = ID3v2Tag::read_from_path(&file.absolute_path) {
-            let metadata =
-                Metadata::from_id3v2(AudioFileId::new(file.absolute_path.clone()), &id3v2.frames);
-            TagRepository::instance().add(metadata).await?;
+        let audio_file_id = AudioFileId::new(file.absolute_path.clone());
+        match file.file_type {
+            Some(FileType::AiffFile) | Some(FileType::WavFile) => {
+                let iff_file = IffFile::read_from_path(&file.absolute_path)?;
+                let frames = iff_file
+                    .id3v2_tag()?
+                    .map(|tag| tag.frames)
+                    .unwrap_or_default();
+                let info = iff_file.info()?;
+                let metadata = Metadata::from_iff(audio_file_id, &frames, info.as_ref());
+                TagRepository::instance().add(metadata).await?;
+            }
+            Some(FileType::AsfFile) => {
+                let tag = AsfTag::read_from_path(&file.absolute_path)?;
+                let metadata = Metadata::from_asf(audio_file_id, &tag);
+                TagRepository::instance().add(metadata).await?;
+            }
+            Some(FileType::MatroskaFile) => {
+                let tags = MatroskaTags::read_from_path(&file.absolute_path)?;
+                let metadata = Metadata::from_matroska(audio_file_id, &tags);
+                TagRepository::instance().add(metadata).await?;
+            }
+            Some(FileType::Mp4aFile) => {
+                let tag = Mp4Tag::read_from_path(&file.absolute_path)?;
+                let metadata = Metadata::from_mp4(audio_file_id, &tag);
+                TagRepository::instance().add(metadata).await?;
+            }
+            Some(FileType::OggFile) => {
+                let header = OggCommentHeader::read_from_path(&file.absolute_path)?;
+                let metadata = Metadata::from_vorbis(audio_file_id, &header.comment);
+                TagRepository::instance().add(metadata).await?;
+            }
+            _ => {
+                // TODO: check if has tag before reading
+                if let Ok(id3v2) = ID3v2Tag::read_from_path(&file.absolute_path) {
+                    let metadata = Metadata::from_id3v2(audio_file_id, &id3v2.frames);
+                    TagRepository::instance().add(metadata).await?;
+                }
+            }
         }
 
         // TODO: add loading other possible tags
         Ok(())
     }
 
+    /// Searches MusicBrainz for the recording of the given file by its tags, scores the
+    /// releases of the recordings found, and lets the user choose one to match the file to.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to look up.
+    pub async fn lookup_audio_file(&self, audio_file: &AudioFile) -> Result<()> {
+        let metadata = TagRepository::instance().get(audio_file).await?;
+        let query = metadata.recording_query(audio_file.duration);
+        if query.is_empty() {
+            return Err(anyhow!(
+                "{} has no tags to look it up by!",
+                audio_file.id.to_string()
+            ));
+        }
+        info!(
+            "Searching recordings for {}: {}",
+            audio_file.id.to_string(),
+            query
+        );
+        let search = MusicBrainzService::instance()
+            .search_recordings(&query, SEARCH_LIMIT)
+            .await?;
+
+        let config = ConfigService::instance().get()?;
+        let mut results = vec![];
+        for recording in &search.recordings {
+            let search_score = recording.score as f64 / 100.0;
+            if search_score < config.search_min_score {
+                continue;
+            }
+            let lookup = MusicBrainzService::instance()
+                .lookup_releases_for_recording(&recording.id)
+                .await?;
+            let loaded = self.load_lookup(&lookup).await?;
+            for (release, album) in lookup.releases.iter().zip(loaded) {
+                let mut parts = metadata.compare_to_release_parts(
+                    release,
+                    &recording.id,
+                    audio_file.duration,
+                    &config.release_weights,
+                );
+                parts.extend(
+                    config
+                        .release_preferences
+                        .compare_to_release(release, &config.release_weights),
+                );
+                parts.push((search_score, config.release_weights.search));
+                results.push(LookupResult {
+                    album,
+                    recording_id: Some(recording.id.clone()),
+                    score: linear_combination_of_weights(&parts),
+                });
+            }
+        }
+
+        let title = metadata.title().unwrap_or(audio_file.id.to_string());
+        Ui::instance().cluster_table.open_lookup_dialog(
+            &title,
+            vec![audio_file.id.clone()],
+            LookupResult::rank(results),
+        )
+    }
+
+    /// Searches MusicBrainz for the release of the files of the given cluster by its album and
+    /// artist, scores the releases found, and lets the user choose one to match the files to.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster to look up.
+    pub async fn lookup_cluster(&self, cluster_id: &ClusterId) -> Result<()> {
+        let cluster = ClusterRepository::instance().get(cluster_id).await?;
+        let audio_files = AudioFileRepository::instance()
+            .get_by_cluster(cluster_id)
+            .await?;
+        let query = cluster.release_query(audio_files.len());
+        info!("Searching releases for {}: {}", cluster.album, query);
+        let search = MusicBrainzService::instance()
+            .search_releases(&query, SEARCH_LIMIT)
+            .await?;
+
+        // compare the album-level tags of the first file, which the files share
+        let metadata = match audio_files.first() {
+            Some(audio_file) => Some(TagRepository::instance().get(audio_file).await?),
+            None => None,
+        };
+        let config = ConfigService::instance().get()?;
+        let mut results = vec![];
+        for hit in &search.releases {
+            let search_score = hit.score as f64 / 100.0;
+            if search_score < config.search_min_score {
+                continue;
+            }
+            let release = MusicBrainzService::instance()
+                .lookup_release(&hit.id)
+                .await?;
+            let album = self.load_release(&release).await?;
+            let mut parts = metadata
+                .as_ref()
+                .map(|metadata| {
+                    metadata.compare_to_release_parts(&release, "", None, &config.release_weights)
+                })
+                .unwrap_or_default();
+            parts.extend(
+                config
+                    .release_preferences
+                    .compare_to_release(&release, &config.release_weights),
+            );
+            parts.push((search_score, config.release_weights.search));
+            results.push(LookupResult {
+                album,
+                recording_id: None,
+                score: linear_combination_of_weights(&parts),
+            });
+        }
+
+        let audio_file_ids = audio_files
+            .into_iter()
+            .map(|audio_file| audio_file.id)
+            .collect();
+        Ui::instance().cluster_table.open_lookup_dialog(
+            &cluster.album,
+            audio_file_ids,
+            LookupResult::rank(results),
+        )
+    }
+
+    /// Looks up the disc the given file was ripped from, by the rip log or cue sheet in its
+    /// directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file_id` - The ID of the audio file to look up the disc of.
+    pub async fn lookup_audio_file_disc(&self, audio_file_id: &AudioFileId) -> Result<()> {
+        let directory = audio_file_id
+            .path
+            .parent()
+            .ok_or_else(|| anyhow!("{} has no directory!", audio_file_id.to_string()))?;
+        self.lookup_disc(directory).await
+    }
+
+    /// Looks up the disc the files of the given cluster were ripped from, by the rip log or cue
+    /// sheet in the directory of its first file.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster to look up the disc of.
+    pub async fn lookup_cluster_disc(&self, cluster_id: &ClusterId) -> Result<()> {
+        let audio_file = AudioFileRepository::instance()
+            .get_by_cluster(cluster_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("The cluster has no files to look up the disc of!"))?;
+        self.lookup_audio_file_disc(&audio_file.id).await
+    }
+
+    /// Reads the table of contents of the disc the files in the given directory were ripped
+    /// from, looks it up on MusicBrainz and lets the user choose a release to match the files
+    /// to.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the rip.
+    async fn lookup_disc(&self, directory: &Path) -> Result<()> {
+        let toc = Self::read_disc_toc(directory)?;
+        info!(
+            "Looking up disc {} of {}: {}",
+            toc.disc_id(),
+            directory.display(),
+            toc.toc()
+        );
+        let lookup = MusicBrainzService::instance().lookup_disc(&toc).await?;
+        let disc_score = match lookup.id {
+            Some(_) => 1.0,
+            None => SIMILAR_DISC_SCORE,
+        };
+
+        let config = ConfigService::instance().get()?;
+        let mut results = vec![];
+        for release in &lookup.releases {
+            let album = self.load_release(release).await?;
+            let mut parts = config
+                .release_preferences
+                .compare_to_release(release, &config.release_weights);
+            parts.push((disc_score, config.release_weights.search));
+            results.push(LookupResult {
+                album,
+                recording_id: None,
+                score: linear_combination_of_weights(&parts),
+            });
+        }
+
+        let audio_file_ids = AudioFileRepository::instance()
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|audio_file| audio_file.id.path.parent() == Some(directory))
+            .map(|audio_file| audio_file.id)
+            .sorted_by(|a, b| a.path.cmp(&b.path))
+            .collect();
+        let title = directory
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(directory.display().to_string());
+        Ui::instance().cluster_table.open_lookup_dialog(
+            &title,
+            audio_file_ids,
+            LookupResult::rank(results),
+        )
+    }
+
+    /// Returns the table of contents of the disc ripped to the given directory, from its EAC or
+    /// XLD rip log, or else from its cue sheet and the lengths of the files it refers to.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the rip.
+    fn read_disc_toc(directory: &Path) -> Result<DiscToc> {
+        let paths: Vec<PathBuf> = std::fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .sorted()
+            .collect();
+        let with_extension = |extension: &'static str| {
+            paths.iter().filter(move |path| {
+                path.extension()
+                    .and_then(|path_extension| path_extension.to_str())
+                    .is_some_and(|path_extension| path_extension.eq_ignore_ascii_case(extension))
+            })
+        };
+
+        for path in with_extension("log") {
+            let log = decode_rip_text(&std::fs::read(path)?);
+            match DiscToc::from_rip_log(&log) {
+                Ok(toc) => return Ok(toc),
+                Err(e) => info!("Skipping {}: {e}", path.display()),
+            }
+        }
+        for path in with_extension("cue") {
+            let cue_sheet = decode_rip_text(&std::fs::read(path)?);
+            let toc = DiscToc::cue_sheet_files(&cue_sheet)
+                .iter()
+                .map(|file_name| Self::get_audio_sectors(&directory.join(file_name)))
+                .collect::<Result<Vec<u32>>>()
+                .and_then(|file_sectors| DiscToc::from_cue_sheet(&cue_sheet, &file_sectors));
+            match toc {
+                Ok(toc) => return Ok(toc),
+                Err(e) => info!("Skipping {}: {e}", path.display()),
+            }
+        }
+        Err(anyhow!(
+            "No rip log or cue sheet with a table of contents found in {}!",
+            directory.display()
+        ))
+    }
+
+    /// Returns the length of the audio file at the given path in CD sectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audio file.
+    fn get_audio_sectors(path: &Path) -> Result<u32> {
+        let source = FsFile::open(path)?;
+        let stream = MediaSourceStream::new(Box::new(source), Default::default());
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+            hint.with_extension(extension);
+        }
+        let reader = symphonia::default::get_probe()
+            .format(
+                &hint,
+                stream,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )?
+            .format;
+        let codec_params = &reader
+            .default_track()
+            .ok_or_else(|| anyhow!("No audio track found in {}!", path.display()))?
+            .codec_params;
+        match (codec_params.n_frames, codec_params.sample_rate) {
+            (Some(frames), Some(sample_rate)) if sample_rate > 0 => {
+                Ok((frames * SECTORS_PER_SECOND as u64 / sample_rate as u64) as u32)
+            }
+            _ => Err(anyhow!("The length of {} is unknown!", path.display())),
+        }
+    }
+
+    /// Loads the release given by its MusicBrainz ID or URL and shows it in the album pane, so
+    /// that files can be moved to its tracks before any are matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The MusicBrainz ID or URL of the release.
+    pub async fn load_release_by_id(&self, input: &str) -> Result<()> {
+        let release_id = parse_release_id(input)?;
+        info!("Loading release {}", release_id);
+        let release = MusicBrainzService::instance()
+            .lookup_release(&release_id)
+            .await?;
+        let album = self.load_release(&release).await?;
+        let tracks = TrackRepository::instance().get_all(&album).await?;
+        let match_states = MatchState::for_tracks(&tracks).await?;
+        Ui::instance()
+            .album_table
+            .add_album(&album, &tracks, &match_states)
+    }
+
+    /// Matches the given files together to the tracks of the release of the lookup result the
+    /// user chose, and removes the clusters that were emptied.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file_ids` - The IDs of the audio files that were looked up.
+    /// * `result` - The lookup result that was chosen.
+    pub async fn match_lookup_result(
+        &self,
+        audio_file_ids: &[AudioFileId],
+        result: &LookupResult,
+    ) -> Result<()> {
+        let mut audio_files = vec![];
+        let mut cluster_ids = vec![];
+        for audio_file_id in audio_file_ids {
+            let mut audio_file = AudioFileRepository::instance().get(audio_file_id).await?;
+            if let Some(recording_id) = &result.recording_id {
+                audio_file.recording_id = Some(recording_id.clone());
+            }
+            if let Some(cluster_id) = &audio_file.cluster_id {
+                if !cluster_ids.contains(cluster_id) {
+                    cluster_ids.push(cluster_id.clone());
+                }
+            }
+            audio_files.push(audio_file);
+        }
+        self.match_files_to_album(audio_files, &result.album, result.score)
+            .await?;
+
+        for cluster_id in &cluster_ids {
+            self.remove_cluster_if_empty(cluster_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Moves the given file to the given track of a loaded album, from its track or from the
+    /// cluster pane. If another file is matched to the track, the two files swap tracks, or
+    /// that file goes back to the cluster pane if the moved file had no track.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file_id` - The ID of the audio file to move.
+    /// * `track_id` - The ID of the track to move the file to.
+    pub async fn move_audio_file(
+        &self,
+        audio_file_id: &AudioFileId,
+        track_id: &TrackId,
+    ) -> Result<()> {
+        let audio_file = AudioFileRepository::instance().get(audio_file_id).await?;
+        let track = TrackRepository::instance().get_by_key(track_id).await?;
+        let old_track = match &audio_file.track_id {
+            Some(old_track_id) if old_track_id == track_id => return Ok(()),
+            Some(old_track_id) => Some(TrackRepository::instance().get_by_key(old_track_id).await?),
+            None => None,
+        };
+        let displaced = AudioFileRepository::instance()
+            .get_matched(&track)
+            .await?
+            .into_iter()
+            .find(|other| other.id != audio_file.id);
+
+        self.match_to_track(audio_file, &track).await?;
+        if let Some(displaced) = displaced {
+            match &old_track {
+                Some(old_track) => self.match_to_track(displaced, old_track).await?,
+                None => self.unmatch(displaced).await?,
+            }
+        }
+
+        if let Some(old_track) = old_track {
+            if old_track.id.album_id != track.id.album_id {
+                self.refresh_album(&old_track.id.album_id).await?;
+            }
+        }
+        self.refresh_album(&track.id.album_id).await
+    }
+
+    /// Matches the given file to the given track, by the user's choice rather than a scan.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to match.
+    /// * `track` - The track to match the file to.
+    async fn match_to_track(&self, mut audio_file: AudioFile, track: &Track) -> Result<()> {
+        let album = AlbumRepository::instance()
+            .get(&track.id.album_id.value)
+            .await?;
+        let tracks = TrackRepository::instance().get_all(&album).await?;
+        let mut metadata = TagRepository::instance().get(&audio_file).await?;
+        metadata.revert_match();
+        audio_file.recording_id = None;
+        let similarity = audio_file.track_similarity(&metadata, track);
+        audio_file.match_to_track(track, similarity);
+        let score = album.score.unwrap_or_default();
+        self.save_match(audio_file, album, &tracks, track, score)
+            .await
+    }
+
     pub fn open_logs(&self) -> Result<()> {
         let logs = LogHistory::instance()
             .logs()
@@ -122,6 +781,37 @@ impl SiskoService {
         Ok(())
     }
 
+    /// Fetches the releases of the release group of the given album, and lets the user choose
+    /// one to switch the album to.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_id` - The ID of the album to show the other versions of.
+    pub async fn open_other_versions(&self, album_id: &AlbumId) -> Result<()> {
+        let album = AlbumRepository::instance().get(&album_id.value).await?;
+        let lookup = MusicBrainzService::instance()
+            .lookup_releases_for_release_group(&album.release_group_id)
+            .await?;
+        let loaded = self.load_lookup(&lookup).await?;
+        let mut versions: Vec<ReleaseVersion> = lookup
+            .releases
+            .iter()
+            .zip(loaded)
+            .map(|(release, album)| ReleaseVersion::new(release, album))
+            .collect();
+        versions.sort_by(|a, b| a.album.date.cmp(&b.album.date));
+        Ui::instance()
+            .album_table
+            .open_other_versions_dialog(album_id, versions)
+    }
+
+    pub fn open_release_preferences(&self) -> Result<()> {
+        let config = ConfigService::instance().get()?;
+        Ui::instance()
+            .menu
+            .open_release_preferences(&config.release_preferences, &config.release_weights)
+    }
+
     pub async fn remove_file(&self, audio_file: &AudioFile) -> Result<()> {
         let mut audio_file = audio_file.clone();
         audio_file
@@ -130,8 +820,27 @@ impl SiskoService {
         AudioFileRepository::instance().remove(audio_file).await
     }
 
+    /// Replaces the release preferences and weights in the config and persists it. They're
+    /// used for files scanned from now on.
+    ///
+    /// # Arguments
+    ///
+    /// * `preferences` - The new release preferences.
+    /// * `weights` - The new release weights.
+    pub fn save_release_preferences(
+        &self,
+        preferences: ReleasePreferences,
+        weights: ReleaseWeights,
+    ) -> Result<()> {
+        let mut config = ConfigService::instance().get()?;
+        config.release_preferences = preferences;
+        config.release_weights = weights;
+        ConfigService::instance().save(config)?;
+        info!("Saved release preferences");
+        Ok(())
+    }
+
     pub async fn save_audio_file(&self, audio_file: &AudioFile) -> Result<()> {
-        let audio_bytes = Self::get_audio_bytes(&audio_file.id.path)?;
         let filename = &audio_file
             .id
             .path
@@ -140,9 +849,37 @@ impl SiskoService {
             .to_string_lossy()
             .to_string();
         let tag = TagRepository::instance().get(audio_file).await?;
-        let tag = ID3v2Tag::from(&tag);
-        let mut bytes = tag.to_bytes();
-        bytes.extend(audio_bytes);
+        let id3v2_version = ConfigService::instance().get()?.id3v2_version;
+        let file_type = FileService::instance()
+            .get(&audio_file.id.path)?
+            .file_type
+            .unwrap_or(FileType::UnsupportedFile);
+        let bytes = match file_type {
+            FileType::AiffFile | FileType::WavFile => {
+                Self::get_iff_bytes(&audio_file.id.path, &tag, id3v2_version)?
+            }
+            FileType::AsfFile => Self::get_asf_bytes(&audio_file.id.path, &tag)?,
+            FileType::MatroskaFile => Self::get_matroska_bytes(&audio_file.id.path, &tag)?,
+            FileType::Mp4aFile => Self::get_mp4_bytes(&audio_file.id.path, &tag)?,
+            FileType::OggFile => Self::get_ogg_bytes(&audio_file.id.path, &tag)?,
+            _ => {
+                let audio_bytes = Self::get_audio_bytes(&audio_file.id.path)?;
+                let tag = tag.into_id3v2_tag(id3v2_version)?;
+                let mut bytes = tag.to_bytes();
+                bytes.extend(audio_bytes);
+                bytes
+            }
+        };
+        // NOTE: refuse to write anything but tags
+        let audio_checksum = match &audio_file.audio_checksum {
+            Some(audio_checksum) => audio_checksum.clone(),
+            None => AudioChecksum::read_from_path(&audio_file.id.path)?.to_string(),
+        };
+        if AudioChecksum::compute(&bytes)?.to_string() != audio_checksum {
+            return Err(anyhow!(
+                "Not saving {filename}, since its audio would have changed!"
+            ));
+        }
         let mut file = FsFile::create(filename)?;
         file.write_all(&bytes)?;
 
@@ -158,6 +895,35 @@ impl SiskoService {
         Ok(())
     }
 
+    fn get_asf_bytes(path: &PathBuf, tag: &Metadata) -> Result<Vec<u8>> {
+        let mut asf_tag = AsfTag::read_from_path(path)?;
+        let new_tag = tag.into_asf();
+        // NOTE: keep the copyright, description and rating, which sisko doesn't understand
+        let description = asf_tag
+            .content_description
+            .get_or_insert_with(Default::default);
+        if let Some(new_description) = new_tag.content_description {
+            description.title = new_description.title;
+            description.author = new_description.author;
+        }
+        let descriptors = new_tag
+            .extended_content_description
+            .map(|description| description.descriptors)
+            .unwrap_or_default();
+        let description = asf_tag
+            .extended_content_description
+            .get_or_insert_with(Default::default);
+        // NOTE: keep the descriptors sisko doesn't understand, e.g. WM/EncodingTime
+        description.descriptors.retain(|descriptor| {
+            !descriptors
+                .iter()
+                .any(|new_descriptor| new_descriptor.name == descriptor.name)
+        });
+        description.descriptors.extend(descriptors);
+        let file_bytes = std::fs::read(path)?;
+        asf_tag.write_to_bytes(&file_bytes)
+    }
+
     fn get_audio_bytes(path: &PathBuf) -> Result<Vec<u8>> {
         let offset = match ID3v2Tag::read_from_path(path).ok() {
             Some(tag) => tag.total_size(),
@@ -171,6 +937,137 @@ impl SiskoService {
         Ok(file_content)
     }
 
+    fn get_iff_bytes(path: &PathBuf, tag: &Metadata, id3v2_version: u8) -> Result<Vec<u8>> {
+        let mut iff_file = IffFile::read_from_path(path)?;
+        let frames = match iff_file.id3v2_tag()? {
+            Some(id3v2_tag) => id3v2_tag.frames,
+            None => vec![],
+        };
+        iff_file.set_id3v2_tag(&tag.merge_into_id3v2_tag(&frames, id3v2_version)?);
+        if iff_file.format == IffFormat::Riff {
+            let mut info = iff_file.info()?.unwrap_or_default();
+            let fields = tag.into_riff_info();
+            // NOTE: keep the info fields sisko doesn't understand, e.g. ICMT
+            info.fields.retain(|field| {
+                !fields
+                    .iter()
+                    .any(|new_field| new_field.field_id == field.field_id)
+            });
+            info.fields.extend(fields);
+            iff_file.set_info(&info)?;
+        }
+        Ok(iff_file.to_bytes())
+    }
+
+    fn get_matroska_bytes(path: &PathBuf, tag: &Metadata) -> Result<Vec<u8>> {
+        let mut matroska_tags = MatroskaTags::read_from_path(path)?;
+        // NOTE: keep the simple tags sisko doesn't have a field for, e.g. nested ones
+        for existing_tag in matroska_tags.tags.iter_mut() {
+            let target_type_value = existing_tag.target_type_value;
+            existing_tag.simple_tags.retain(|simple_tag| {
+                let field_type = TagFieldType::from_matroska(target_type_value, &simple_tag.name);
+                tag.get(&field_type).is_none()
+            });
+        }
+        for new_tag in tag.into_matroska() {
+            match matroska_tags
+                .tags
+                .iter_mut()
+                .find(|existing_tag| existing_tag.target_type_value == new_tag.target_type_value)
+            {
+                Some(existing_tag) => existing_tag.simple_tags.extend(new_tag.simple_tags),
+                None => matroska_tags.tags.push(new_tag),
+            }
+        }
+        matroska_tags.tags.retain(|tag| !tag.simple_tags.is_empty());
+        let file_bytes = std::fs::read(path)?;
+        matroska_tags.write_to_bytes(&file_bytes)
+    }
+
+    fn get_mp4_bytes(path: &PathBuf, tag: &Metadata) -> Result<Vec<u8>> {
+        let mut mp4_tag = Mp4Tag::read_from_path(path)?;
+        let original = Metadata::from_mp4(tag.audio_file_id.clone(), &mp4_tag);
+        let mut changed = tag.changed_field_types(&original);
+        // NOTE: a number and its total share an item, so both are written when either changed
+        for pair in [
+            [TagFieldType::TrackNumber, TagFieldType::TotalTracks],
+            [TagFieldType::DiscNumber, TagFieldType::TotalDiscs],
+        ] {
+            if pair.iter().any(|field_type| changed.contains(field_type)) {
+                changed.extend(pair);
+            }
+        }
+        // NOTE: the items of unchanged fields are kept as they were read, e.g. non-text ones and
+        // every cover
+        mp4_tag
+            .items
+            .retain(|item| !changed.contains(&TagFieldType::from(item)));
+        mp4_tag.items.extend(tag.subset(&changed).into_mp4()?);
+        let file_bytes = std::fs::read(path)?;
+        mp4_tag.write_to_bytes(&file_bytes)
+    }
+
+    fn get_ogg_bytes(path: &PathBuf, tag: &Metadata) -> Result<Vec<u8>> {
+        let mut header = OggCommentHeader::read_from_path(path)?;
+        let original = Metadata::from_vorbis(tag.audio_file_id.clone(), &header.comment);
+        let changed = tag.changed_field_types(&original);
+        // NOTE: the comments of unchanged fields are written back as they were read, e.g. every
+        // picture and repeated or unknown comments
+        header
+            .comment
+            .fields
+            .retain(|field| !changed.contains(&TagFieldType::from(field)));
+        header
+            .comment
+            .fields
+            .extend(tag.subset(&changed).into_vorbis()?);
+        let file_bytes = std::fs::read(path)?;
+        header.write_to_bytes(&file_bytes)
+    }
+
+    /// Saves the given file as matched to the given track, updating its tags for the track and
+    /// the score of the album, and moves it from the cluster table to the album table.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file that was matched.
+    /// * `album` - The album the file was matched to.
+    /// * `tracks` - The tracks of the album.
+    /// * `track` - The track the file was matched to.
+    /// * `score` - How well the album matched the file, from 0 to 1.
+    async fn save_match(
+        &self,
+        mut audio_file: AudioFile,
+        mut album: Album,
+        tracks: &[Track],
+        track: &Track,
+        score: f64,
+    ) -> Result<()> {
+        audio_file.cluster_id = None;
+        audio_file.scan_status = ScanStatus::Matched;
+        album.score = Some(score);
+        AlbumRepository::instance().save(album.clone()).await?;
+        let mut updated_tag = TagRepository::instance().get(&audio_file).await?;
+        updated_tag.revert_match();
+        updated_tag.update_for_match(&audio_file, &album, track);
+        audio_file
+            .events
+            .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
+        AudioFileRepository::instance()
+            .save(audio_file.clone())
+            .await?;
+        TagRepository::instance().save(updated_tag).await?;
+        let match_states = MatchState::for_tracks(tracks).await?;
+
+        // TODO: replace with event handler for AudioFileMatched
+        Ui::instance()
+            .cluster_table
+            .remove_cluster_file(&audio_file)?;
+        Ui::instance()
+            .album_table
+            .add_album(&album, tracks, &match_states)
+    }
+
     pub async fn select_audio_file(&self, audio_file_id: &AudioFileId) -> Result<()> {
         let audio_file = AudioFileRepository::instance().get(audio_file_id).await?;
         let tag = TagRepository::instance().get(&audio_file).await?;
@@ -178,60 +1075,472 @@ impl SiskoService {
     }
 
     pub async fn scan_audio_file(&self, audio_file: &AudioFile) -> Result<()> {
+        self.match_audio_file(audio_file).await?;
+        Ok(())
+    }
+
+    /// Scans the files of the given cluster as one album: files are scanned one by one until
+    /// one is matched to a release, and then the rest are identified by their fingerprints and
+    /// matched together to the tracks left on that release.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster to scan.
+    pub async fn scan_cluster(&self, cluster_id: &ClusterId) -> Result<()> {
+        let cluster = ClusterRepository::instance().get(cluster_id).await?;
+        let audio_files = AudioFileRepository::instance()
+            .get_by_cluster(cluster_id)
+            .await?;
+        info!(
+            "Scanning {} files in cluster {}",
+            audio_files.len(),
+            cluster.album
+        );
+        let mut matched: Option<(Album, Vec<Track>)> = None;
+        let mut rest = vec![];
+        for mut audio_file in audio_files {
+            match &matched {
+                None => match self.match_audio_file(&audio_file).await {
+                    Ok(Some(album_id)) => {
+                        let album = AlbumRepository::instance().get(&album_id.value).await?;
+                        let tracks = TrackRepository::instance().get_all(&album).await?;
+                        matched = Some((album, tracks));
+                    }
+                    Ok(None) => {
+                        rest.push(AudioFileRepository::instance().get(&audio_file.id).await?)
+                    }
+                    Err(e) => {
+                        warn!("Error scanning {}: {}", audio_file.id.to_string(), e);
+                        rest.push(audio_file);
+                    }
+                },
+                Some((_, tracks)) => {
+                    let metadata = TagRepository::instance().get(&audio_file).await?;
+                    if self
+                        .match_by_musicbrainz_ids(&audio_file, &metadata)
+                        .await?
+                        .is_some()
+                    {
+                        continue;
+                    }
+                    if let Err(e) = self.identify_recording(&mut audio_file, tracks).await {
+                        warn!("Error identifying {}: {}", audio_file.id.to_string(), e);
+                    }
+                    rest.push(audio_file);
+                }
+            }
+        }
+
+        if let Some((album, _)) = matched {
+            let score = album.score.unwrap_or_default();
+            self.match_files_to_album(rest, &album, score).await?;
+        }
+        self.remove_cluster_if_empty(cluster_id).await
+    }
+
+    /// Looks up the fingerprint of the given file and sets the recording of the best AcoustID
+    /// candidate that's one of the given tracks, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to identify.
+    /// * `tracks` - The tracks the recording may be of.
+    async fn identify_recording(&self, audio_file: &mut AudioFile, tracks: &[Track]) -> Result<()> {
+        let fingerprint = AcoustIdService::instance()
+            .get_fingerprint(&audio_file.id.path)
+            .await?;
+        let lookup = AcoustIdService::instance()
+            .lookup_fingerprint(&fingerprint)
+            .await?;
+        let config = ConfigService::instance().get()?;
+        let candidates = RecordingCandidate::from_results(&lookup, config.acoustid_min_score);
+        match candidates.iter().find(|candidate| {
+            tracks
+                .iter()
+                .any(|track| track.recording_id == candidate.recording_id)
+        }) {
+            Some(candidate) => {
+                audio_file.acoust_id = Some(candidate.acoust_id.clone());
+                audio_file.fingerprint_recording_id = Some(candidate.recording_id.clone());
+                audio_file.recording_id = Some(candidate.recording_id.clone());
+            }
+            // NOTE: kept so that a file of a recording that isn't on the release is reported
+            None => {
+                audio_file.fingerprint_recording_id = candidates
+                    .first()
+                    .map(|candidate| candidate.recording_id.clone())
+            }
+        }
+        Ok(())
+    }
+
+    /// Matches the given files together to the tracks of the given album that no file is
+    /// matched to yet, so that no two files get the same track. The files that don't fit any
+    /// track are saved as unmatched.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_files` - The audio files to match.
+    /// * `album` - The album to match the files to.
+    /// * `score` - How well the album matched the files, from 0 to 1.
+    async fn match_files_to_album(
+        &self,
+        audio_files: Vec<AudioFile>,
+        album: &Album,
+        score: f64,
+    ) -> Result<()> {
+        let tracks = TrackRepository::instance().get_all(album).await?;
+        let mut free_tracks = vec![];
+        for track in &tracks {
+            if AudioFileRepository::instance()
+                .get_matched(track)
+                .await?
+                .is_empty()
+            {
+                free_tracks.push(track.clone());
+            }
+        }
+        let mut metadata = vec![];
+        for audio_file in &audio_files {
+            metadata.push(TagRepository::instance().get(audio_file).await?);
+        }
+        let files: Vec<(&AudioFile, &Metadata)> = audio_files.iter().zip(&metadata).collect();
+        let assignment = AudioFile::assign_tracks(&files, &free_tracks);
+
+        for (mut audio_file, track) in audio_files.into_iter().zip(assignment) {
+            match track {
+                Some((index, similarity)) => {
+                    let track = &free_tracks[index];
+                    audio_file.match_to_track(track, similarity);
+                    self.save_match(audio_file, album.clone(), &tracks, track, score)
+                        .await?;
+                }
+                None => {
+                    warn!(
+                        "{} doesn't fit any track left on {}",
+                        audio_file.id.to_string(),
+                        album.title
+                    );
+                    audio_file.scan_status = ScanStatus::NoMatch;
+                    audio_file
+                        .events
+                        .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
+                    AudioFileRepository::instance().save(audio_file).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Matches the given file to the track of the release it's tagged with by its MusicBrainz
+    /// IDs, without fingerprinting it, returning the ID of the release if it was matched. The
+    /// track is found by its release track ID, or else by its recording ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to match.
+    /// * `metadata` - The tags of the audio file.
+    async fn match_by_musicbrainz_ids(
+        &self,
+        audio_file: &AudioFile,
+        metadata: &Metadata,
+    ) -> Result<Option<AlbumId>> {
+        let release_id = match metadata.musicbrainz_release_id() {
+            Some(release_id) => release_id,
+            None => return Ok(None),
+        };
+        let album = match AlbumRepository::instance().get(&release_id).await {
+            Ok(album) => album,
+            Err(_) => match MusicBrainzService::instance()
+                .lookup_release(&release_id)
+                .await
+            {
+                Ok(release) => self.load_release(&release).await?,
+                Err(e) => {
+                    warn!(
+                        "Error loading release {} of {}: {}",
+                        release_id,
+                        audio_file.id.to_string(),
+                        e
+                    );
+                    return Ok(None);
+                }
+            },
+        };
+        let tracks = TrackRepository::instance().get_all(&album).await?;
+        let track_id = metadata.musicbrainz_track_id();
+        let recording_id = metadata.musicbrainz_recording_id();
+        let track = tracks
+            .iter()
+            .find(|track| track_id.as_ref() == Some(&track.id.track_id))
+            .or_else(|| {
+                tracks
+                    .iter()
+                    .find(|track| recording_id.as_ref() == Some(&track.recording_id))
+            });
+        let track = match track {
+            Some(track) => track.clone(),
+            None => {
+                warn!(
+                    "{} is tagged with release {} but none of its tracks",
+                    audio_file.id.to_string(),
+                    release_id
+                );
+                return Ok(None);
+            }
+        };
+
+        info!(
+            "Matching {} by its MusicBrainz IDs",
+            audio_file.id.to_string()
+        );
+        let mut audio_file = audio_file.clone();
+        audio_file.match_to_track(&track, 1.0);
+        let album_id = album.id.clone();
+        self.save_match(audio_file, album, &tracks, &track, 1.0)
+            .await?;
+        Ok(Some(album_id))
+    }
+
+    /// Removes the given cluster if none of the files are in it anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster to remove.
+    async fn remove_cluster_if_empty(&self, cluster_id: &ClusterId) -> Result<()> {
+        if AudioFileRepository::instance()
+            .get_by_cluster(cluster_id)
+            .await?
+            .is_empty()
+        {
+            let cluster = ClusterRepository::instance().get(cluster_id).await?;
+            ClusterRepository::instance().remove(cluster).await?;
+        }
+        Ok(())
+    }
+
+    /// Matches the given file to the best scoring release, returning the ID of the release it
+    /// was matched to, if any. Files tagged with MusicBrainz IDs are matched by them, and the
+    /// rest are fingerprinted and looked up.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to match.
+    async fn match_audio_file(&self, audio_file: &AudioFile) -> Result<Option<AlbumId>> {
+        // musicbrainz ids
+        let metadata = TagRepository::instance().get(audio_file).await?;
+        if let Some(album_id) = self.match_by_musicbrainz_ids(audio_file, &metadata).await? {
+            return Ok(Some(album_id));
+        }
+
         // fingerprint
         let file_path = audio_file.id.path.clone();
-        let fingerprint = AcoustIdService::instance().get_fingerprint(&file_path)?;
+        let fingerprint = AcoustIdService::instance()
+            .get_fingerprint(&file_path)
+            .await?;
 
         // acoustid
         let lookup = AcoustIdService::instance()
             .lookup_fingerprint(&fingerprint)
             .await?;
+        let config = ConfigService::instance().get()?;
+        let candidates = RecordingCandidate::from_results(&lookup, config.acoustid_min_score);
+
+        // releases
+        let length = audio_file.duration.or(fingerprint
+            .duration
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs));
+        let mut albums = vec![];
+        for candidate in &candidates {
+            let lookup = MusicBrainzService::instance()
+                .lookup_releases_for_recording(&candidate.recording_id)
+                .await?;
+            let loaded = self.load_lookup(&lookup).await?;
+            for (release, album) in lookup.releases.iter().zip(loaded) {
+                let mut parts = metadata.compare_to_release_parts(
+                    release,
+                    &candidate.recording_id,
+                    length,
+                    &config.release_weights,
+                );
+                parts.extend(
+                    config
+                        .release_preferences
+                        .compare_to_release(release, &config.release_weights),
+                );
+                parts.push((candidate.score, config.release_weights.acoustid));
+                let score = linear_combination_of_weights(&parts);
+                albums.push((candidate, album, score));
+            }
+        }
 
-        // recording
-        let recordingid = lookup[0].recordings[0].id.clone();
+        // match
         let mut audio_file = audio_file.clone();
-        audio_file.acoust_id = Some(lookup[0].id.clone());
-        audio_file.recording_id = Some(recordingid.clone());
+        for (candidate, album, score) in self.rank_albums(albums) {
+            audio_file.acoust_id = Some(candidate.acoust_id.clone());
+            audio_file.fingerprint_recording_id = Some(candidate.recording_id.clone());
+            audio_file.recording_id = Some(candidate.recording_id.clone());
+            let tracks = TrackRepository::instance().get_all(&album).await?;
+            let matched_track = match audio_file.match_to_album(&album, &tracks, &metadata) {
+                Ok(matched_track) => matched_track,
+                Err(_) => continue,
+            };
+            let album_id = album.id.clone();
+            self.save_match(audio_file, album, &tracks, &matched_track, score)
+                .await?;
+            return Ok(Some(album_id));
+        }
 
-        // lookup
-        let lookup = MusicBrainzService::instance()
-            .lookup_releases_for_recording(&recordingid)
+        // no match
+        warn!(
+            "No match found for {} among {} AcoustID candidates",
+            audio_file.id.to_string(),
+            candidates.len()
+        );
+        audio_file.acoust_id = lookup.first().map(|result| result.id.clone());
+        audio_file.fingerprint_recording_id = candidates
+            .first()
+            .map(|candidate| candidate.recording_id.clone());
+        audio_file.recording_id = None;
+        audio_file.scan_status = ScanStatus::NoMatch;
+        audio_file
+            .events
+            .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
+        AudioFileRepository::instance().save(audio_file).await?;
+        Ok(None)
+    }
+
+    /// Switches the given album to another release of its release group, moving its files to
+    /// the tracks of the same recordings on the new release. The files that don't fit any
+    /// track of the new release go back to the cluster pane.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_id` - The ID of the album to switch.
+    /// * `new_album_id` - The ID of the release to switch the album to.
+    pub async fn switch_release(&self, album_id: &AlbumId, new_album_id: &AlbumId) -> Result<()> {
+        if album_id == new_album_id {
+            return Ok(());
+        }
+        let album = AlbumRepository::instance().get(&album_id.value).await?;
+        let new_album = AlbumRepository::instance().get(&new_album_id.value).await?;
+        let mut audio_files = vec![];
+        for track in TrackRepository::instance().get_all(&album).await? {
+            for mut audio_file in AudioFileRepository::instance().get_matched(&track).await? {
+                audio_file.recording_id = Some(track.recording_id.clone());
+                audio_files.push(audio_file);
+            }
+        }
+        info!(
+            "Switching {} files from {} to {}",
+            audio_files.len(),
+            album_id.to_string(),
+            new_album_id.to_string()
+        );
+        let audio_file_ids: Vec<AudioFileId> = audio_files
+            .iter()
+            .map(|audio_file| audio_file.id.clone())
+            .collect();
+        self.match_files_to_album(audio_files, &new_album, album.score.unwrap_or_default())
             .await?;
 
-        // get album
-        let albums = self.load_lookup(&lookup).await?;
-        let album = self.choose_album(&albums)?;
-        let tracks = TrackRepository::instance().get_all(&album).await?;
-        let matched_track = audio_file.match_to_album(&album, &tracks)?;
-        let mut updated_tag = TagRepository::instance().get(&audio_file).await?;
-        updated_tag.update_for_match(&audio_file, &album, &matched_track);
+        for audio_file_id in &audio_file_ids {
+            let audio_file = AudioFileRepository::instance().get(audio_file_id).await?;
+            if audio_file
+                .track_id
+                .as_ref()
+                .is_some_and(|track_id| track_id.album_id == *album_id)
+            {
+                self.unmatch(audio_file).await?;
+            }
+        }
+        self.refresh_album(album_id).await
+    }
+
+    /// Unmatches all the files of the given album, and removes it from the album pane.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_id` - The ID of the album to unmatch.
+    pub async fn unmatch_album(&self, album_id: &AlbumId) -> Result<()> {
+        let album = AlbumRepository::instance().get(&album_id.value).await?;
+        for track in TrackRepository::instance().get_all(&album).await? {
+            for audio_file in AudioFileRepository::instance().get_matched(&track).await? {
+                self.unmatch(audio_file).await?;
+            }
+        }
+        self.refresh_album(album_id).await
+    }
+
+    /// Unmatches the given file from its track, rolling back the tags the match set, and puts
+    /// it back in the cluster pane.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file_id` - The ID of the audio file to unmatch.
+    pub async fn unmatch_audio_file(&self, audio_file_id: &AudioFileId) -> Result<()> {
+        let audio_file = AudioFileRepository::instance().get(audio_file_id).await?;
+        let album_id = audio_file
+            .track_id
+            .as_ref()
+            .map(|track_id| track_id.album_id.clone())
+            .ok_or_else(|| anyhow!("{} isn't matched to a track!", audio_file_id.to_string()))?;
+        self.unmatch(audio_file).await?;
+        self.refresh_album(&album_id).await
+    }
+
+    /// Unmatches the given file from its track and rolls back the tags the match set.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to unmatch.
+    async fn unmatch(&self, mut audio_file: AudioFile) -> Result<()> {
+        let mut metadata = TagRepository::instance().get(&audio_file).await?;
+        metadata.revert_match();
+        metadata
+            .events
+            .push(DomainEvent::TagUpdated(metadata.clone()));
+        audio_file.recording_id = None;
+        audio_file.scan_status = ScanStatus::Unscanned;
+        audio_file.similarity = None;
+        audio_file.track_id = None;
         audio_file
             .events
             .push(DomainEvent::AudioFileUpdated(audio_file.clone()));
-        AudioFileRepository::instance()
-            .save(audio_file.clone())
-            .await?;
-        TagRepository::instance().save(updated_tag).await?;
-        let match_states = MatchState::for_tracks(&tracks).await?;
+        AudioFileRepository::instance().save(audio_file).await?;
+        TagRepository::instance().save(metadata).await
+    }
 
-        // TODO: replace with event handler for AudioFileMatched
-        Ui::instance()
-            .cluster_table
-            .remove_cluster_file(&audio_file)?;
-        Ui::instance()
-            .album_table
-            .add_album(&album, &tracks, &match_states)?;
-        Ok(())
+    /// Shows the current matches of the given album in the album pane, or removes it from
+    /// there if none of its tracks are matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_id` - The ID of the album to show.
+    async fn refresh_album(&self, album_id: &AlbumId) -> Result<()> {
+        let album = AlbumRepository::instance().get(&album_id.value).await?;
+        let tracks = TrackRepository::instance().get_all(&album).await?;
+        let match_states = MatchState::for_tracks(&tracks).await?;
+        match match_states
+            .iter()
+            .any(|match_state| match_state.is_matched())
+        {
+            true => Ui::instance()
+                .album_table
+                .add_album(&album, &tracks, &match_states),
+            false => Ui::instance().album_table.remove_album(album_id),
+        }
     }
 
     pub fn update_ui(&self) {
         spawn(async move {
             let audio_files = AudioFileRepository::instance().get_all().await?;
             for audio_file in audio_files.into_iter().filter(|f| f.track_id.is_none()) {
-                let metadata = TagRepository::instance().get(&audio_file).await?;
-                Ui::instance()
-                    .cluster_table
-                    .add_cluster_file(audio_file, &metadata)?;
+                SiskoService::instance()
+                    .update_cluster_table(audio_file)
+                    .await?;
             }
 
             let albums = AlbumRepository::instance().get_all().await?;
@@ -248,19 +1557,36 @@ impl SiskoService {
         });
     }
 
-    fn choose_album(&self, albums: &[Album]) -> Result<Album> {
-        /*let (release, _) = lookup
-            .releases
-            .iter()
-            .map(|r| (r, self.get_priority(&r)))
-            .max_by(|(_, p1), (_, p2)| p1.total_cmp(p2))
-            .unwrap();
-        Ok(release.id.clone())*/
-        Ok(albums[0].clone())
+    /// Returns the given albums ordered from best to worst by their scores, keeping the
+    /// release order of each lookup among equal scores.
+    fn rank_albums<'a>(
+        &self,
+        mut albums: Vec<(&'a RecordingCandidate, Album, f64)>,
+    ) -> Vec<(&'a RecordingCandidate, Album, f64)> {
+        albums.sort_by(|(_, _, score1), (_, _, score2)| score2.total_cmp(score1));
+        albums
     }
 
-    fn get_priority(&self, release: &Release) -> f64 {
-        0.5
+    /// Shows the given file in the cluster table under its cluster while it's unmatched, or
+    /// removes it from there once it's matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to show.
+    async fn update_cluster_table(&self, audio_file: AudioFile) -> Result<()> {
+        if audio_file.track_id.is_some() {
+            return Ui::instance()
+                .cluster_table
+                .remove_cluster_file(&audio_file);
+        }
+        let metadata = TagRepository::instance().get(&audio_file).await?;
+        let cluster = match &audio_file.cluster_id {
+            Some(cluster_id) => Some(ClusterRepository::instance().get(cluster_id).await?),
+            None => None,
+        };
+        Ui::instance()
+            .cluster_table
+            .add_cluster_file(audio_file, &metadata, cluster)
     }
 
     async fn load_lookup(&self, lookup: &ReleaseLookup) -> Result<Vec<Album>> {
@@ -273,6 +1599,9 @@ impl SiskoService {
     }
 
     async fn load_release(&self, release: &Release) -> Result<Album> {
+        if let Ok(album) = AlbumRepository::instance().get(&release.id).await {
+            return Ok(album);
+        }
         let album = Album::from(release);
         AlbumRepository::instance().add(album.clone()).await?;
 
@@ -299,3 +1628,135 @@ impl Default for SiskoService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::FieldValue;
+    use sisko_lib::id3v2_frame::ID3v2Frame;
+    use sisko_lib::id3v2_frame_fields::ID3v2FrameFields;
+    use sisko_lib::id3v2_frame_flags::ID3v2FrameFlags;
+    use sisko_lib::id3v2_frame_header::ID3v2FrameHeader;
+    use sisko_lib::iff_chunk::IffChunk;
+    use sisko_lib::picture_type::PictureType;
+    use sisko_lib::riff_info::RiffInfo;
+    use sisko_lib::riff_info_field::RiffInfoField;
+    use sisko_lib::text_encoding::TextEncoding;
+    use std::collections::HashMap;
+
+    #[test]
+    fn get_iff_bytes_keeps_unknown_info_fields() {
+        let path = std::env::temp_dir().join(format!("sisko-{}.wav", std::process::id()));
+        let mut iff_file = IffFile {
+            format: IffFormat::Riff,
+            form_type: String::from("WAVE"),
+            chunks: vec![IffChunk::new("data", vec![0; 4])],
+        };
+        let info = RiffInfo {
+            fields: vec![
+                RiffInfoField::new("INAM", "Old Title"),
+                RiffInfoField::new("ICMT", "Ripped by hand"),
+            ],
+        };
+        iff_file.set_info(&info).unwrap();
+        std::fs::write(&path, iff_file.to_bytes()).unwrap();
+
+        let mut tag = Metadata::from_iff(AudioFileId::new(path.clone()), &[], Some(&info));
+        tag.update(
+            &TagFieldType::Title,
+            FieldValue::Text(String::from("New Title")),
+        );
+        let bytes = SiskoService::get_iff_bytes(&path, &tag, 4);
+        std::fs::remove_file(&path).unwrap();
+
+        let iff_file = IffFile::parse(&bytes.unwrap()).unwrap();
+        let info = iff_file.info().unwrap().unwrap();
+        assert_eq!(info.get("ICMT"), Some("Ripped by hand"));
+        assert_eq!(info.get("INAM"), Some("New Title"));
+        let frames = iff_file.id3v2_tag().unwrap().unwrap().frames;
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| frame.header.frame_id.as_str())
+                .collect_vec(),
+            vec!["TIT2"]
+        );
+        assert_eq!(
+            Metadata::from_id3v2(AudioFileId::new(path), &frames).value(&TagFieldType::Title),
+            Some(&FieldValue::Text(String::from("New Title")))
+        );
+    }
+
+    #[test]
+    fn get_iff_bytes_keeps_frames_without_a_field() {
+        let path = std::env::temp_dir().join(format!("sisko-{}.aiff", std::process::id()));
+        let mut iff_file = IffFile {
+            format: IffFormat::Aiff,
+            form_type: String::from("AIFF"),
+            chunks: vec![IffChunk::new("SSND", vec![0; 4])],
+        };
+        let original = Metadata::new(
+            AudioFileId::new(path.clone()),
+            HashMap::from([(
+                TagFieldType::Title,
+                FieldValue::Text(String::from("Old Title")),
+            )]),
+        );
+        let mut id3v2_tag = original.into_id3v2_tag(4).unwrap();
+        let fields = ID3v2FrameFields::AttachedPictureFields {
+            encoding: TextEncoding::Utf8,
+            mime_type: String::from("image/png"),
+            picture_type: PictureType::CoverFront,
+            description: String::from("Cover"),
+            picture_data: vec![1, 2, 3],
+        };
+        let size = fields.to_bytes().len() as u32;
+        id3v2_tag.frames.push(ID3v2Frame {
+            header: ID3v2FrameHeader {
+                frame_id: String::from("APIC"),
+                size,
+                flags: ID3v2FrameFlags::parse(&[0, 0]),
+            },
+            fields,
+        });
+        id3v2_tag.header.size += size + 10;
+        iff_file.set_id3v2_tag(&id3v2_tag);
+        std::fs::write(&path, iff_file.to_bytes()).unwrap();
+
+        let frames = iff_file.id3v2_tag().unwrap().unwrap().frames;
+        let mut tag = Metadata::from_iff(AudioFileId::new(path.clone()), &frames, None);
+        tag.update(
+            &TagFieldType::Title,
+            FieldValue::Text(String::from("New Title")),
+        );
+        let bytes = SiskoService::get_iff_bytes(&path, &tag, 3);
+        std::fs::remove_file(&path).unwrap();
+
+        let iff_file = IffFile::parse(&bytes.unwrap()).unwrap();
+        let frames = iff_file.id3v2_tag().unwrap().unwrap().frames;
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| frame.header.frame_id.as_str())
+                .sorted()
+                .collect_vec(),
+            vec!["APIC", "TIT2"]
+        );
+        let picture = frames.iter().find(|frame| frame.header.frame_id == "APIC");
+        match &picture.unwrap().fields {
+            ID3v2FrameFields::AttachedPictureFields {
+                description,
+                picture_data,
+                ..
+            } => {
+                assert_eq!(description, "Cover");
+                assert_eq!(picture_data, &[1, 2, 3]);
+            }
+            fields => panic!("Expected the picture to be kept, got {:?}!", fields),
+        }
+        assert_eq!(
+            Metadata::from_id3v2(AudioFileId::new(path), &frames).value(&TagFieldType::Title),
+            Some(&FieldValue::Text(String::from("New Title")))
+        );
+    }
+}
@@ -5,5 +5,7 @@ use serde::Deserialize;
 pub struct AcoustIdResult {
     pub id: String,
     pub score: f64,
+    /// The recordings linked to the result, which AcoustID omits when there are none.
+    #[serde(default)]
     pub recordings: Vec<AcoustIdRecording>,
 }
@@ -1,11 +1,39 @@
-use super::{AcoustIdResult, Fingerprint};
+use super::{
+    AcoustIdResult, AcoustIdSubmission, AcoustIdSubmissionStatus, Fingerprint, FingerprintError,
+};
+use crate::infrastructure::config::{AppConfig, ConfigService, FingerprintBackend};
 use anyhow::{anyhow, Result};
-use regex::Regex;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use sisko_lib::chromaprint::{encode_fingerprint, similarity, Chromaprint};
 use std::collections::HashMap;
+use std::io::ErrorKind;
 use std::path::Path;
-use std::process::Command;
 use std::sync::OnceLock;
+use std::time::Duration;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tokio::process::Command;
+
+/// The API key of sisko as an AcoustID client application.
+const CLIENT_KEY: &str = "KS7Sc4UiGc";
+
+/// The version of sisko reported to AcoustID.
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The number of fingerprints submitted to AcoustID in each request.
+const SUBMIT_BATCH_SIZE: usize = 50;
+
+/// The number of times the statuses of pending submissions are checked.
+const POLL_ATTEMPTS: usize = 10;
+
+/// The time to wait between checks of the statuses of pending submissions.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct AcoustIdService {}
 
@@ -19,35 +47,103 @@ impl AcoustIdService {
         Self {}
     }
 
-    pub fn get_fingerprint(&self, path: &Path) -> Result<Fingerprint> {
-        let output = Command::new("fpcalc").arg(path).output()?;
-        let output = String::from_utf8_lossy(&output.stdout);
-        let regex = Regex::new(r"DURATION=(.*)\nFINGERPRINT=(.*)")?;
-        let captures: Vec<String> = regex
-            .captures(&output)
-            .ok_or_else(|| {
-                anyhow!(
-                    "Failed to match fpcalc output to expected regex: {}!",
-                    output
-                )
-            })?
-            .iter()
-            .skip(1)
-            .map(|c| {
-                Ok(String::from(
-                    c.ok_or_else(|| {
-                        anyhow!(
-                            "Failed to match fpcalc output to expected regex: {}!",
-                            output
-                        )
-                    })?
-                    .as_str(),
-                ))
-            })
-            .collect::<Result<Vec<String>>>()?;
+    /// Returns the fingerprint of the audio file at the given path, calculated with the
+    /// configured backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audio file.
+    pub async fn get_fingerprint(&self, path: &Path) -> Result<Fingerprint> {
+        match ConfigService::instance().get()?.fingerprint_backend {
+            FingerprintBackend::Native => self.get_native_fingerprint(path).await,
+            FingerprintBackend::Fpcalc => self.get_fpcalc_fingerprint(path).await,
+        }
+    }
+
+    /// Returns the fingerprint of the audio file at the given path, calculated in-process.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audio file.
+    pub async fn get_native_fingerprint(&self, path: &Path) -> Result<Fingerprint> {
+        let (duration, fingerprint) = self.get_native_raw_fingerprint(path).await?;
         Ok(Fingerprint {
-            duration: captures[0].clone(),
-            fingerprint: captures[1].clone(),
+            duration: duration.to_string(),
+            fingerprint: encode_fingerprint(&fingerprint),
+        })
+    }
+
+    /// Returns the fingerprint of the audio file at the given path, calculated by fpcalc.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audio file.
+    pub async fn get_fpcalc_fingerprint(&self, path: &Path) -> Result<Fingerprint> {
+        let output: FpcalcOutput<String> = self.run_fpcalc(path, false).await?;
+        Ok(Fingerprint {
+            duration: (output.duration as u64).to_string(),
+            fingerprint: output.fingerprint,
+        })
+    }
+
+    /// Returns the similarity of the native and fpcalc fingerprints of the audio file at the
+    /// given path, from 0 to 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audio file.
+    pub async fn compare_fingerprints(&self, path: &Path) -> Result<f64> {
+        let (_, native) = self.get_native_raw_fingerprint(path).await?;
+        let fpcalc: FpcalcOutput<Vec<u32>> = self.run_fpcalc(path, true).await?;
+        Ok(similarity(&native, &fpcalc.fingerprint))
+    }
+
+    /// Returns the duration in whole seconds and the raw fingerprint of the audio file at the
+    /// given path, decoding it on a blocking thread.
+    async fn get_native_raw_fingerprint(&self, path: &Path) -> Result<(u64, Vec<u32>)> {
+        let length = ConfigService::instance().get()?.fingerprint_length;
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || decode_raw_fingerprint(&path, length)).await?
+    }
+
+    /// Runs fpcalc on the audio file at the given path and returns its parsed JSON output.
+    async fn run_fpcalc<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        raw: bool,
+    ) -> Result<FpcalcOutput<T>> {
+        let config = ConfigService::instance().get()?;
+        let mut command = Command::new(&config.fpcalc_path);
+        command
+            .arg("-json")
+            .arg("-length")
+            .arg(config.fingerprint_length.to_string())
+            .kill_on_drop(true);
+        if raw {
+            command.arg("-raw");
+        }
+        let timeout = Duration::from_secs(config.fingerprint_timeout);
+        let output = tokio::time::timeout(timeout, command.arg(path).output())
+            .await
+            .map_err(|_| FingerprintError::TimedOut(timeout))?
+            .map_err(|e| match e.kind() {
+                ErrorKind::NotFound => anyhow!(FingerprintError::NotInstalled(config.fpcalc_path)),
+                _ => anyhow!("Failed to run fpcalc: {}!", e),
+            })?;
+        if !output.status.success() {
+            return Err(FingerprintError::DecodeFailed(format!(
+                "fpcalc failed on {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+            .into());
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| {
+            anyhow!(
+                "Failed to parse fpcalc output {}: {}!",
+                String::from_utf8_lossy(&output.stdout),
+                e
+            )
         })
     }
 
@@ -56,19 +152,138 @@ impl AcoustIdService {
         fingerprint: &Fingerprint,
     ) -> Result<Vec<AcoustIdResult>> {
         let mut data: HashMap<&str, String> = HashMap::new();
-        data.insert("client", "KS7Sc4UiGc".to_string());
+        data.insert("client", CLIENT_KEY.to_string());
         data.insert("meta", "recordingids".to_string());
         data.insert("duration", fingerprint.duration.clone());
         data.insert("fingerprint", fingerprint.fingerprint.clone());
+        let url = format!("{}/lookup", ConfigService::instance().get()?.acoustid_url);
+        let client = reqwest::Client::new();
+        let response: LookupResponse = client.post(url).form(&data).send().await?.json().await?;
+        Ok(response.results)
+    }
+
+    /// Submits the given fingerprints to AcoustID in batches with the configured user API key,
+    /// and returns the statuses of the submissions, indexed into the given fingerprints.
+    ///
+    /// # Arguments
+    ///
+    /// * `submissions` - The fingerprints to submit.
+    pub async fn submit(
+        &self,
+        submissions: &[AcoustIdSubmission],
+    ) -> Result<Vec<AcoustIdSubmissionStatus>> {
+        Self::submit_with(&ConfigService::instance().get()?, submissions).await
+    }
+
+    /// Submits the given fingerprints to the AcoustID API of the given config.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The config with the AcoustID API URL and user API key.
+    /// * `submissions` - The fingerprints to submit.
+    async fn submit_with(
+        config: &AppConfig,
+        submissions: &[AcoustIdSubmission],
+    ) -> Result<Vec<AcoustIdSubmissionStatus>> {
+        let api_key = config.acoustid_api_key.clone().ok_or(anyhow!(
+            "Set acoustid_api_key in config.json to submit fingerprints to AcoustID!"
+        ))?;
+        let url = format!("{}/submit", config.acoustid_url);
         let client = reqwest::Client::new();
-        let response: LookupResponse = client
-            .post("https://api.acoustid.org/v2/lookup")
-            .form(&data)
+        let mut statuses = vec![];
+        for (batch_index, batch) in submissions.chunks(SUBMIT_BATCH_SIZE).enumerate() {
+            let mut data: Vec<(String, String)> = vec![
+                (String::from("client"), CLIENT_KEY.to_string()),
+                (String::from("clientversion"), CLIENT_VERSION.to_string()),
+                (String::from("format"), String::from("json")),
+                (String::from("user"), api_key.clone()),
+            ];
+            for (index, submission) in batch.iter().enumerate() {
+                data.push((format!("duration.{}", index), submission.duration.clone()));
+                data.push((
+                    format!("fingerprint.{}", index),
+                    submission.fingerprint.clone(),
+                ));
+                data.push((format!("mbid.{}", index), submission.recording_id.clone()));
+                if let Some(bitrate) = submission.bitrate {
+                    data.push((format!("bitrate.{}", index), (bitrate / 1000).to_string()));
+                }
+                if let Some(file_format) = &submission.file_format {
+                    data.push((format!("fileformat.{}", index), file_format.clone()));
+                }
+            }
+            let response: SubmissionResponse =
+                client.post(&url).form(&data).send().await?.json().await?;
+            // NOTE: the statuses aren't necessarily in the order submitted, so their indexes into
+            // the batch are made indexes into all of the submissions
+            statuses.extend(response.into_submissions()?.into_iter().map(|mut status| {
+                status.index = status
+                    .index
+                    .map(|index| batch_index * SUBMIT_BATCH_SIZE + index);
+                status
+            }));
+        }
+        Ok(statuses)
+    }
+
+    /// Returns the current statuses of the submissions with the given IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the submissions.
+    pub async fn get_submission_statuses(
+        &self,
+        ids: &[u64],
+    ) -> Result<Vec<AcoustIdSubmissionStatus>> {
+        let url = format!(
+            "{}/submission_status",
+            ConfigService::instance().get()?.acoustid_url
+        );
+        let mut query = vec![
+            (String::from("client"), CLIENT_KEY.to_string()),
+            (String::from("clientversion"), CLIENT_VERSION.to_string()),
+            (String::from("format"), String::from("json")),
+        ];
+        query.extend(ids.iter().map(|id| (String::from("id"), id.to_string())));
+        let response: SubmissionResponse = reqwest::Client::new()
+            .get(url)
+            .query(&query)
             .send()
             .await?
             .json()
             .await?;
-        Ok(response.results)
+        response.into_submissions()
+    }
+
+    /// Polls the statuses of the given submissions until none are pending or the attempts run
+    /// out, and returns the last statuses.
+    ///
+    /// # Arguments
+    ///
+    /// * `statuses` - The statuses returned when the fingerprints were submitted.
+    pub async fn poll_submissions(
+        &self,
+        mut statuses: Vec<AcoustIdSubmissionStatus>,
+    ) -> Result<Vec<AcoustIdSubmissionStatus>> {
+        for _ in 0..POLL_ATTEMPTS {
+            let pending: Vec<u64> = statuses
+                .iter()
+                .filter(|status| status.is_pending())
+                .map(|status| status.id)
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+            for updated in self.get_submission_statuses(&pending).await? {
+                if let Some(status) = statuses.iter_mut().find(|status| status.id == updated.id) {
+                    // NOTE: the index is only given when submitting, so it's kept
+                    status.status = updated.status;
+                    status.result = updated.result;
+                }
+            }
+        }
+        Ok(statuses)
     }
 }
 
@@ -78,8 +293,232 @@ impl Default for AcoustIdService {
     }
 }
 
+/// Returns the duration in whole seconds and the raw fingerprint of the first seconds of the
+/// audio file at the given path, decoding it with symphonia.
+///
+/// # Arguments
+///
+/// * `path` - The path of the audio file.
+/// * `length` - The number of seconds of audio to fingerprint.
+fn decode_raw_fingerprint(path: &Path, length: u32) -> Result<(u64, Vec<u32>)> {
+    let source = std::fs::File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+        hint.with_extension(extension);
+    }
+    let format_options = FormatOptions {
+        enable_gapless: true,
+        ..Default::default()
+    };
+    let decode_failed =
+        |e: SymphoniaError| FingerprintError::DecodeFailed(format!("{}: {}", path.display(), e));
+    let mut reader = symphonia::default::get_probe()
+        .format(&hint, stream, &format_options, &MetadataOptions::default())
+        .map_err(decode_failed)?
+        .format;
+    let track = reader
+        .default_track()
+        .ok_or(FingerprintError::DecodeFailed(format!(
+            "No audio track found in {}",
+            path.display()
+        )))?;
+    let track_id = track.id;
+    let frame_count = track.codec_params.n_frames;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(decode_failed)?;
+    let mut chromaprint: Option<Chromaprint> = None;
+    let mut decoded_frame_count = 0;
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(decode_failed(e).into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // NOTE: a corrupt packet is skipped, as fpcalc does
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(decode_failed(e).into()),
+        };
+        let spec = *decoded.spec();
+        decoded_frame_count += decoded.frames() as u64;
+        let chromaprint =
+            chromaprint.get_or_insert_with(|| Chromaprint::new(spec.rate, spec.channels.count()));
+        let max_sample_count = (length * spec.rate) as usize;
+        if chromaprint.sample_count() < max_sample_count {
+            let mut samples = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+            samples.copy_interleaved_ref(decoded);
+            let remaining = (max_sample_count - chromaprint.sample_count()) * spec.channels.count();
+            let samples = samples.samples();
+            chromaprint.consume(&samples[..samples.len().min(remaining)]);
+        } else if frame_count.is_some() {
+            break;
+        }
+    }
+    let chromaprint = chromaprint.ok_or(FingerprintError::DecodeFailed(format!(
+        "No audio decoded from {}",
+        path.display()
+    )))?;
+    let duration = frame_count.unwrap_or(decoded_frame_count) / chromaprint.sample_rate as u64;
+    Ok((duration, chromaprint.fingerprint()))
+}
+
+/// Represents the JSON output of fpcalc, with the fingerprint either compressed or raw.
+#[derive(Deserialize)]
+struct FpcalcOutput<T> {
+    duration: f64,
+    fingerprint: T,
+}
+
+/// Represents an error returned by the AcoustID API.
+#[derive(Deserialize)]
+struct ApiError {
+    pub message: String,
+}
+
+/// Represents the response to a submission or a submission status request.
+#[derive(Deserialize)]
+struct SubmissionResponse {
+    pub status: String,
+    #[serde(default)]
+    pub submissions: Vec<AcoustIdSubmissionStatus>,
+    pub error: Option<ApiError>,
+}
+
+impl SubmissionResponse {
+    /// Returns the submissions of the response, or the error AcoustID returned.
+    fn into_submissions(self) -> Result<Vec<AcoustIdSubmissionStatus>> {
+        match (self.status.as_str(), self.error) {
+            ("ok", _) => Ok(self.submissions),
+            (_, Some(error)) => Err(anyhow!("AcoustID returned an error: {}!", error.message)),
+            (status, None) => Err(anyhow!("AcoustID returned status {}!", status)),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct LookupResponse {
     pub status: String,
     pub results: Vec<AcoustIdResult>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// The lowest similarity the native fingerprints may have to fpcalc's.
+    const MIN_SIMILARITY: f64 = 0.9;
+
+    /// Answers submissions like AcoustID, with the statuses in reverse order and the ID of
+    /// each submission being its recording ID.
+    async fn answer_submissions(listener: TcpListener) {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 4096];
+            let body = loop {
+                let count = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..count]);
+                let request = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(|length| length.parse::<usize>().unwrap())
+                        })
+                        .unwrap_or_default();
+                    if body.len() >= length {
+                        break body.to_string();
+                    }
+                }
+            };
+            let submissions = body
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .filter_map(|(key, value)| Some((key.strip_prefix("mbid.")?, value)))
+                .map(|(index, id)| {
+                    format!(r#"{{"index":"{}","id":{},"status":"pending"}}"#, index, id)
+                })
+                .rev()
+                .collect::<Vec<_>>();
+            let json = format!(
+                r#"{{"status":"ok","submissions":[{}]}}"#,
+                submissions.join(",")
+            );
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                 Connection: close\r\n\r\n{}",
+                json.len(),
+                json
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn submit_indexes_statuses_into_submissions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = AppConfig {
+            acoustid_api_key: Some(String::from("key")),
+            acoustid_url: format!("http://{}", listener.local_addr().unwrap()),
+            ..Default::default()
+        };
+        tokio::spawn(answer_submissions(listener));
+        let submissions = (0..SUBMIT_BATCH_SIZE + 10)
+            .map(|index| AcoustIdSubmission {
+                bitrate: None,
+                duration: String::from("60"),
+                file_format: None,
+                fingerprint: String::from("AQAA"),
+                recording_id: index.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let statuses = AcoustIdService::submit_with(&config, &submissions)
+            .await
+            .unwrap();
+
+        assert_eq!(statuses.len(), submissions.len());
+        assert_eq!(statuses[0].index, Some(SUBMIT_BATCH_SIZE - 1));
+        for status in statuses {
+            let submission = &submissions[status.index.unwrap()];
+            assert_eq!(submission.recording_id, status.id.to_string());
+        }
+    }
+
+    #[test]
+    #[ignore = "needs fpcalc's output recorded as ref/fpcalc/<name>.json, with \
+                `fpcalc -json -raw -length 120 ref/<name>.mp3`"]
+    fn native_fingerprints_match_recorded_fpcalc_output() {
+        let paths = std::fs::read_dir("ref")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "mp3"))
+            .collect::<Vec<_>>();
+        assert!(!paths.is_empty());
+        for path in paths {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            let recorded = std::fs::read_to_string(format!("ref/fpcalc/{}.json", name)).unwrap();
+            let fpcalc: FpcalcOutput<Vec<u32>> = serde_json::from_str(&recorded).unwrap();
+            let (duration, native) = decode_raw_fingerprint(&path, 120).unwrap();
+            assert_eq!(duration, fpcalc.duration as u64, "{}", name);
+            let similarity = similarity(&native, &fpcalc.fingerprint);
+            assert!(
+                similarity >= MIN_SIMILARITY,
+                "{} is only {:.2} similar to fpcalc",
+                name,
+                similarity
+            );
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/// Represents a fingerprint to contribute to AcoustID, linked to the recording it's of.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcoustIdSubmission {
+    /// The average bitrate of the audio in bit/s, if known.
+    pub bitrate: Option<u32>,
+
+    /// The duration of the audio in whole seconds.
+    pub duration: String,
+
+    /// The format of the file, e.g. "MP3", if known.
+    pub file_format: Option<String>,
+
+    /// The compressed fingerprint.
+    pub fingerprint: String,
+
+    /// The MusicBrainz ID of the recording.
+    pub recording_id: String,
+}
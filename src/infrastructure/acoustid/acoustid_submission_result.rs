@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct AcoustIdSubmissionResult {
+    /// The AcoustID the submitted fingerprint was imported as.
+    pub id: String,
+}
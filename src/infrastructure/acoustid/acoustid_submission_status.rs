@@ -0,0 +1,43 @@
+use super::AcoustIdSubmissionResult;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct AcoustIdSubmissionStatus {
+    /// The ID of the submission.
+    pub id: u64,
+
+    /// The index of the submitted fingerprint, which is only given when it's submitted.
+    #[serde(default, deserialize_with = "deserialize_index")]
+    pub index: Option<usize>,
+
+    /// The state of the submission: "pending" or "imported".
+    pub status: String,
+
+    /// The result of the submission, once it's imported.
+    #[serde(default)]
+    pub result: Option<AcoustIdSubmissionResult>,
+}
+
+impl AcoustIdSubmissionStatus {
+    /// Returns whether the submission is still waiting to be imported.
+    pub fn is_pending(&self) -> bool {
+        self.status == "pending"
+    }
+}
+
+/// Deserializes the index of a submission, which AcoustID gives as a string.
+fn deserialize_index<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Index {
+        Number(usize),
+        Text(String),
+    }
+    match Option::<Index>::deserialize(deserializer)? {
+        Some(Index::Number(index)) => Ok(Some(index)),
+        Some(Index::Text(index)) => index.parse().map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Represents the ways calculating a fingerprint can fail.
+#[derive(Debug)]
+pub enum FingerprintError {
+    /// The fpcalc binary wasn't found at the configured path.
+    NotInstalled(PathBuf),
+    /// The audio couldn't be decoded.
+    DecodeFailed(String),
+    /// Fingerprinting took longer than the configured timeout.
+    TimedOut(Duration),
+}
+
+impl Display for FingerprintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FingerprintError::NotInstalled(path) => write!(
+                f,
+                "fpcalc was not found at \"{}\"! Install Chromaprint, set fpcalc_path in the config or use the native fingerprint backend.",
+                path.display()
+            ),
+            FingerprintError::DecodeFailed(message) => {
+                write!(f, "Failed to decode audio: {}", message)
+            }
+            FingerprintError::TimedOut(timeout) => {
+                write!(f, "Fingerprinting timed out after {} seconds!", timeout.as_secs())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FingerprintError {}
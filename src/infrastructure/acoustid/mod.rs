@@ -1,9 +1,17 @@
 mod acoustid_recording;
 mod acoustid_result;
 mod acoustid_service;
+mod acoustid_submission;
+mod acoustid_submission_result;
+mod acoustid_submission_status;
 mod fingerprint;
+mod fingerprint_error;
 
 pub use acoustid_recording::*;
 pub use acoustid_result::*;
 pub use acoustid_service::*;
+pub use acoustid_submission::*;
+pub use acoustid_submission_result::*;
+pub use acoustid_submission_status::*;
 pub use fingerprint::*;
+pub use fingerprint_error::*;
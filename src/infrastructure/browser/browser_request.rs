@@ -0,0 +1,42 @@
+use reqwest::Url;
+
+/// Represents a request the browser sends from musicbrainz.org's tagger links.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BrowserRequest {
+    /// Asks for the release with the given MusicBrainz ID, or its URL, to be loaded.
+    OpenAlbum(String),
+}
+
+/// Represents why a browser request couldn't be handled, with the HTTP status to answer with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BrowserRequestError {
+    /// The request is missing a parameter its path needs.
+    BadRequest,
+    /// The request's path isn't one that's listened for.
+    NotFound,
+}
+
+impl BrowserRequest {
+    /// Returns the request for the given request target, the path and query of the URL the
+    /// browser asked for, or none for the root path, which only checks that sisko is listening.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The request target, such as "/openalbum?id=<mbid>".
+    pub fn parse(target: &str) -> Result<Option<BrowserRequest>, BrowserRequestError> {
+        let url = Url::parse(&format!("http://localhost{}", target))
+            .map_err(|_| BrowserRequestError::BadRequest)?;
+        let parameter = |name: &str| {
+            url.query_pairs()
+                .find(|(key, value)| key == name && !value.trim().is_empty())
+                .map(|(_, value)| value.trim().to_owned())
+        };
+        match url.path() {
+            "/" => Ok(None),
+            "/openalbum" => parameter("id")
+                .map(|id| Some(BrowserRequest::OpenAlbum(id)))
+                .ok_or(BrowserRequestError::BadRequest),
+            _ => Err(BrowserRequestError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+use super::BrowserRequest;
+use anyhow::Result;
+
+pub type BrowserRequestHandler = Box<dyn Fn(BrowserRequest) -> Result<()> + Send + Sync>;
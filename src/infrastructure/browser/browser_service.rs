@@ -0,0 +1,154 @@
+use super::{BrowserRequest, BrowserRequestError, BrowserRequestHandler};
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// The most bytes of a request's head that are read.
+const MAX_REQUEST_HEAD_SIZE: usize = 8192;
+
+/// Represents a service that listens on a localhost port for the requests musicbrainz.org's
+/// tagger links send, as Picard does.
+pub struct BrowserService {}
+
+impl BrowserService {
+    pub fn instance() -> &'static Self {
+        static INSTANCE: OnceLock<BrowserService> = OnceLock::new();
+        INSTANCE.get_or_init(Self::new)
+    }
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Starts listening on the given localhost port, handing each request to the given handler,
+    /// and returns the address listened on.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The port to listen on, or 0 for any free port.
+    /// * `handler` - The handler of the requests.
+    pub async fn listen(&self, port: u16, handler: BrowserRequestHandler) -> Result<SocketAddr> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+        let address = listener.local_addr()?;
+        let handler = Arc::new(handler);
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let handler = handler.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = Self::respond(stream, &handler).await {
+                                warn!("Error answering a browser request: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("Error accepting a browser connection: {}", e),
+                }
+            }
+        });
+        Ok(address)
+    }
+
+    /// Reads a request from the given connection, hands it to the handler and answers it.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The connection from the browser.
+    /// * `handler` - The handler of the requests.
+    async fn respond(mut stream: TcpStream, handler: &BrowserRequestHandler) -> Result<()> {
+        let mut head = vec![];
+        let mut buffer = [0; 1024];
+        while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+            let count = stream.read(&mut buffer).await?;
+            if count == 0 {
+                break;
+            }
+            head.extend_from_slice(&buffer[..count]);
+            if head.len() > MAX_REQUEST_HEAD_SIZE {
+                return Err(anyhow!("The request head is too large!"));
+            }
+        }
+        let head = String::from_utf8_lossy(&head);
+        let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+
+        let (status, body) = match (request_line.next(), request_line.next()) {
+            (Some("GET"), Some(target)) => {
+                info!("Browser request: {}", target);
+                match BrowserRequest::parse(target) {
+                    Ok(Some(request)) => match handler(request) {
+                        Ok(()) => ("200 OK", "sisko is loading the release."),
+                        Err(e) => {
+                            warn!("Error handling browser request {}: {}", target, e);
+                            (
+                                "500 Internal Server Error",
+                                "sisko couldn't load the release.",
+                            )
+                        }
+                    },
+                    Ok(None) => ("200 OK", "sisko is listening."),
+                    Err(BrowserRequestError::BadRequest) => {
+                        ("400 Bad Request", "The request is missing the release ID.")
+                    }
+                    Err(BrowserRequestError::NotFound) => ("404 Not Found", "Nothing to see here."),
+                }
+            }
+            (Some(_), Some(_)) => ("405 Method Not Allowed", "Only GET requests are answered."),
+            _ => ("400 Bad Request", "The request is malformed."),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+}
+
+impl Default for BrowserService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn browser_service_answers_requests() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let handled = requests.clone();
+        let address = BrowserService::new()
+            .listen(
+                0,
+                Box::new(move |request| {
+                    handled.lock().unwrap().push(request);
+                    Ok(())
+                }),
+            )
+            .await
+            .unwrap();
+        let get = |target: &str| reqwest::get(format!("http://{}{}", address, target));
+
+        let response = get("/openalbum?id=0a0b0c0d-1111-2222-3333-444455556666&t=1")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(get("/").await.unwrap().status(), 200);
+        assert_eq!(get("/openalbum").await.unwrap().status(), 400);
+        assert_eq!(get("/add").await.unwrap().status(), 404);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![BrowserRequest::OpenAlbum(String::from(
+                "0a0b0c0d-1111-2222-3333-444455556666"
+            ))]
+        );
+    }
+}
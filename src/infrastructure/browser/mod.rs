@@ -0,0 +1,7 @@
+mod browser_request;
+mod browser_request_handler;
+mod browser_service;
+
+pub use browser_request::*;
+pub use browser_request_handler::*;
+pub use browser_service::*;
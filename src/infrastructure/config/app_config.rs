@@ -0,0 +1,66 @@
+use super::{FingerprintBackend, ReleasePreferences, ReleaseWeights};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Represents the user's settings, persisted between runs.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AppConfig {
+    /// The user's AcoustID API key, needed to submit fingerprints.
+    pub acoustid_api_key: Option<String>,
+
+    /// The lowest AcoustID score, from 0 to 1, that a result may have to be a match candidate.
+    pub acoustid_min_score: f64,
+
+    /// The base URL of the AcoustID web service.
+    pub acoustid_url: String,
+
+    /// The localhost port that requests from musicbrainz.org's tagger links are listened for
+    /// on, or none to not listen.
+    pub browser_port: Option<u16>,
+
+    /// How audio fingerprints are calculated.
+    pub fingerprint_backend: FingerprintBackend,
+
+    /// The number of seconds of audio at the start of each file that are fingerprinted.
+    pub fingerprint_length: u32,
+
+    /// The number of seconds fpcalc may take to fingerprint a file before it's stopped.
+    pub fingerprint_timeout: u64,
+
+    /// The path of the fpcalc binary, or just its name to look for it on the path.
+    pub fpcalc_path: PathBuf,
+
+    /// The major version of ID3v2 that tags are written as, 3 or 4. ID3v2.3 separates repeated
+    /// values with a slash, since only ID3v2.4 can store them separately.
+    pub id3v2_version: u8,
+
+    /// The kinds of releases preferred when several match a file.
+    pub release_preferences: ReleasePreferences,
+
+    /// How much each comparison counts towards the score of a release.
+    pub release_weights: ReleaseWeights,
+
+    /// The lowest MusicBrainz search score, from 0 to 1, that a result may have to be a match
+    /// candidate.
+    pub search_min_score: f64,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            acoustid_api_key: None,
+            acoustid_min_score: 0.5,
+            acoustid_url: String::from("https://api.acoustid.org/v2"),
+            browser_port: Some(8000),
+            fingerprint_backend: FingerprintBackend::default(),
+            fingerprint_length: 120,
+            fingerprint_timeout: 60,
+            fpcalc_path: PathBuf::from("fpcalc"),
+            id3v2_version: 4,
+            release_preferences: ReleasePreferences::default(),
+            release_weights: ReleaseWeights::default(),
+            search_min_score: 0.5,
+        }
+    }
+}
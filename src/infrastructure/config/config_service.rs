@@ -0,0 +1,156 @@
+use super::AppConfig;
+use anyhow::{anyhow, Result};
+use log::warn;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// The path of the file the config is persisted to.
+const CONFIG_PATH: &str = "config.json";
+
+/// Represents a service for reading and persisting the user's settings.
+pub struct ConfigService {
+    /// The current config.
+    config: Mutex<AppConfig>,
+
+    /// The path of the file the config is persisted to.
+    path: PathBuf,
+}
+
+impl ConfigService {
+    pub fn instance() -> &'static Self {
+        static INSTANCE: OnceLock<ConfigService> = OnceLock::new();
+        INSTANCE.get_or_init(Self::new)
+    }
+
+    /// Returns a new config service with the persisted config, or the default config if there
+    /// isn't one.
+    pub fn new() -> Self {
+        Self::with_path(PathBuf::from(CONFIG_PATH))
+    }
+
+    /// Returns a new config service with the config persisted at the given path, or the
+    /// default config if there isn't one.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file the config is persisted to.
+    fn with_path(path: PathBuf) -> Self {
+        let config = match Self::load(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Using the default config: {}", e);
+                AppConfig::default()
+            }
+        };
+        Self {
+            config: Mutex::new(config),
+            path,
+        }
+    }
+
+    /// Returns a copy of the current config.
+    pub fn get(&self) -> Result<AppConfig> {
+        Ok(self
+            .config
+            .lock()
+            .map_err(|_| anyhow!("Error locking config mutex!"))?
+            .clone())
+    }
+
+    /// Replaces the current config and persists it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The new config.
+    pub fn save(&self, config: AppConfig) -> Result<()> {
+        fs::write(&self.path, serde_json::to_string_pretty(&config)?)?;
+        *self
+            .config
+            .lock()
+            .map_err(|_| anyhow!("Error locking config mutex!"))? = config;
+        Ok(())
+    }
+
+    /// Reads the config persisted at the given path.
+    fn load(path: &Path) -> Result<AppConfig> {
+        if !path.exists() {
+            return Ok(AppConfig::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+impl Default for ConfigService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::{ReleasePreferences, ReleaseWeights};
+    use std::env;
+
+    #[test]
+    fn save_persists_release_preferences_and_weights() {
+        let path = env::temp_dir().join(format!("sisko-config-{}.json", std::process::id()));
+        let config = AppConfig {
+            release_preferences: ReleasePreferences {
+                countries: vec![String::from("GB"), String::from("XE")],
+                formats: vec![String::from("CD")],
+                statuses: vec![String::from("Official")],
+                types: vec![String::from("Album"), String::from("EP")],
+            },
+            release_weights: ReleaseWeights {
+                preferred_country: 7.0,
+                title: 30.0,
+                ..ReleaseWeights::default()
+            },
+            ..AppConfig::default()
+        };
+
+        ConfigService::with_path(path.clone())
+            .save(config.clone())
+            .unwrap();
+        let loaded = ConfigService::with_path(path.clone()).get().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn load_defaults_missing_preferences_and_weights() {
+        let path =
+            env::temp_dir().join(format!("sisko-partial-config-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{"release_preferences": {"formats": ["Vinyl"]}, "release_weights": {"album": 1.5}}"#,
+        )
+        .unwrap();
+
+        let loaded = ConfigService::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(
+            loaded.release_preferences,
+            ReleasePreferences {
+                formats: vec![String::from("Vinyl")],
+                ..ReleasePreferences::default()
+            }
+        );
+        assert_eq!(
+            loaded.release_weights,
+            ReleaseWeights {
+                album: 1.5,
+                ..ReleaseWeights::default()
+            }
+        );
+        assert_eq!(
+            loaded.search_min_score,
+            AppConfig::default().search_min_score
+        );
+    }
+}
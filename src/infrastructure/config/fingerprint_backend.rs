@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the ways an audio fingerprint can be calculated.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FingerprintBackend {
+    /// Chromaprint computed in-process from audio decoded with symphonia.
+    Native,
+    /// Chromaprint's `fpcalc` binary, which must be installed and on the path.
+    #[default]
+    Fpcalc,
+}
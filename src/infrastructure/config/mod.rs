@@ -0,0 +1,11 @@
+mod app_config;
+mod config_service;
+mod fingerprint_backend;
+mod release_preferences;
+mod release_weights;
+
+pub use app_config::*;
+pub use config_service::*;
+pub use fingerprint_backend::*;
+pub use release_preferences::*;
+pub use release_weights::*;
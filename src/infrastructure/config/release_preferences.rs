@@ -0,0 +1,76 @@
+use super::ReleaseWeights;
+use crate::infrastructure::musicbrainz::Release;
+use serde::{Deserialize, Serialize};
+
+/// Represents the kinds of releases the user prefers when several match a file. Each list is
+/// ordered from most to least preferred, and an empty list has no preference.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ReleasePreferences {
+    /// The preferred release countries, as MusicBrainz country codes, e.g. "GB".
+    pub countries: Vec<String>,
+
+    /// The preferred medium formats, e.g. "CD".
+    pub formats: Vec<String>,
+
+    /// The preferred release statuses, e.g. "Official".
+    pub statuses: Vec<String>,
+
+    /// The preferred primary types of release groups, e.g. "Album".
+    pub types: Vec<String>,
+}
+
+impl ReleasePreferences {
+    /// Returns how well the given release fits the preferences as (similarity, weight) parts
+    /// of its score, one for each list that isn't empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `release` - The release to compare.
+    /// * `weights` - How much each preference counts towards the score of the release.
+    pub fn compare_to_release(
+        &self,
+        release: &Release,
+        weights: &ReleaseWeights,
+    ) -> Vec<(f64, f64)> {
+        let mut parts = vec![];
+        if !self.countries.is_empty() {
+            let similarity = Self::preference(&self.countries, &release.country);
+            parts.push((similarity, weights.preferred_country));
+        }
+        if !self.formats.is_empty() {
+            let similarity = release
+                .media
+                .iter()
+                .map(|media| Self::preference(&self.formats, &media.format))
+                .fold(0.0, f64::max);
+            parts.push((similarity, weights.preferred_format));
+        }
+        if !self.statuses.is_empty() {
+            let similarity = Self::preference(&self.statuses, &release.status);
+            parts.push((similarity, weights.preferred_status));
+        }
+        if !self.types.is_empty() {
+            let similarity = release
+                .release_group
+                .primary_type
+                .as_ref()
+                .map_or(0.0, |primary_type| {
+                    Self::preference(&self.types, primary_type)
+                });
+            parts.push((similarity, weights.preferred_type));
+        }
+        parts
+    }
+
+    /// Returns 1 for the first of the given preferences down to 1/n for the last, or 0 if the
+    /// given value isn't one of them.
+    fn preference(preferences: &[String], value: &str) -> f64 {
+        preferences
+            .iter()
+            .position(|preference| preference.eq_ignore_ascii_case(value))
+            .map_or(0.0, |index| {
+                (preferences.len() - index) as f64 / preferences.len() as f64
+            })
+    }
+}
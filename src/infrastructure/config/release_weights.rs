@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents how much each comparison counts towards the score of a release for a file.
+/// Only comparisons of fields that the file's tags have, and preferences that the user has
+/// set, are counted.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ReleaseWeights {
+    /// The weight of the AcoustID score of the fingerprint.
+    pub acoustid: f64,
+
+    /// The weight of the similarity of the album title.
+    pub album: f64,
+
+    /// The weight of the similarity of the track artist.
+    pub artist: f64,
+
+    /// The weight of whether the release country is the same.
+    pub country: f64,
+
+    /// The weight of whether the release date or year is the same.
+    pub date: f64,
+
+    /// The weight of whether the medium format is the same.
+    pub format: f64,
+
+    /// The weight of how close the track length is to the file's length.
+    pub length: f64,
+
+    /// The weight of how preferred the release country is.
+    pub preferred_country: f64,
+
+    /// The weight of how preferred the formats of the release's media are.
+    pub preferred_format: f64,
+
+    /// The weight of how preferred the release status is.
+    pub preferred_status: f64,
+
+    /// The weight of how preferred the primary type of the release group is.
+    pub preferred_type: f64,
+
+    /// The weight of the MusicBrainz search score of a release found by searching.
+    pub search: f64,
+
+    /// The weight of the similarity of the track title.
+    pub title: f64,
+
+    /// The weight of whether the medium has as many tracks as the file's total tracks.
+    pub total_tracks: f64,
+}
+
+impl Default for ReleaseWeights {
+    fn default() -> Self {
+        Self {
+            acoustid: 20.0,
+            album: 12.0,
+            artist: 6.0,
+            country: 2.0,
+            date: 4.0,
+            format: 2.0,
+            length: 10.0,
+            preferred_country: 2.0,
+            preferred_format: 2.0,
+            preferred_status: 10.0,
+            preferred_type: 10.0,
+            search: 20.0,
+            title: 22.0,
+            total_tracks: 5.0,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ui::models::{
-    AlbumView, AudioFileColumn, AudioFileView, FileColumn, FileView, TagFieldColumn, TagFieldView,
+    AlbumView, AudioFileColumn, ClusterView, FileColumn, FileView, TagFieldColumn, TagFieldView,
     ALBUM_FILE_TABLE, CLUSTER_FILE_TABLE, FILE_TABLE, METADATA_TABLE,
 };
 use anyhow::{anyhow, Result};
@@ -8,7 +8,7 @@ use cursive_table_view::TableView;
 
 pub trait CursiveExtensions {
     fn clone_album_view(&mut self, index: usize) -> Result<AlbumView>;
-    fn clone_audio_file_view(&mut self, index: usize) -> Result<AudioFileView>;
+    fn clone_cluster_view(&mut self, index: usize) -> Result<ClusterView>;
     fn clone_file_view(&mut self, index: usize) -> Result<FileView>;
     fn clone_tag_field_view(&mut self, index: usize) -> Result<TagFieldView>;
 }
@@ -32,10 +32,10 @@ impl CursiveExtensions for Cursive {
         .and_then(|r| r)
     }
 
-    fn clone_audio_file_view(&mut self, index: usize) -> Result<AudioFileView> {
+    fn clone_cluster_view(&mut self, index: usize) -> Result<ClusterView> {
         self.call_on_name(
             CLUSTER_FILE_TABLE,
-            |table_view: &mut TableView<AudioFileView, AudioFileColumn>| -> Result<AudioFileView> {
+            |table_view: &mut TableView<ClusterView, AudioFileColumn>| -> Result<ClusterView> {
                 let item = table_view.borrow_item(index).ok_or_else(|| {
                     anyhow!(
                         "Failed to find item at index {} in {}!",
@@ -21,7 +21,8 @@ const DDL: &str = r#"
         script TEXT,
         sort_artist TEXT NOT NULL,
         title TEXT NOT NULL,
-        total_discs INTEGER NOT NULL
+        total_discs INTEGER NOT NULL,
+        score REAL
     );
 
     CREATE INDEX idx_albums_artist_id
@@ -35,6 +36,7 @@ const DDL: &str = r#"
         id TEXT NOT NULL,
         artist TEXT NOT NULL,
         artist_id TEXT NOT NULL,
+        artists TEXT NOT NULL,
         disc_number INTEGER NOT NULL,
         disc_subtitle TEXT NOT NULL,
         isrc TEXT,
@@ -57,23 +59,48 @@ const DDL: &str = r#"
     CREATE INDEX tracks_recording_id
     ON tracks(recording_id);
 
+    CREATE TABLE clusters (
+        id TEXT PRIMARY KEY,
+        album TEXT NOT NULL,
+        artist TEXT NOT NULL
+    );
+
     CREATE TABLE audio_files (
         id TEXT PRIMARY KEY,
         acoust_id TEXT,
+        audio_checksum TEXT,
+        bitrate INTEGER,
         duration TEXT,
         fingerprint TEXT,
+        stream_duration REAL,
         recording_id TEXT,
         album_id TEXT,
         track_id TEXT,
-        FOREIGN KEY (album_id, track_id) REFERENCES tracks(album_id, id)
+        scan_status TEXT NOT NULL,
+        cluster_id TEXT,
+        similarity REAL,
+        sample_rate INTEGER,
+        channel_mode TEXT,
+        encoder TEXT,
+        encoder_delay INTEGER,
+        encoder_padding INTEGER,
+        fingerprint_recording_id TEXT,
+        FOREIGN KEY (album_id, track_id) REFERENCES tracks(album_id, id),
+        FOREIGN KEY (cluster_id) REFERENCES clusters(id)
     );
 
     CREATE INDEX idx_audio_files_acoust_id
     ON audio_files(acoust_id);
 
+    CREATE INDEX idx_audio_files_audio_checksum
+    ON audio_files(audio_checksum);
+
     CREATE INDEX idx_audio_files_recording_id
     ON audio_files(recording_id);
 
+    CREATE INDEX idx_audio_files_cluster_id
+    ON audio_files(cluster_id);
+
     CREATE TABLE metadata_fields (
         audio_file_id TEXT NOT NULL,
         field_type TEXT NOT NULL,
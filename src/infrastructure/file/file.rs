@@ -13,6 +13,8 @@ pub struct File {
     pub size: Option<u64>,
     /// The type of the file.
     pub file_type: Option<FileType>,
+    /// The type the file's extension suggests, if it doesn't match the file's content.
+    pub extension_file_type: Option<FileType>,
     /// The date the file was last modified.
     pub date_modified: Option<DateTime<Utc>>,
 }
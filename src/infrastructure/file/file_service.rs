@@ -3,6 +3,8 @@ use crate::ui::models::FileDialogType;
 use anyhow::{anyhow, Result};
 use chrono::DateTime;
 use itertools::Itertools;
+use log::warn;
+use sisko_lib::file_format::FileFormat;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -55,7 +57,23 @@ impl FileService {
     /// Loads the file with the given path into the service.
     fn load(&self, path: &Path) -> Result<()> {
         let metadata = path.metadata().ok();
-        let file_type = Some(FileType::from(path));
+        let extension_file_type = FileType::from(path);
+        // NOTE: the content decides the type, falling back on the extension if it isn't recognized
+        let file_type = match extension_file_type {
+            FileType::Directory => FileType::Directory,
+            _ => FileFormat::read_from_path(path)
+                .map(FileType::from)
+                .unwrap_or(extension_file_type),
+        };
+        let extension_file_type = (file_type != extension_file_type).then(|| {
+            warn!(
+                "File \"{}\" is named as a {} but its content is a {}!",
+                path.to_string_lossy(),
+                extension_file_type.as_str(),
+                file_type.as_str()
+            );
+            extension_file_type
+        });
         let file = File {
             absolute_path: path.to_path_buf(),
             name: path
@@ -70,7 +88,8 @@ impl FileService {
                 .into_string()
                 .unwrap_or(String::from("<invalid unicode>")),
             size: metadata.as_ref().map(|metadata| metadata.len()),
-            file_type,
+            file_type: Some(file_type),
+            extension_file_type,
             date_modified: match &metadata {
                 Some(metadata) => match metadata.modified() {
                     Ok(system_time) => match system_time.duration_since(SystemTime::UNIX_EPOCH) {
@@ -141,6 +160,7 @@ impl FileService {
                     name: "..".to_string(),
                     size: None,
                     file_type: Some(FileType::Directory),
+                    extension_file_type: None,
                     date_modified: None,
                 }),
             );
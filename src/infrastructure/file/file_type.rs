@@ -1,4 +1,5 @@
 use mime_guess::{self, mime};
+use sisko_lib::file_format::FileFormat;
 use std::path::Path;
 
 /// Represents the possible file types.
@@ -8,8 +9,12 @@ pub enum FileType {
     Directory,
     /// An Audio Interchange File Format file.
     AiffFile,
+    /// An Advanced Systems Format (Windows Media Audio) file.
+    AsfFile,
     /// A Free Lossless Audio Codec file.
     FlacFile,
+    /// A Matroska (MKA/WebM) file.
+    MatroskaFile,
     /// An MPEG Audio Layer III file.
     Mp3File,
     /// An MPEG-4 Audio file.
@@ -28,7 +33,9 @@ impl FileType {
         match *self {
             FileType::Directory => "Directory",
             FileType::AiffFile => "AIFF File",
+            FileType::AsfFile => "ASF File",
             FileType::FlacFile => "FLAC File",
+            FileType::MatroskaFile => "Matroska File",
             FileType::Mp3File => "MP3 File",
             FileType::Mp4aFile => "MP4A File",
             FileType::OggFile => "OGG File",
@@ -38,8 +45,29 @@ impl FileType {
     }
 }
 
+impl From<FileFormat> for FileType {
+    /// Returns the FileType of a file with the given content format.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format identified from the file's content.
+    fn from(format: FileFormat) -> Self {
+        match format {
+            FileFormat::Aac => FileType::UnsupportedFile,
+            FileFormat::Aiff => FileType::AiffFile,
+            FileFormat::Asf => FileType::AsfFile,
+            FileFormat::Flac => FileType::FlacFile,
+            FileFormat::Matroska => FileType::MatroskaFile,
+            FileFormat::Mp4 => FileType::Mp4aFile,
+            FileFormat::Mpeg => FileType::Mp3File,
+            FileFormat::Ogg => FileType::OggFile,
+            FileFormat::Wav => FileType::WavFile,
+        }
+    }
+}
+
 impl From<&Path> for FileType {
-    /// Returns the FileType of the given path.
+    /// Returns the FileType of the given path, guessed from its extension.
     ///
     /// # Arguments
     ///
@@ -59,8 +87,14 @@ impl From<&Path> for FileType {
                             "mpeg" => FileType::Mp3File,
                             "ogg" => FileType::OggFile,
                             "wav" => FileType::WavFile,
+                            "webm" => FileType::MatroskaFile,
+                            "x-matroska" => FileType::MatroskaFile,
+                            "x-ms-wma" => FileType::AsfFile,
                             _ => FileType::UnsupportedFile,
                         }
+                    } else if mime.type_() == mime::VIDEO && mime.subtype() == "webm" {
+                        // NOTE: audio-only WebM files usually have the video extension
+                        FileType::MatroskaFile
                     } else {
                         FileType::UnsupportedFile
                     }
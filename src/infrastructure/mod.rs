@@ -1,4 +1,6 @@
 pub mod acoustid;
+pub mod browser;
+pub mod config;
 mod cursive_extensions;
 pub mod database;
 mod entity;
@@ -160,69 +162,28 @@ pub fn linear_combination_of_weights(parts: &[(f64, f64)]) -> f64 {
 
     sum_of_products / total
 }
-/*
-pub fn get_score(release: &Release) -> f64 {
-    release.score / 100.0
-}
-
-// This function and those it calls are ripped from picard:
-// https://github.com/metabrainz/picard/blob/95c8b72be586379f86bb853ed3b251e99b23f687/picard/metadata.py#L8
-pub fn compare_to_release(
-    tag_fields: &[TagField],
-    release: &Release,
-    weights: &HashMap<TagFieldType, f64>,
-) -> f64 {
-    let parts = compare_to_release_parts(tag_fields, release, weights);
-    let sim = linear_combination_of_weights(&parts) * get_score(release);
-    sim
-}
-
-pub fn compare_to_release_parts(
-    tag_fields: &[TagField],
-    release: &Release,
-    weights: &HashMap<TagFieldType, f64>,
-) -> Vec<(f64, f64)> {
-    let parts = vec![];
-    let field_types: Vec<TagFieldType> = vec![]; // TODO
-    for field_type in field_types {
-        if tag_fields.iter().any(|f| f.tag_field_type() == field_type)
-            && weights.contains_key(field_type)
-        {
-            parts.push((
-                similarity2(
-                    tag_fields
-                        .iter()
-                        .find(|f| f.tag_field_type() == field_type)
-                        .unwrap(),
-                    release[field_type],
-                ),
-                weights[field_type],
-            ));
-        }
-    }
-    parts
-}
-
+// This function is ripped from picard:
+// https://github.com/metabrainz/picard/blob/95c8b72be586379f86bb853ed3b251e99b23f687/picard/util/__init__.py
+/// Returns the similarity of the given strings, from 0 to 1, comparing them word by word so that
+/// differences in word order and punctuation matter little.
 pub fn similarity2(a: &str, b: &str) -> f64 {
-    let SPLIT_WORDS_REGEX: Regex = Regex::new(r"\W+").unwrap();
-
     if a == b {
         return 1.0;
     }
 
     let a = a.to_lowercase();
     let b = b.to_lowercase();
-    let a: Vec<&str> = SPLIT_WORDS_REGEX
-        .split(&a)
+    let a: Vec<&str> = a
+        .split(|c: char| !c.is_alphanumeric())
         .filter(|s| !s.is_empty())
         .collect();
-    let b: Vec<&str> = SPLIT_WORDS_REGEX
-        .split(&b)
+    let b: Vec<&str> = b
+        .split(|c: char| !c.is_alphanumeric())
         .filter(|s| !s.is_empty())
         .collect();
     let (a, mut b) = if a.len() > b.len() { (b, a) } else { (a, b) };
 
-    if a.len() == 0 || b.len() == 0 {
+    if a.is_empty() || b.is_empty() {
         return 0.0;
     }
 
@@ -247,9 +208,11 @@ pub fn similarity2(a: &str, b: &str) -> f64 {
 
     // a.len() must be > 0 here, so no divide by zero
     score / (a.len() as f64 + (b.len() as f64 * 0.4))
-}*/
+}
 
-pub fn levenshtein_distance(a: &str, b: &str) -> f64 {
+/// Returns the similarity of the given words, from 0 to 1, by their edit distance relative to
+/// the longer one. Strings are compared by similarity2, which compares them word by word.
+fn levenshtein_distance(a: &str, b: &str) -> f64 {
     let (a, b) = if a.len() > b.len() { (b, a) } else { (a, b) };
 
     if a.is_empty() || b.is_empty() {
@@ -275,6 +238,90 @@ pub fn levenshtein_distance(a: &str, b: &str) -> f64 {
     1.0 - (current[a.len()] as f64 / max(a.len(), b.len()) as f64)
 }
 
+/// Returns the column assigned to each row of the given cost matrix so that no column is
+/// assigned twice and the total cost is as low as possible, using the Hungarian method. Rows
+/// are left unassigned only when there are more rows than columns.
+///
+/// # Arguments
+///
+/// * `costs` - The cost of assigning each row to each column, with as many columns per row.
+pub fn min_cost_assignment(costs: &[Vec<f64>]) -> Vec<Option<usize>> {
+    let rows = costs.len();
+    let columns = costs.first().map_or(0, |row| row.len());
+    if rows > columns {
+        // every row of the transposed matrix can be assigned
+        let transposed: Vec<Vec<f64>> = (0..columns)
+            .map(|column| costs.iter().map(|row| row[column]).collect())
+            .collect();
+        let mut assignment = vec![None; rows];
+        for (column, row) in min_cost_assignment(&transposed).into_iter().enumerate() {
+            if let Some(row) = row {
+                assignment[row] = Some(column);
+            }
+        }
+        return assignment;
+    }
+
+    // potentials of the rows and columns, and the row assigned to each column, all 1-based so
+    // that index 0 can stand for the row being added
+    let mut row_potentials = vec![0.0; rows + 1];
+    let mut column_potentials = vec![0.0; columns + 1];
+    let mut column_rows = vec![0_usize; columns + 1];
+    let mut previous_columns = vec![0_usize; columns + 1];
+    for row in 1..=rows {
+        column_rows[0] = row;
+        let mut column = 0;
+        let mut min_slacks = vec![f64::INFINITY; columns + 1];
+        let mut visited = vec![false; columns + 1];
+        loop {
+            visited[column] = true;
+            let visited_row = column_rows[column];
+            let mut delta = f64::INFINITY;
+            let mut next_column = 0;
+            for other in (1..=columns).filter(|&other| !visited[other]) {
+                let slack = costs[visited_row - 1][other - 1]
+                    - row_potentials[visited_row]
+                    - column_potentials[other];
+                if slack < min_slacks[other] {
+                    min_slacks[other] = slack;
+                    previous_columns[other] = column;
+                }
+                if min_slacks[other] < delta {
+                    delta = min_slacks[other];
+                    next_column = other;
+                }
+            }
+            for other in 0..=columns {
+                if visited[other] {
+                    row_potentials[column_rows[other]] += delta;
+                    column_potentials[other] -= delta;
+                } else {
+                    min_slacks[other] -= delta;
+                }
+            }
+            column = next_column;
+            if column_rows[column] == 0 {
+                break;
+            }
+        }
+
+        // flip the augmenting path back to the added row
+        while column != 0 {
+            let previous_column = previous_columns[column];
+            column_rows[column] = column_rows[previous_column];
+            column = previous_column;
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for (column, &row) in column_rows.iter().enumerate().skip(1) {
+        if row != 0 {
+            assignment[row - 1] = Some(column - 1);
+        }
+    }
+    assignment
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +336,26 @@ mod tests {
         );
         assert_eq!(levenshtein_distance("stink", "link"), 0.6);
     }
+
+    #[test]
+    fn min_cost_assignment_works() {
+        let costs = vec![
+            vec![4.0, 1.0, 3.0],
+            vec![2.0, 0.0, 5.0],
+            vec![3.0, 2.0, 2.0],
+        ];
+        assert_eq!(min_cost_assignment(&costs), vec![Some(1), Some(0), Some(2)]);
+        let costs = vec![vec![0.9, 0.1], vec![0.2, 0.8], vec![1.0, 1.0]];
+        assert_eq!(min_cost_assignment(&costs), vec![Some(1), Some(0), None]);
+        assert_eq!(min_cost_assignment(&[]), vec![]);
+    }
+
+    #[test]
+    fn similarity2_works() {
+        assert_eq!(similarity2("The Beatles", "The Beatles"), 1.0);
+        assert_eq!(similarity2("Beatles, The", "the beatles"), 1.0);
+        assert_eq!(similarity2("", "Abbey Road"), 0.0);
+        assert!(similarity2("Abbey Road", "Abbey Road (Remastered)") > 0.7);
+        assert!(similarity2("Abbey Road", "Let It Be") < 0.3);
+    }
 }
@@ -7,3 +7,78 @@ pub struct ArtistCredit {
     pub joinphrase: String,
     pub artist: Artist,
 }
+
+/// Returns the artists as they're credited, joined by their join phrases, e.g. "A feat. B".
+///
+/// # Arguments
+///
+/// * `credits` - The credits of the artists.
+pub fn credited_name(credits: &[ArtistCredit]) -> String {
+    credits
+        .iter()
+        .map(|credit| format!("{}{}", credit.name, credit.joinphrase))
+        .collect()
+}
+
+/// Returns the sort names of the credited artists, joined by their join phrases, e.g.
+/// "A, The feat. B".
+///
+/// # Arguments
+///
+/// * `credits` - The credits of the artists.
+pub fn credited_sort_name(credits: &[ArtistCredit]) -> String {
+    credits
+        .iter()
+        .map(|credit| format!("{}{}", credit.artist.sort_name, credit.joinphrase))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn credits() -> Vec<ArtistCredit> {
+        serde_json::from_value(json!([
+            {
+                "name": "The Artist",
+                "joinphrase": " feat. ",
+                "artist": {
+                    "type-id": null,
+                    "sort-name": "Artist, The",
+                    "name": "The Artist",
+                    "id": "a",
+                    "disambiguation": ""
+                }
+            },
+            {
+                "name": "Guest",
+                "joinphrase": "",
+                "artist": {
+                    "type-id": null,
+                    "sort-name": "Guest",
+                    "name": "Guest",
+                    "id": "b",
+                    "disambiguation": ""
+                }
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn credited_name_joins_credits_with_their_join_phrases() {
+        assert_eq!(credited_name(&credits()), "The Artist feat. Guest");
+    }
+
+    #[test]
+    fn credited_sort_name_joins_sort_names_with_the_join_phrases() {
+        assert_eq!(credited_sort_name(&credits()), "Artist, The feat. Guest");
+    }
+
+    #[test]
+    fn credited_name_of_a_single_credit_is_its_name() {
+        assert_eq!(credited_name(&credits()[1..]), "Guest");
+        assert_eq!(credited_name(&[]), "");
+    }
+}
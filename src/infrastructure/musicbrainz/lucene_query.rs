@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Represents a query for the MusicBrainz search API, in Lucene syntax. Entities matching any of
+/// its terms are found, and the more terms they match the higher they score.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LuceneQuery {
+    terms: Vec<String>,
+}
+
+impl LuceneQuery {
+    /// Returns a new query without any terms.
+    pub fn new() -> Self {
+        Self { terms: vec![] }
+    }
+
+    /// Adds a term that the given field must contain the given phrase, unless the phrase is
+    /// empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field to search.
+    /// * `phrase` - The phrase to search for.
+    pub fn phrase(mut self, field: &str, phrase: &str) -> Self {
+        let phrase = phrase.trim();
+        if !phrase.is_empty() {
+            let phrase = phrase.replace('\\', "\\\\").replace('"', "\\\"");
+            self.terms.push(format!("{}:\"{}\"", field, phrase));
+        }
+        self
+    }
+
+    /// Adds a term that the given field must equal the given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field to search.
+    /// * `value` - The value to search for.
+    pub fn value(mut self, field: &str, value: impl Display) -> Self {
+        self.terms.push(format!("{}:{}", field, value));
+        self
+    }
+
+    /// Returns whether the query doesn't have any terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl Display for LuceneQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.terms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phrase_escapes_quotes_and_backslashes() {
+        let query = LuceneQuery::new().phrase("release", r#"12" \ Vinyl"#);
+
+        assert_eq!(query.to_string(), r#"release:"12\" \\ Vinyl""#);
+    }
+
+    #[test]
+    fn phrase_quotes_other_reserved_characters() {
+        let query = LuceneQuery::new().phrase("artist", "AC/DC: (Live) && [Remix] +-!^~*?{}");
+
+        assert_eq!(
+            query.to_string(),
+            r#"artist:"AC/DC: (Live) && [Remix] +-!^~*?{}""#
+        );
+    }
+
+    #[test]
+    fn phrase_skips_empty_phrases() {
+        let query = LuceneQuery::new()
+            .phrase("release", "")
+            .phrase("artist", "  ");
+
+        assert!(query.is_empty());
+        assert_eq!(query.to_string(), "");
+    }
+
+    #[test]
+    fn to_string_joins_terms_with_spaces() {
+        let query = LuceneQuery::new()
+            .phrase("release", " Abbey Road ")
+            .value("tracks", 17);
+
+        assert!(!query.is_empty());
+        assert_eq!(query.to_string(), r#"release:"Abbey Road" tracks:17"#);
+    }
+}
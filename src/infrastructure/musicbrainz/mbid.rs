@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+/// Returns the MusicBrainz ID of the release given by its ID or its musicbrainz.org URL, such
+/// as "https://musicbrainz.org/release/<mbid>/discids".
+///
+/// # Arguments
+///
+/// * `input` - The release ID or URL.
+pub fn parse_release_id(input: &str) -> Result<String> {
+    let regex = Regex::new(
+        r"(?i)^(?:(?:https?://)?(?:[a-z]+\.)?musicbrainz\.org/release/)?(?<mbid>[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})(?:[/?#].*)?$",
+    )?;
+    regex
+        .captures(input.trim())
+        .map(|captures| captures["mbid"].to_lowercase())
+        .ok_or_else(|| anyhow!("{} isn't a MusicBrainz release ID or URL!", input.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBID: &str = "0a0b0c0d-1111-2222-3333-444455556666";
+
+    #[test]
+    fn parse_release_id_takes_an_mbid() {
+        assert_eq!(parse_release_id(MBID).unwrap(), MBID);
+        assert_eq!(
+            parse_release_id(&format!(" {} ", MBID.to_uppercase())).unwrap(),
+            MBID
+        );
+    }
+
+    #[test]
+    fn parse_release_id_takes_a_release_url() {
+        for url in [
+            format!("https://musicbrainz.org/release/{}", MBID),
+            format!("http://beta.musicbrainz.org/release/{}/discids", MBID),
+            format!("musicbrainz.org/release/{}?tab=tracks", MBID),
+            format!("https://musicbrainz.org/release/{}/", MBID),
+            format!("https://musicbrainz.org/release/{}#tracklist", MBID),
+            format!(" HTTPS://MusicBrainz.org/release/{} ", MBID.to_uppercase()),
+        ] {
+            assert_eq!(parse_release_id(&url).unwrap(), MBID, "{}", url);
+        }
+    }
+
+    #[test]
+    fn parse_release_id_rejects_invalid_input() {
+        for input in [
+            String::new(),
+            String::from("not an id"),
+            MBID[1..].to_string(),
+            format!("https://musicbrainz.org/artist/{}", MBID),
+            format!("https://example.com/release/{}", MBID),
+            format!("https://musicbrainz.org/release-group/{}", MBID),
+            format!("https://musicbrainz.org/release/{}x", MBID),
+            format!("{}0", MBID),
+            format!("release {}", MBID),
+        ] {
+            assert!(parse_release_id(&input).is_err(), "{}", input);
+        }
+    }
+}
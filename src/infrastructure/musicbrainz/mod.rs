@@ -4,14 +4,18 @@ mod cover_art_archive;
 mod entity;
 mod label;
 mod label_info;
+mod lucene_query;
+mod mbid;
 mod media;
 mod media_track;
 mod musicbrainz_service;
 mod recording;
+mod recording_search_result;
 mod release;
 mod release_event;
 mod release_event_area;
 mod release_group;
+mod release_search_result;
 mod text_representation;
 
 pub use artist::*;
@@ -20,12 +24,16 @@ pub use cover_art_archive::*;
 pub use entity::*;
 pub use label::*;
 pub use label_info::*;
+pub use lucene_query::*;
+pub use mbid::*;
 pub use media::*;
 pub use media_track::*;
 pub use musicbrainz_service::*;
 pub use recording::*;
+pub use recording_search_result::*;
 pub use release::*;
 pub use release_event::*;
 pub use release_event_area::*;
 pub use release_group::*;
+pub use release_search_result::*;
 pub use text_representation::*;
@@ -1,13 +1,24 @@
-use super::Release;
+use super::{LuceneQuery, RecordingSearchResult, Release, ReleaseSearchResult};
 use anyhow::Result;
 use reqwest::header::USER_AGENT;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use sisko_lib::disc_toc::DiscToc;
 use std::sync::OnceLock;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::time::{sleep, Duration, Instant};
 
 static ONE_SEC: Duration = Duration::from_secs(1);
 
+/// The base URL of the MusicBrainz web service.
+const BASE_URL: &str = "https://musicbrainz.org/ws/2";
+
+/// The related entities included with looked up releases.
+const RELEASE_INCLUDES: &str = "recordings+release-groups+media+isrcs+artist-credits+labels";
+
+/// The most entities MusicBrainz returns from a single browse request.
+const BROWSE_LIMIT: &str = "100";
+
 pub struct MusicBrainzService {
     last: AsyncMutex<Instant>,
 }
@@ -24,10 +35,105 @@ impl MusicBrainzService {
         }
     }
 
-    pub async fn lookup_releases_for_recording(
+    /// Returns the releases with a disc of the given table of contents. When no disc has its
+    /// disc ID, MusicBrainz falls back to the releases with discs of a similar table of contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `toc` - The table of contents of the disc.
+    pub async fn lookup_disc(&self, toc: &DiscToc) -> Result<DiscLookup> {
+        self.get(
+            &format!("{}/discid/{}", BASE_URL, toc.disc_id()),
+            &[
+                ("toc", &toc.toc()),
+                ("cdstubs", "no"),
+                ("inc", RELEASE_INCLUDES),
+            ],
+        )
+        .await
+    }
+
+    /// Returns the release with the given MusicBrainz ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `release_id` - The MusicBrainz ID of the release.
+    pub async fn lookup_release(&self, release_id: &str) -> Result<Release> {
+        self.get(
+            &format!("{}/release/{}", BASE_URL, release_id),
+            &[("inc", RELEASE_INCLUDES)],
+        )
+        .await
+    }
+
+    pub async fn lookup_releases_for_recording(&self, recording_id: &str) -> Result<ReleaseLookup> {
+        self.get(
+            &format!("{}/release", BASE_URL),
+            &[("recording", recording_id), ("inc", RELEASE_INCLUDES)],
+        )
+        .await
+    }
+
+    /// Returns the releases of the given release group, up to the most MusicBrainz browses at
+    /// once.
+    ///
+    /// # Arguments
+    ///
+    /// * `release_group_id` - The MusicBrainz ID of the release group.
+    pub async fn lookup_releases_for_release_group(
         &self,
-        recording_id: &String,
+        release_group_id: &str,
     ) -> Result<ReleaseLookup> {
+        self.get(
+            &format!("{}/release", BASE_URL),
+            &[
+                ("release-group", release_group_id),
+                ("inc", RELEASE_INCLUDES),
+                ("limit", BROWSE_LIMIT),
+            ],
+        )
+        .await
+    }
+
+    /// Returns the recordings that best match the given query, best first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search with.
+    /// * `limit` - The most recordings to return.
+    pub async fn search_recordings(
+        &self,
+        query: &LuceneQuery,
+        limit: usize,
+    ) -> Result<RecordingSearch> {
+        self.get(
+            &format!("{}/recording", BASE_URL),
+            &[("query", &query.to_string()), ("limit", &limit.to_string())],
+        )
+        .await
+    }
+
+    /// Returns the releases that best match the given query, best first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search with.
+    /// * `limit` - The most releases to return.
+    pub async fn search_releases(
+        &self,
+        query: &LuceneQuery,
+        limit: usize,
+    ) -> Result<ReleaseSearch> {
+        self.get(
+            &format!("{}/release", BASE_URL),
+            &[("query", &query.to_string()), ("limit", &limit.to_string())],
+        )
+        .await
+    }
+
+    /// Requests the given URL as JSON, waiting first so that no more than one request is made
+    /// each second, as MusicBrainz asks.
+    async fn get<T: DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T> {
         let mut last = self.last.lock().await;
         let elapsed = Instant::now() - *last;
         if elapsed < ONE_SEC {
@@ -36,13 +142,13 @@ impl MusicBrainzService {
 
         let client = reqwest::Client::new();
         let response = client
-            .get(format!(
-                "https://musicbrainz.org/ws/2/release?recording={}&fmt=json&inc=recordings+release-groups+media+isrcs+artist-credits+labels",
-                recording_id
-            ))
+            .get(url)
+            .query(&[("fmt", "json")])
+            .query(query)
             .header(USER_AGENT, "sisko/0.1 (curtis.hollibaugh@protonmail.ch)")
             .send()
-            .await?;
+            .await?
+            .error_for_status()?;
 
         *last = Instant::now();
 
@@ -56,6 +162,13 @@ impl Default for MusicBrainzService {
     }
 }
 
+#[derive(Deserialize)]
+pub struct DiscLookup {
+    /// The disc ID, given only when a disc has it rather than a similar table of contents.
+    pub id: Option<String>,
+    pub releases: Vec<Release>,
+}
+
 #[derive(Deserialize)]
 pub struct ReleaseLookup {
     #[serde(rename = "release-count")]
@@ -64,3 +177,17 @@ pub struct ReleaseLookup {
     pub release_offset: i32,
     pub releases: Vec<Release>,
 }
+
+#[derive(Deserialize)]
+pub struct RecordingSearch {
+    pub count: i32,
+    pub offset: i32,
+    pub recordings: Vec<RecordingSearchResult>,
+}
+
+#[derive(Deserialize)]
+pub struct ReleaseSearch {
+    pub count: i32,
+    pub offset: i32,
+    pub releases: Vec<ReleaseSearchResult>,
+}
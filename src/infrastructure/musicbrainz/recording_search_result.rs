@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// Represents a recording found by a search, with only the fields that searches return.
+#[derive(Deserialize)]
+pub struct RecordingSearchResult {
+    pub id: String,
+    /// How well the recording matched the query, from 0 to 100.
+    pub score: i32,
+    pub title: String,
+    pub length: Option<u64>,
+}
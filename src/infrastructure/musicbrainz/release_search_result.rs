@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+/// Represents a release found by a search, with only the fields that searches return.
+#[derive(Deserialize)]
+pub struct ReleaseSearchResult {
+    pub id: String,
+    /// How well the release matched the query, from 0 to 100.
+    pub score: i32,
+    pub title: String,
+}
@@ -5,12 +5,15 @@ pub mod ui;
 use crate::domain::events::DomainEvent;
 use crate::domain::repos::{AudioFileRepository, TagRepository, TrackRepository};
 use crate::domain::services::{LogHistory, MediatorService, SiskoService};
+use crate::infrastructure::acoustid::AcoustIdService;
+use crate::infrastructure::browser::{BrowserRequest, BrowserService};
+use crate::infrastructure::config::ConfigService;
 use crate::infrastructure::spawn;
 use crate::ui::events::UiEvent;
-use crate::ui::models::{AlbumViewId, TagFieldViewId};
+use crate::ui::models::{AlbumViewId, ClusterViewId, TagFieldViewId};
 use crate::ui::services::{CursiveWrapper, Ui, UiEventService};
 use anyhow::{anyhow, Result};
-use clap::Command;
+use clap::{value_parser, Arg, Command};
 use log::{error, info, LevelFilter};
 use log4rs::append::file::FileAppender;
 use log4rs::append::Append;
@@ -23,6 +26,7 @@ use sisko_lib::id3v2_frame_fields::ID3v2FrameFields;
 use sisko_lib::id3v2_tag::ID3v2Tag;
 use std::fs::File;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 /// This is the entrypoint of the program.
@@ -31,7 +35,26 @@ async fn main() -> Result<()> {
     config_logger()?;
     let matches = cli().get_matches();
     match matches.subcommand() {
-        None => run_gui().await?,
+        None => run_gui(vec![]).await?,
+        Some(("load-release", matches)) => {
+            run_gui(
+                matches
+                    .get_many::<String>("releases")
+                    .unwrap_or_default()
+                    .map(|release| UiEvent::LoadRelease(release.clone()))
+                    .collect(),
+            )
+            .await?
+        }
+        Some(("fingerprint", matches)) => {
+            run_fingerprint(
+                matches
+                    .get_many::<PathBuf>("paths")
+                    .unwrap_or_default()
+                    .collect(),
+            )
+            .await
+        }
         _ => run_test(),
     }
     Ok(())
@@ -116,10 +139,25 @@ pub fn cli() -> Command {
     Command::new("sisko")
         .subcommand_required(false)
         .subcommand(Command::new("test"))
+        .subcommand(
+            Command::new("fingerprint")
+                .about("Compares native and fpcalc fingerprints of audio files")
+                .arg(
+                    Arg::new("paths")
+                        .required(true)
+                        .num_args(1..)
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("load-release")
+                .about("Opens the GUI with releases loaded by their MusicBrainz IDs or URLs")
+                .arg(Arg::new("releases").required(true).num_args(1..)),
+        )
 }
 
-/// Runs the program's cursive UI.
-pub async fn run_gui() -> Result<()> {
+/// Runs the program's cursive UI, handling the given events once it's set up.
+pub async fn run_gui(events: Vec<UiEvent>) -> Result<()> {
     // create ui
     let cursive = CursiveWrapper::new();
 
@@ -127,18 +165,100 @@ pub async fn run_gui() -> Result<()> {
     UiEventService::instance().subscribe(Box::new(move |event| {
         let result = match event {
             UiEvent::OpenLogs => SiskoService::instance().open_logs(),
+            UiEvent::ClusterFiles => {
+                spawn(async move { SiskoService::instance().cluster_files().await });
+                Ok(())
+            }
             UiEvent::FileSelected(file) => {
                 let file = file.clone();
                 spawn(async move { SiskoService::instance().add_file(file).await });
                 Ok(())
             }
+            UiEvent::FindDuplicates => {
+                spawn(async move { SiskoService::instance().find_duplicates().await });
+                Ok(())
+            }
             UiEvent::FolderSelected(folder) => {
                 let folder = folder.clone();
                 spawn(async move { SiskoService::instance().add_folder(folder).await });
                 Ok(())
             }
+            UiEvent::LoadRelease(input) => {
+                let input = input.clone();
+                spawn(async move { SiskoService::instance().load_release_by_id(&input).await });
+                Ok(())
+            }
+            UiEvent::LookupAudioFile(audio_file_id) => {
+                let audio_file_id = audio_file_id.clone();
+                spawn(async move {
+                    let audio_file = AudioFileRepository::instance().get(&audio_file_id).await?;
+                    SiskoService::instance()
+                        .lookup_audio_file(&audio_file)
+                        .await
+                });
+                Ok(())
+            }
+            UiEvent::LookupAudioFileDisc(audio_file_id) => {
+                let audio_file_id = audio_file_id.clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .lookup_audio_file_disc(&audio_file_id)
+                        .await
+                });
+                Ok(())
+            }
+            UiEvent::LookupCluster(cluster_id) => {
+                let cluster_id = cluster_id.clone();
+                spawn(async move { SiskoService::instance().lookup_cluster(&cluster_id).await });
+                Ok(())
+            }
+            UiEvent::LookupClusterDisc(cluster_id) => {
+                let cluster_id = cluster_id.clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .lookup_cluster_disc(&cluster_id)
+                        .await
+                });
+                Ok(())
+            }
+            UiEvent::MatchLookupResult(audio_file_ids, result) => {
+                let audio_file_ids = audio_file_ids.clone();
+                let result = result.as_ref().clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .match_lookup_result(&audio_file_ids, &result)
+                        .await
+                });
+                Ok(())
+            }
+            UiEvent::MoveAudioFile(audio_file_id, track_id) => {
+                let audio_file_id = audio_file_id.clone();
+                let track_id = track_id.clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .move_audio_file(&audio_file_id, &track_id)
+                        .await
+                });
+                Ok(())
+            }
             UiEvent::OpenAddFile => Ui::instance().menu.open_file_dialog(),
             UiEvent::OpenAddFolder => Ui::instance().menu.open_directory_dialog(),
+            UiEvent::OpenLoadRelease => Ui::instance().menu.open_load_release_dialog(),
+            UiEvent::OpenOtherVersions(album_id) => {
+                let album_id = album_id.clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .open_other_versions(&album_id)
+                        .await
+                });
+                Ok(())
+            }
+            UiEvent::OpenReleasePreferences => SiskoService::instance().open_release_preferences(),
+            UiEvent::OpenTrackPicker(audio_file_id) => {
+                Ui::instance().album_table.open_track_picker(audio_file_id)
+            }
+            UiEvent::ReleasePreferencesSaved(preferences, weights) => SiskoService::instance()
+                .save_release_preferences(preferences.clone(), weights.clone()),
             UiEvent::SaveAudioFile(audio_file) => {
                 let audio_file = audio_file.clone();
                 spawn(async move { SiskoService::instance().save_audio_file(&audio_file).await });
@@ -180,11 +300,32 @@ pub async fn run_gui() -> Result<()> {
                 });
                 Ok(())
             }
-            UiEvent::SelectClusterFile(audio_file_view) => {
-                let audio_file_view = audio_file_view.clone();
+            UiEvent::ScanCluster(cluster_id) => {
+                let cluster_id = cluster_id.clone();
+                spawn(async move { SiskoService::instance().scan_cluster(&cluster_id).await });
+                Ok(())
+            }
+            UiEvent::SelectClusterView(cluster_view) => {
+                if let ClusterViewId::AudioFile(_, audio_file_id) = &cluster_view.id {
+                    let audio_file_id = audio_file_id.clone();
+                    spawn(async move {
+                        SiskoService::instance()
+                            .select_audio_file(&audio_file_id)
+                            .await
+                    });
+                }
+                Ok(())
+            }
+            UiEvent::SubmitAcoustIds => {
+                spawn(async move { SiskoService::instance().submit_acoustids().await });
+                Ok(())
+            }
+            UiEvent::SwitchRelease(album_id, new_album_id) => {
+                let album_id = album_id.clone();
+                let new_album_id = new_album_id.clone();
                 spawn(async move {
                     SiskoService::instance()
-                        .select_audio_file(&audio_file_view.id)
+                        .switch_release(&album_id, &new_album_id)
                         .await
                 });
                 Ok(())
@@ -216,36 +357,38 @@ pub async fn run_gui() -> Result<()> {
                     if let Err(e) = {
                         Ui::instance()
                             .album_table
-                            .open_album_view_dialog(&audio_file, &album_view.title)
+                            .open_album_view_dialog(&album_view, &audio_file)
                     } {
                         error!("{}", e);
                     }
                 });
                 Ok(())
             }
-            UiEvent::SubmitClusterFile(audio_file_view) => {
-                let audio_file_view = audio_file_view.clone();
-                tokio::spawn(async move {
-                    match AudioFileRepository::instance()
-                        .get(&audio_file_view.id)
-                        .await
-                    {
-                        Ok(audio_file) => {
-                            let title = audio_file_view.title;
-                            if let Err(e) = Ui::instance()
-                                .cluster_table
-                                .open_audio_file_dialog(&audio_file, &title)
-                            {
+            UiEvent::SubmitClusterView(cluster_view) => match &cluster_view.id {
+                ClusterViewId::Cluster(_) => Ui::instance()
+                    .cluster_table
+                    .open_cluster_dialog(cluster_view),
+                ClusterViewId::AudioFile(_, audio_file_id) => {
+                    let audio_file_id = audio_file_id.clone();
+                    let title = cluster_view.title.clone();
+                    tokio::spawn(async move {
+                        match AudioFileRepository::instance().get(&audio_file_id).await {
+                            Ok(audio_file) => {
+                                if let Err(e) = Ui::instance()
+                                    .cluster_table
+                                    .open_audio_file_dialog(&audio_file, &title)
+                                {
+                                    error!("{}", e);
+                                }
+                            }
+                            Err(e) => {
                                 error!("{}", e);
                             }
                         }
-                        Err(e) => {
-                            error!("{}", e);
-                        }
-                    }
-                });
-                Ok(())
-            }
+                    });
+                    Ok(())
+                }
+            },
             UiEvent::SubmitMetadataRow(tag_field_view) => {
                 let tag_field_view = tag_field_view.clone();
                 spawn(async move {
@@ -262,6 +405,26 @@ pub async fn run_gui() -> Result<()> {
                 });
                 Ok(())
             }
+            UiEvent::ToggleCluster(cluster_id) => {
+                Ui::instance().cluster_table.toggle_cluster(cluster_id)
+            }
+            UiEvent::ToggleIncompleteAlbums => {
+                Ui::instance().album_table.toggle_incomplete_albums()
+            }
+            UiEvent::UnmatchAlbum(album_id) => {
+                let album_id = album_id.clone();
+                spawn(async move { SiskoService::instance().unmatch_album(&album_id).await });
+                Ok(())
+            }
+            UiEvent::UnmatchAudioFile(audio_file_id) => {
+                let audio_file_id = audio_file_id.clone();
+                spawn(async move {
+                    SiskoService::instance()
+                        .unmatch_audio_file(&audio_file_id)
+                        .await
+                });
+                Ok(())
+            }
         };
         if let Err(error) = result {
             error!("Error processing event {event}: {error}!");
@@ -286,10 +449,56 @@ pub async fn run_gui() -> Result<()> {
         Ok(())
     }));
 
+    // listen for releases sent from the browser
+    if let Some(port) = ConfigService::instance().get()?.browser_port {
+        let listening = BrowserService::instance()
+            .listen(
+                port,
+                Box::new(|request| match request {
+                    BrowserRequest::OpenAlbum(release_id) => {
+                        UiEventService::instance().send(UiEvent::LoadRelease(release_id))
+                    }
+                }),
+            )
+            .await;
+        match listening {
+            Ok(address) => info!("Listening for browser requests on {}.", address),
+            Err(e) => error!(
+                "Error listening for browser requests on port {}: {}!",
+                port, e
+            ),
+        }
+    }
+
+    for event in events {
+        UiEventService::instance().send(event)?;
+    }
+
     info!("Running GUI.");
     cursive.run()
 }
 
+/// Prints the native fingerprint of each file at the given paths and how closely it matches the
+/// fingerprint calculated by fpcalc.
+pub async fn run_fingerprint(paths: Vec<&PathBuf>) {
+    let acoustid_service = AcoustIdService::instance();
+    for path in paths {
+        match acoustid_service.get_native_fingerprint(path).await {
+            Ok(fingerprint) => println!(
+                "{}\nDURATION={}\nFINGERPRINT={}",
+                path.display(),
+                fingerprint.duration,
+                fingerprint.fingerprint
+            ),
+            Err(e) => println!("{}\n{}", path.display(), e),
+        }
+        match acoustid_service.compare_fingerprints(path).await {
+            Ok(similarity) => println!("SIMILARITY={:.3}", similarity),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
 /// Runs a test.
 pub fn run_test() {
     let tag = ID3v2Tag::read_from_path("/home/curtis/Downloads/04_discipline_64kb.mp3")
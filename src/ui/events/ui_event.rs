@@ -1,23 +1,45 @@
-use crate::domain::models::AudioFile;
+use crate::domain::models::{AlbumId, AudioFile, AudioFileId, ClusterId, LookupResult, TrackId};
+use crate::infrastructure::config::{ReleasePreferences, ReleaseWeights};
 use crate::infrastructure::file::File;
-use crate::ui::models::{AlbumView, AudioFileView, TagFieldView};
+use crate::ui::models::{AlbumView, ClusterView, TagFieldView};
 use std::fmt::Display;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub enum UiEvent {
+    ClusterFiles,
     FileSelected(Arc<File>),
+    FindDuplicates,
     FolderSelected(Arc<File>),
+    LoadRelease(String),
+    LookupAudioFile(AudioFileId),
+    LookupAudioFileDisc(AudioFileId),
+    LookupCluster(ClusterId),
+    LookupClusterDisc(ClusterId),
+    MatchLookupResult(Vec<AudioFileId>, Box<LookupResult>),
+    MoveAudioFile(AudioFileId, TrackId),
     OpenAddFile,
     OpenAddFolder,
+    OpenLoadRelease,
     OpenLogs,
+    OpenOtherVersions(AlbumId),
+    OpenReleasePreferences,
+    OpenTrackPicker(AudioFileId),
+    ReleasePreferencesSaved(ReleasePreferences, ReleaseWeights),
     SaveAudioFile(AudioFile),
     ScanAudioFile(AudioFile),
+    ScanCluster(ClusterId),
     SelectAlbumView(AlbumView),
-    SelectClusterFile(AudioFileView),
+    SelectClusterView(ClusterView),
+    SubmitAcoustIds,
+    SwitchRelease(AlbumId, AlbumId),
     SubmitAlbumView(AlbumView),
-    SubmitClusterFile(AudioFileView),
+    SubmitClusterView(ClusterView),
     SubmitMetadataRow(TagFieldView),
+    ToggleCluster(Option<ClusterId>),
+    ToggleIncompleteAlbums,
+    UnmatchAlbum(AlbumId),
+    UnmatchAudioFile(AudioFileId),
 }
 
 impl Display for UiEvent {
@@ -0,0 +1,62 @@
+use super::MatchState;
+use std::fmt::Display;
+
+/// Represents how completely the files matched to an album cover its tracks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlbumStatus {
+    /// Every track has exactly one file matched.
+    Complete,
+    /// Some tracks have more than one file matched.
+    Duplicates { duplicated: usize },
+    /// Some files are of recordings that aren't on the release, and no track has more than one
+    /// file matched.
+    Extra { extra: usize },
+    /// Some tracks have no file matched, and there are no duplicated or extra files.
+    Incomplete { matched: usize, total: usize },
+}
+
+impl AlbumStatus {
+    /// Returns the status of an album whose tracks have the given match states.
+    ///
+    /// # Arguments
+    ///
+    /// * `match_states` - The match state of each track of the album.
+    pub fn for_match_states(match_states: &[MatchState]) -> Self {
+        let duplicated = match_states
+            .iter()
+            .filter(|match_state| match_state.is_duplicated())
+            .count();
+        let extra = match_states
+            .iter()
+            .filter(|match_state| match_state.is_extra())
+            .count();
+        let matched = match_states
+            .iter()
+            .filter(|match_state| match_state.is_matched())
+            .count();
+        match (duplicated, extra, matched == match_states.len()) {
+            (0, 0, true) => AlbumStatus::Complete,
+            (0, 0, false) => AlbumStatus::Incomplete {
+                matched,
+                total: match_states.len(),
+            },
+            (0, extra, _) => AlbumStatus::Extra { extra },
+            _ => AlbumStatus::Duplicates { duplicated },
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        *self == AlbumStatus::Complete
+    }
+}
+
+impl Display for AlbumStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlbumStatus::Complete => write!(f, "●"),
+            AlbumStatus::Duplicates { duplicated } => write!(f, "‼ {} duplicated", duplicated),
+            AlbumStatus::Extra { extra } => write!(f, "✚ {} extra", extra),
+            AlbumStatus::Incomplete { matched, total } => write!(f, "◐ {}/{}", matched, total),
+        }
+    }
+}
@@ -1,8 +1,34 @@
-use super::{AudioFileColumn, MatchState};
+use super::{AlbumStatus, AudioFileColumn, MatchState};
 use crate::domain::models::{Album, AlbumId, AudioFile, Track, TrackId};
 use crate::infrastructure::{DurationExtensions, Entity, EntityId};
 use cursive_table_view::TableViewItem;
 use std::cmp::Ordering;
+use std::time::Duration;
+
+/// The difference between a track's and its file's lengths that is shown.
+const LENGTH_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// The glyphs of the similarity of a track's match, from least to most similar.
+const SIMILARITY_GLYPHS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// The similarity from which a match is strong.
+const STRONG_MATCH_THRESHOLD: f64 = 0.8;
+
+/// The similarity below which a match is weak.
+const WEAK_MATCH_THRESHOLD: f64 = 0.6;
+
+/// The coloured glyphs of strong, middling and weak matches. Table rows are drawn in a single
+/// colour, so the colour of an album's weakest match is carried by a glyph.
+const SIMILARITY_COLOURS: [&str; 3] = ["🟩", "🟨", "🟥"];
+
+/// The glyph of tracks with more than one file matched.
+const DUPLICATED_GLYPH: &str = "‼";
+
+/// The glyph of tracks with a file of a recording that isn't on the release matched.
+const EXTRA_GLYPH: &str = "✚";
+
+/// The mark of tracks whose matched file is saved.
+const SAVED_MARK: &str = "✔";
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AlbumViewId {
@@ -32,9 +58,15 @@ impl EntityId for AlbumViewId {
 pub struct AlbumView {
     pub id: AlbumViewId,
     pub artist: String,
+    /// The properties of the matched file's audio stream, for track rows.
+    pub audio: String,
     pub disc_number: i32,
     pub length: String,
+    pub mb_length: Duration,
     pub number: i32,
+
+    /// How completely the files cover the tracks, for album rows.
+    pub status: Option<AlbumStatus>,
     pub title: String,
 }
 
@@ -45,7 +77,16 @@ impl AlbumView {
         match_states: &Vec<MatchState>,
     ) -> Vec<AlbumView> {
         let mut views = vec![];
-        views.push(Self::for_album(album, MatchState::from(match_states)));
+        let weakest_similarity = match_states
+            .iter()
+            .filter_map(|match_state| match_state.similarity())
+            .reduce(f64::min);
+        views.push(Self::for_album(
+            album,
+            MatchState::from(match_states),
+            weakest_similarity,
+            AlbumStatus::for_match_states(match_states),
+        ));
         let mut tracks: Vec<AlbumView> = tracks
             .iter()
             .enumerate()
@@ -55,35 +96,76 @@ impl AlbumView {
         views
     }
 
-    pub fn for_album(album: &Album, match_state: MatchState) -> AlbumView {
+    /// Returns the row of the given album, coloured by its weakest match, and showing whether
+    /// its tracks are all matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `album` - The album to show.
+    /// * `match_state` - The combined match state of the album's tracks.
+    /// * `weakest_similarity` - The lowest similarity of the album's matched files, if any.
+    /// * `status` - How completely the files cover the album's tracks.
+    pub fn for_album(
+        album: &Album,
+        match_state: MatchState,
+        weakest_similarity: Option<f64>,
+        status: AlbumStatus,
+    ) -> AlbumView {
         let icon = match match_state {
-            MatchState::MatchedChanges => "⦿⃰",
-            MatchState::MatchedNoChanges => "⦿",
+            MatchState::MatchedChanges(_)
+            | MatchState::MatchedDuplicates(_, true)
+            | MatchState::MatchedExtra(_, true) => "⦿⃰",
+            MatchState::MatchedDuplicates(_, false)
+            | MatchState::MatchedExtra(_, false)
+            | MatchState::MatchedNoChanges(_) => "⦿",
             MatchState::UnmatchedChanges => "⦾⃰",
             MatchState::UnmatchedNoChanges => "⦾",
         };
+        let icon = match weakest_similarity {
+            Some(similarity) => format!("{} {}", icon, similarity_colour(similarity)),
+            None => icon.to_string(),
+        };
         Self {
             id: AlbumViewId::Album(album.id.clone()),
             artist: album.artist.clone(),
+            audio: String::new(),
             disc_number: 0,
             length: album.length.to_pretty_string(),
+            mb_length: album.length,
             number: 0,
-            title: format!("{} {}", icon, album.title.clone()),
+            status: Some(status),
+            title: match album.score {
+                Some(score) => format!(
+                    "{} {} ({:.0}%) {}",
+                    icon,
+                    album.title,
+                    score * 100.0,
+                    status
+                ),
+                None => format!("{} {} {}", icon, album.title, status),
+            },
         }
     }
 
     pub fn for_track(track: &Track, match_state: MatchState) -> AlbumView {
         let icon = match match_state {
-            MatchState::MatchedChanges => "█", // TODO: use match rating to use other block characters: ▁, ▂, ▃, ▄, ▅, ▆, ▇
-            MatchState::MatchedNoChanges => "✔",
-            MatchState::UnmatchedChanges | MatchState::UnmatchedNoChanges => " ",
+            MatchState::MatchedChanges(similarity) => format!("{} ", similarity_glyph(similarity)),
+            MatchState::MatchedDuplicates(_, _) => format!("{} ", DUPLICATED_GLYPH),
+            MatchState::MatchedExtra(_, _) => format!("{} ", EXTRA_GLYPH),
+            MatchState::MatchedNoChanges(similarity) => {
+                format!("{}{}", similarity_glyph(similarity), SAVED_MARK)
+            }
+            MatchState::UnmatchedChanges | MatchState::UnmatchedNoChanges => String::from("  "),
         };
         Self {
             id: AlbumViewId::Track(track.id.clone()),
             artist: track.artist.clone(),
+            audio: String::new(),
             disc_number: track.disc_number,
             length: track.length.to_pretty_string(),
+            mb_length: track.length,
             number: track.number,
+            status: None,
             title: format!(
                 "  {} {}-{} {}",
                 icon, track.disc_number, track.number, track.title
@@ -91,11 +173,54 @@ impl AlbumView {
         }
     }
 
-    pub fn update_for_audio_file(&mut self, _audio_file: &AudioFile) {
-        // TODO: idk if there's actually anything to update :shrug:
+    /// Updates a track to show the matched file's audio properties, and its length too when it
+    /// differs from the track's by more than a couple of seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file matched to this track.
+    pub fn update_for_audio_file(&mut self, audio_file: &AudioFile) {
+        if let AlbumViewId::Track(_) = &self.id {
+            self.audio = audio_file.audio_properties();
+        }
+        if let (AlbumViewId::Track(_), Some(duration)) = (&self.id, audio_file.duration) {
+            self.length = match self.mb_length.abs_diff(duration) > LENGTH_TOLERANCE {
+                true => format!(
+                    "{} ({})",
+                    self.mb_length.to_pretty_string(),
+                    duration.to_pretty_string()
+                ),
+                false => self.mb_length.to_pretty_string(),
+            };
+        }
+    }
+}
+
+/// Returns the coloured glyph of the given similarity, green for a strong match, yellow for a
+/// middling one and red for a weak one.
+///
+/// # Arguments
+///
+/// * `similarity` - The similarity of a match, from 0 to 1.
+fn similarity_colour(similarity: f64) -> &'static str {
+    match similarity {
+        similarity if similarity >= STRONG_MATCH_THRESHOLD => SIMILARITY_COLOURS[0],
+        similarity if similarity >= WEAK_MATCH_THRESHOLD => SIMILARITY_COLOURS[1],
+        _ => SIMILARITY_COLOURS[2],
     }
 }
 
+/// Returns the glyph of the given similarity, from ▁ for none to █ for a perfect match.
+///
+/// # Arguments
+///
+/// * `similarity` - The similarity of a match, from 0 to 1.
+fn similarity_glyph(similarity: f64) -> &'static str {
+    let last = SIMILARITY_GLYPHS.len() - 1;
+    let index = (similarity.clamp(0.0, 1.0) * last as f64).round() as usize;
+    SIMILARITY_GLYPHS[index]
+}
+
 impl Entity for AlbumView {
     type Id = AlbumViewId;
 
@@ -118,6 +243,7 @@ impl TableViewItem<AudioFileColumn> for AlbumView {
             AudioFileColumn::Title => self.title.clone(),
             AudioFileColumn::Artist => self.artist.clone(),
             AudioFileColumn::Length => self.length.clone(),
+            AudioFileColumn::Audio => self.audio.clone(),
         }
     }
 
@@ -153,6 +279,7 @@ impl TableViewItem<AudioFileColumn> for AlbumView {
                 },
                 AudioFileColumn::Artist => self.artist.cmp(&other.artist),
                 AudioFileColumn::Length => self.length.cmp(&other.length),
+                AudioFileColumn::Audio => self.audio.cmp(&other.audio),
             },
 
             // order different album (or equal album rows) by album id
@@ -161,3 +288,98 @@ impl TableViewItem<AudioFileColumn> for AlbumView {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn album() -> Album {
+        Album {
+            artist: String::from("Artist"),
+            artist_id: String::from("artist"),
+            asin: None,
+            barcode: None,
+            catalog_number: None,
+            date: String::from("2001-02-03"),
+            events: vec![],
+            id: AlbumId::new(String::from("album")),
+            length: Duration::from_secs(360),
+            record_label: None,
+            release_country: String::from("GB"),
+            release_group_id: String::from("release-group"),
+            release_status: String::from("Official"),
+            release_type: Some(String::from("Album")),
+            score: None,
+            script: None,
+            sort_artist: String::from("Artist"),
+            title: String::from("Album"),
+            total_discs: 1,
+        }
+    }
+
+    fn track(number: i32) -> Track {
+        Track {
+            artist: String::from("Artist"),
+            artist_id: String::from("artist"),
+            artists: String::from("Artist"),
+            disc_number: 1,
+            disc_subtitle: String::new(),
+            events: vec![],
+            id: TrackId::new(AlbumId::new(String::from("album")), number.to_string()),
+            isrc: None,
+            length: Duration::from_secs(180),
+            media: String::from("CD"),
+            number,
+            original_release_date: String::from("2001-02-03"),
+            original_year: String::from("2001"),
+            recording_id: format!("recording-{}", number),
+            sort_artist: String::from("Artist"),
+            title: format!("Song {}", number),
+            total_tracks: 2,
+        }
+    }
+
+    #[test]
+    fn similarity_glyph_rises_with_the_similarity() {
+        assert_eq!(similarity_glyph(0.0), "▁");
+        assert_eq!(similarity_glyph(0.5), "▅");
+        assert_eq!(similarity_glyph(1.0), "█");
+        assert_eq!(similarity_glyph(-1.0), "▁");
+        assert_eq!(similarity_glyph(2.0), "█");
+    }
+
+    #[test]
+    fn similarity_colour_is_green_yellow_or_red_by_threshold() {
+        assert_eq!(similarity_colour(1.0), "🟩");
+        assert_eq!(similarity_colour(STRONG_MATCH_THRESHOLD), "🟩");
+        assert_eq!(similarity_colour(0.7), "🟨");
+        assert_eq!(similarity_colour(WEAK_MATCH_THRESHOLD), "🟨");
+        assert_eq!(similarity_colour(0.59), "🟥");
+    }
+
+    #[test]
+    fn all_for_album_colours_the_album_by_its_weakest_match() {
+        let views = AlbumView::all_for_album(
+            &album(),
+            &[track(1), track(2)],
+            &vec![
+                MatchState::MatchedNoChanges(1.0),
+                MatchState::MatchedChanges(0.5),
+            ],
+        );
+
+        assert_eq!(views.len(), 3);
+        assert!(views[0].title.starts_with("⦿⃰ 🟥 Album"));
+        assert_eq!(views[1].title, "  █✔ 1-1 Song 1");
+        assert_eq!(views[2].title, "  ▅  1-2 Song 2");
+    }
+
+    #[test]
+    fn all_for_album_leaves_an_unmatched_album_uncoloured() {
+        let views =
+            AlbumView::all_for_album(&album(), &[track(1)], &vec![MatchState::UnmatchedNoChanges]);
+
+        assert!(views[0].title.starts_with("⦾ Album"));
+        assert_eq!(views[1].title, "     1-1 Song 1");
+    }
+}
@@ -9,6 +9,8 @@ pub enum AudioFileColumn {
     Artist,
     /// The audio length column.
     Length,
+    /// The audio properties column, e.g. bitrate and sample rate.
+    Audio,
 }
 
 impl AudioFileColumn {
@@ -18,6 +20,7 @@ impl AudioFileColumn {
             AudioFileColumn::Title => "Title",
             AudioFileColumn::Artist => "Artist",
             AudioFileColumn::Length => "Length",
+            AudioFileColumn::Audio => "Audio",
         }
     }
 }
@@ -0,0 +1,169 @@
+use super::AudioFileColumn;
+use crate::domain::models::{AudioFile, AudioFileId, Cluster, ClusterId, Metadata};
+use crate::infrastructure::{Entity, EntityId};
+use cursive_table_view::TableViewItem;
+use std::cmp::Ordering;
+
+/// The title of the cluster row of the files that haven't been clustered.
+const UNCLUSTERED_TITLE: &str = "Unclustered Files";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClusterViewId {
+    /// The row of a cluster, or of the unclustered files if there's no cluster ID.
+    Cluster(Option<ClusterId>),
+    /// The row of a file in a cluster, or in the unclustered files if there's no cluster ID.
+    AudioFile(Option<ClusterId>, AudioFileId),
+}
+
+impl ClusterViewId {
+    pub fn audio_file_id(&self) -> Option<&AudioFileId> {
+        match self {
+            ClusterViewId::Cluster(_) => None,
+            ClusterViewId::AudioFile(_, audio_file_id) => Some(audio_file_id),
+        }
+    }
+
+    pub fn cluster_id(&self) -> &Option<ClusterId> {
+        match self {
+            ClusterViewId::Cluster(cluster_id) => cluster_id,
+            ClusterViewId::AudioFile(cluster_id, _) => cluster_id,
+        }
+    }
+}
+
+impl EntityId for ClusterViewId {
+    fn to_string(&self) -> String {
+        match self {
+            ClusterViewId::Cluster(cluster_id) => cluster_id
+                .as_ref()
+                .map(|cluster_id| cluster_id.to_string())
+                .unwrap_or_default(),
+            ClusterViewId::AudioFile(_, audio_file_id) => audio_file_id.to_string(),
+        }
+    }
+}
+
+/// Represents the UI view of a cluster, or of a file in one, in the cluster table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClusterView {
+    pub id: ClusterViewId,
+    pub artist: String,
+    /// The properties of a file's audio stream, e.g. bitrate and sample rate.
+    pub audio: String,
+    /// Whether the rows of a cluster's files are shown.
+    pub is_expanded: bool,
+    pub length: String,
+    pub title: String,
+}
+
+impl ClusterView {
+    /// Returns the row of the given cluster, or of the unclustered files if there's none.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster` - The cluster to show.
+    /// * `is_expanded` - Whether the rows of the cluster's files are shown.
+    pub fn for_cluster(cluster: Option<&Cluster>, is_expanded: bool) -> Self {
+        let (title, artist) = match cluster {
+            Some(cluster) => (cluster.album.clone(), cluster.artist.clone()),
+            None => (UNCLUSTERED_TITLE.to_string(), String::new()),
+        };
+        Self {
+            id: ClusterViewId::Cluster(cluster.map(|cluster| cluster.id.clone())),
+            artist,
+            audio: String::new(),
+            is_expanded,
+            length: String::new(),
+            title,
+        }
+    }
+
+    /// Returns the row of the given file in its cluster.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to show.
+    /// * `metadata` - The tags of the audio file.
+    pub fn for_audio_file(audio_file: &AudioFile, metadata: &Metadata) -> Self {
+        Self {
+            id: ClusterViewId::AudioFile(audio_file.cluster_id.clone(), audio_file.id.clone()),
+            artist: metadata.artist().unwrap_or("<no artist>".to_string()),
+            audio: audio_file.audio_properties(),
+            is_expanded: false,
+            length: audio_file.length().unwrap_or("?:??".to_string()),
+            title: metadata.title().unwrap_or("<no title>".to_string()),
+        }
+    }
+}
+
+impl Entity for ClusterView {
+    type Id = ClusterViewId;
+
+    fn id(&self) -> &Self::Id
+    where
+        Self::Id: EntityId,
+    {
+        &self.id
+    }
+}
+
+impl TableViewItem<AudioFileColumn> for ClusterView {
+    /// Returns the value of the given column for this row.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to get the value of.
+    fn to_column(&self, column: AudioFileColumn) -> String {
+        match column {
+            AudioFileColumn::Title => match (&self.id, self.is_expanded) {
+                (ClusterViewId::Cluster(_), true) => format!("▾ {}", self.title),
+                (ClusterViewId::Cluster(_), false) => format!("▸ {}", self.title),
+                (ClusterViewId::AudioFile(_, _), _) => format!("  {}", self.title),
+            },
+            AudioFileColumn::Artist => self.artist.clone(),
+            AudioFileColumn::Length => self.length.clone(),
+            AudioFileColumn::Audio => self.audio.clone(),
+        }
+    }
+
+    /// Compares the value of the given column to another row.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other row to compare to.
+    /// * `column` - The column to compare between the rows.
+    fn cmp(&self, other: &Self, column: AudioFileColumn) -> Ordering
+    where
+        Self: Sized,
+    {
+        match (
+            &self.id,
+            &other.id,
+            self.id.cluster_id() == other.id.cluster_id(),
+        ) {
+            // put cluster row before its file rows
+            (ClusterViewId::Cluster(_), ClusterViewId::AudioFile(_, _), true) => Ordering::Less,
+            (ClusterViewId::AudioFile(_, _), ClusterViewId::Cluster(_), true) => Ordering::Greater,
+
+            // sort files in same cluster by their actual column values
+            (ClusterViewId::AudioFile(_, _), ClusterViewId::AudioFile(_, _), true) => {
+                match column {
+                    AudioFileColumn::Title => self.title.cmp(&other.title),
+                    AudioFileColumn::Artist => self.artist.cmp(&other.artist),
+                    AudioFileColumn::Length => self.length.cmp(&other.length),
+                    AudioFileColumn::Audio => self.audio.cmp(&other.audio),
+                }
+            }
+
+            // order different clusters by cluster id, with the unclustered files last
+            _ => match (self.id.cluster_id(), other.id.cluster_id()) {
+                (Some(cluster_id), Some(other_cluster_id)) => {
+                    cluster_id.value.cmp(&other_cluster_id.value)
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+    }
+}
@@ -33,9 +33,14 @@ impl From<&Arc<File>> for FileView {
                 Some(size) => size.to_string(),
                 None => String::new(),
             },
-            file_type: match file.file_type {
-                Some(file_type) => file_type.as_str().to_string(),
-                None => String::from("Unknown"),
+            file_type: match (file.file_type, file.extension_file_type) {
+                (Some(file_type), Some(extension_file_type)) => format!(
+                    "{} (misnamed {})",
+                    file_type.as_str(),
+                    extension_file_type.as_str()
+                ),
+                (Some(file_type), None) => file_type.as_str().to_string(),
+                (None, _) => String::from("Unknown"),
             },
             date_modified: file
                 .date_modified
@@ -5,43 +5,100 @@ use crate::domain::{
 use anyhow::Result;
 use std::ops::BitOr;
 
-// TODO: add match confidence to matched variants
+/// The similarity of files matched before similarities were stored.
+const UNKNOWN_SIMILARITY: f64 = 1.0;
+
+/// Represents whether a track, or all tracks of an album, are matched and have unsaved changes.
+/// Matched states hold the lowest similarity of the matched files, from 0 to 1.
 #[derive(Clone, Copy, Debug)]
 pub enum MatchState {
-    MatchedChanges,
-    MatchedNoChanges,
+    MatchedChanges(f64),
+    /// More than one file is matched to the track, and whether any has unsaved changes.
+    MatchedDuplicates(f64, bool),
+    /// A file whose fingerprint is of a recording that isn't on the release is matched to the
+    /// track, and whether any has unsaved changes.
+    MatchedExtra(f64, bool),
+    MatchedNoChanges(f64),
     UnmatchedChanges,
     UnmatchedNoChanges,
 }
 
 impl MatchState {
+    /// Returns the match state of each of the given tracks. Tracks with more than one file
+    /// matched are reported as duplicated, and tracks with a file whose fingerprint is of a
+    /// recording that isn't among the tracks are reported as having an extra file.
+    ///
+    /// # Arguments
+    ///
+    /// * `tracks` - The tracks to get the match states of, all of one release.
     pub async fn for_tracks(tracks: &[Track]) -> Result<Vec<MatchState>> {
         let mut match_states = vec![];
         for track in tracks {
             let matches = AudioFileRepository::instance().get_matched(track).await?;
-            let is_matched = !matches.is_empty();
+            let similarity = matches
+                .iter()
+                .map(|m| m.similarity.unwrap_or(UNKNOWN_SIMILARITY))
+                .reduce(f64::min);
+            let is_duplicated = matches.len() > 1;
+            let is_extra = matches.iter().any(|m| {
+                m.fingerprint_recording_id
+                    .as_ref()
+                    .is_some_and(|recording_id| {
+                        !tracks
+                            .iter()
+                            .any(|track| track.recording_id == *recording_id)
+                    })
+            });
             let mut has_changes: Vec<bool> = vec![];
             for m in matches {
                 let tag = TagRepository::instance().get(&m).await?;
                 has_changes.push(tag.has_changes());
             }
             let has_changes = has_changes.iter().any(|h| *h);
-            match_states.push(MatchState::from((is_matched, has_changes)));
+            match (similarity, is_duplicated, is_extra) {
+                (Some(similarity), true, _) => {
+                    match_states.push(MatchState::MatchedDuplicates(similarity, has_changes))
+                }
+                (Some(similarity), false, true) => {
+                    match_states.push(MatchState::MatchedExtra(similarity, has_changes))
+                }
+                _ => match_states.push(MatchState::from((similarity, has_changes))),
+            }
         }
         Ok(match_states)
     }
 
     pub fn has_changes(&self) -> bool {
         match self {
-            MatchState::MatchedChanges | MatchState::UnmatchedChanges => true,
-            MatchState::MatchedNoChanges | MatchState::UnmatchedNoChanges => false,
+            MatchState::MatchedChanges(_) | MatchState::UnmatchedChanges => true,
+            MatchState::MatchedDuplicates(_, has_changes)
+            | MatchState::MatchedExtra(_, has_changes) => *has_changes,
+            MatchState::MatchedNoChanges(_) | MatchState::UnmatchedNoChanges => false,
         }
     }
 
+    /// Returns whether more than one file is matched to the track.
+    pub fn is_duplicated(&self) -> bool {
+        matches!(self, MatchState::MatchedDuplicates(_, _))
+    }
+
+    /// Returns whether a file of a recording that isn't on the release is matched to the track.
+    pub fn is_extra(&self) -> bool {
+        matches!(self, MatchState::MatchedExtra(_, _))
+    }
+
     pub fn is_matched(&self) -> bool {
+        self.similarity().is_some()
+    }
+
+    /// Returns the lowest similarity of the matched files, if matched.
+    pub fn similarity(&self) -> Option<f64> {
         match self {
-            MatchState::MatchedChanges | MatchState::MatchedNoChanges => true,
-            MatchState::UnmatchedChanges | MatchState::UnmatchedNoChanges => false,
+            MatchState::MatchedChanges(similarity)
+            | MatchState::MatchedDuplicates(similarity, _)
+            | MatchState::MatchedExtra(similarity, _)
+            | MatchState::MatchedNoChanges(similarity) => Some(*similarity),
+            MatchState::UnmatchedChanges | MatchState::UnmatchedNoChanges => None,
         }
     }
 }
@@ -50,19 +107,22 @@ impl BitOr for MatchState {
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        let is_matched = self.is_matched() && rhs.is_matched();
+        let similarity = match (self.similarity(), rhs.similarity()) {
+            (Some(similarity), Some(rhs_similarity)) => Some(similarity.min(rhs_similarity)),
+            _ => None,
+        };
         let has_changes = self.has_changes() || rhs.has_changes();
-        Self::from((is_matched, has_changes))
+        Self::from((similarity, has_changes))
     }
 }
 
-impl From<(bool, bool)> for MatchState {
-    fn from((is_matched, has_changes): (bool, bool)) -> Self {
-        match (is_matched, has_changes) {
-            (true, true) => Self::MatchedChanges,
-            (true, false) => Self::MatchedNoChanges,
-            (false, true) => Self::UnmatchedChanges,
-            (false, false) => Self::UnmatchedNoChanges,
+impl From<(Option<f64>, bool)> for MatchState {
+    fn from((similarity, has_changes): (Option<f64>, bool)) -> Self {
+        match (similarity, has_changes) {
+            (Some(similarity), true) => Self::MatchedChanges(similarity),
+            (Some(similarity), false) => Self::MatchedNoChanges(similarity),
+            (None, true) => Self::UnmatchedChanges,
+            (None, false) => Self::UnmatchedNoChanges,
         }
     }
 }
@@ -71,8 +131,113 @@ impl From<&Vec<MatchState>> for MatchState {
     fn from(match_states: &Vec<MatchState>) -> Self {
         match_states
             .iter()
-            .fold(MatchState::MatchedNoChanges, |accumulator, current| {
+            .fold(MatchState::MatchedNoChanges(1.0), |accumulator, current| {
                 accumulator | *current
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        AlbumId, AudioFile, AudioFileId, FieldValue, Metadata, ScanStatus, TagFieldType, TrackId,
+    };
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn track(album_id: &AlbumId, number: i32, recording_id: &str) -> Track {
+        Track {
+            artist: String::from("Artist"),
+            artist_id: String::from("artist"),
+            artists: String::from("Artist"),
+            disc_number: 1,
+            disc_subtitle: String::new(),
+            events: vec![],
+            id: TrackId::new(album_id.clone(), number.to_string()),
+            isrc: None,
+            length: Duration::from_secs(180),
+            media: String::from("CD"),
+            number,
+            original_release_date: String::from("2001-02-03"),
+            original_year: String::from("2001"),
+            recording_id: String::from(recording_id),
+            sort_artist: String::from("Artist"),
+            title: format!("Song {}", number),
+            total_tracks: 2,
+        }
+    }
+
+    async fn add_matched_file(name: &str, track: &Track, fingerprint_recording_id: &str) {
+        let id = AudioFileId::new(PathBuf::from(format!(
+            "/sisko-test/{}/{}.mp3",
+            std::process::id(),
+            name
+        )));
+        let mut audio_file = AudioFile {
+            acoust_id: None,
+            audio_checksum: None,
+            bitrate: None,
+            channel_mode: None,
+            cluster_id: None,
+            duration: None,
+            encoder: None,
+            encoder_delay: None,
+            encoder_padding: None,
+            events: vec![],
+            fingerprint: None,
+            fingerprint_recording_id: Some(String::from(fingerprint_recording_id)),
+            id: id.clone(),
+            recording_id: None,
+            sample_rate: None,
+            scan_status: ScanStatus::Matched,
+            similarity: None,
+            track_id: None,
+        };
+        audio_file.match_to_track(track, 0.9);
+        // NOTE: matching points the file at the track's recording, but keeps its fingerprint's
+        assert_eq!(audio_file.recording_id, Some(track.recording_id.clone()));
+        assert_eq!(
+            audio_file.fingerprint_recording_id.as_deref(),
+            Some(fingerprint_recording_id)
+        );
+        AudioFileRepository::instance()
+            .add(audio_file)
+            .await
+            .unwrap();
+        TagRepository::instance()
+            .add(Metadata::new(
+                id,
+                HashMap::from([(TagFieldType::Title, FieldValue::Text(track.title.clone()))]),
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn for_tracks_reports_files_of_recordings_not_on_the_release_as_extra() {
+        let album_id = AlbumId::new(format!("extra-{}", std::process::id()));
+        let tracks = [track(&album_id, 1, "first"), track(&album_id, 2, "second")];
+        add_matched_file("first", &tracks[0], "elsewhere").await;
+        add_matched_file("second", &tracks[1], "second").await;
+
+        let match_states = MatchState::for_tracks(&tracks).await.unwrap();
+
+        let matched = AudioFileRepository::instance()
+            .get_matched(&tracks[0])
+            .await
+            .unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].recording_id.as_deref(), Some("first"));
+        assert_eq!(
+            matched[0].fingerprint_recording_id.as_deref(),
+            Some("elsewhere")
+        );
+        assert!(match_states[0].is_extra());
+        assert_eq!(match_states[0].similarity(), Some(0.9));
+        assert!(!match_states[1].is_extra());
+        assert!(!match_states[1].is_duplicated());
+        assert_eq!(match_states[1].similarity(), Some(0.9));
+    }
+}
@@ -1,6 +1,7 @@
+mod album_status;
 mod album_view;
 mod audio_file_column;
-mod audio_file_view;
+mod cluster_view;
 mod file_column;
 mod file_dialog_type;
 mod file_view;
@@ -11,9 +12,10 @@ mod tag_field_view;
 mod tag_view;
 mod ui_element;
 
+pub use album_status::*;
 pub use album_view::*;
 pub use audio_file_column::*;
-pub use audio_file_view::*;
+pub use cluster_view::*;
 pub use file_column::*;
 pub use file_dialog_type::*;
 pub use file_view::*;
@@ -16,6 +16,9 @@ pub const HIDEABLE_LEFT_PANEL: &str = "hideable_left_panel";
 /// The name of the hideable right panel.
 pub const HIDEABLE_RIGHT_PANEL: &str = "hideable_right_panel";
 
+/// The name of the input for the ID or URL of a release to load.
+pub const LOAD_RELEASE_INPUT: &str = "load_release_input";
+
 /// The name of the metadata table.
 pub const METADATA_TABLE: &str = "metadata_table";
 
@@ -1,26 +1,49 @@
 use super::{CbSinkService, UiEventService};
 use crate::{
-    domain::models::{Album, AudioFile, Metadata, Track},
+    domain::models::{
+        Album, AlbumId, AudioFile, AudioFileId, Metadata, ReleaseVersion, Track, TrackId,
+    },
+    infrastructure::TableViewExtensions,
     ui::{
         events::UiEvent,
-        models::{AlbumView, AlbumViewId, AudioFileColumn, MatchState, ALBUM_FILE_TABLE},
+        models::{
+            AlbumStatus, AlbumView, AlbumViewId, AudioFileColumn, MatchState, ALBUM_FILE_TABLE,
+        },
     },
 };
 use anyhow::{anyhow, Result};
 use cursive::{
-    views::{Button, Dialog, LinearLayout},
+    views::{Button, Dialog, LinearLayout, ScrollView, SelectView},
     Cursive,
 };
 use cursive_table_view::TableView;
 use log::error;
+use std::sync::{Arc, Mutex};
+
+/// The rows of complete albums, which aren't in the table while only incomplete albums are
+/// shown, or none while all albums are shown.
+type HiddenAlbums = Arc<Mutex<Option<Vec<AlbumView>>>>;
 
-pub struct AlbumTable {}
+pub struct AlbumTable {
+    hidden: HiddenAlbums,
+}
 
 impl AlbumTable {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            hidden: Arc::new(Mutex::new(None)),
+        }
     }
 
+    /// Adds the rows of the given album and its tracks, replacing the album's rows if it's
+    /// already shown. The rows of a complete album are hidden while only incomplete albums are
+    /// shown.
+    ///
+    /// # Arguments
+    ///
+    /// * `album` - The album to show.
+    /// * `tracks` - The tracks of the album.
+    /// * `match_states` - The match state of each track.
     pub fn add_album(
         &self,
         album: &Album,
@@ -29,42 +52,155 @@ impl AlbumTable {
     ) -> Result<()> {
         let album_id = album.id.clone();
         let album_views = AlbumView::all_for_album(album, tracks, match_states);
+        let is_complete = AlbumStatus::for_match_states(match_states).is_complete();
+        let hidden = self.hidden.clone();
         CbSinkService::instance()?
             .send(Box::new(move |s: &mut Cursive| {
                 s.call_on_name(
                     ALBUM_FILE_TABLE,
                     |table: &mut TableView<AlbumView, AudioFileColumn>| {
-                        if !table
-                            .borrow_items()
-                            .iter()
-                            .any(|item| *item.id.album_id() == album_id)
-                        {
-                            for view in album_views {
-                                table.insert_item(view);
+                        let Ok(mut hidden) = hidden.lock() else {
+                            error!("Error locking hidden albums mutex!");
+                            return;
+                        };
+                        remove_album_rows(table, &album_id);
+                        if let Some(hidden) = hidden.as_mut() {
+                            hidden.retain(|view| *view.id.album_id() != album_id);
+                            if is_complete {
+                                hidden.extend(album_views);
+                                return;
                             }
                         }
+                        for view in album_views {
+                            table.insert_item(view);
+                        }
                     },
                 );
             }))
             .map_err(|_| anyhow!("Error sending add album callback to CbSink!"))
     }
 
+    /// Opens the actions dialog of the given album or track row.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_view` - The album or track row to open the dialog for.
+    /// * `audio_file` - The audio file matched to the track, if any.
     pub fn open_album_view_dialog(
         &self,
+        album_view: &AlbumView,
         audio_file: &Option<AudioFile>,
-        title: &str,
     ) -> Result<()> {
+        let album_view = album_view.clone();
         let audio_file = audio_file.clone();
-        let title = title.to_owned();
         CbSinkService::instance()?
             .send(Box::new(move |s: &mut Cursive| {
-                if let Err(e) = album_view_dialog(s, audio_file, title) {
-                    error!("Error opening album view dialog: {e}!");
-                }
+                album_view_dialog(s, album_view, audio_file);
             }))
             .map_err(|_| anyhow!("Error sending open album view dialog callback to CbSink!"))
     }
 
+    /// Opens a dialog listing the other releases of the given album's release group, to switch
+    /// the album to one.
+    ///
+    /// # Arguments
+    ///
+    /// * `album_id` - The ID of the album to switch.
+    /// * `versions` - The releases of the album's release group.
+    pub fn open_other_versions_dialog(
+        &self,
+        album_id: &AlbumId,
+        versions: Vec<ReleaseVersion>,
+    ) -> Result<()> {
+        let album_id = album_id.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                other_versions_dialog(s, album_id, versions);
+            }))
+            .map_err(|_| anyhow!("Error sending open other versions dialog callback to CbSink!"))
+    }
+
+    /// Opens a dialog listing the tracks of the loaded albums, to move the given file to one.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file_id` - The ID of the audio file to move.
+    pub fn open_track_picker(&self, audio_file_id: &AudioFileId) -> Result<()> {
+        let audio_file_id = audio_file_id.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                track_picker_dialog(s, audio_file_id);
+            }))
+            .map_err(|_| anyhow!("Error sending open track picker callback to CbSink!"))
+    }
+
+    pub fn remove_album(&self, album_id: &AlbumId) -> Result<()> {
+        let album_id = album_id.clone();
+        let hidden = self.hidden.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                s.call_on_name(
+                    ALBUM_FILE_TABLE,
+                    |table: &mut TableView<AlbumView, AudioFileColumn>| {
+                        let Ok(mut hidden) = hidden.lock() else {
+                            error!("Error locking hidden albums mutex!");
+                            return;
+                        };
+                        remove_album_rows(table, &album_id);
+                        if let Some(hidden) = hidden.as_mut() {
+                            hidden.retain(|view| *view.id.album_id() != album_id);
+                        }
+                    },
+                );
+            }))
+            .map_err(|_| anyhow!("Error sending remove album callback to CbSink!"))
+    }
+
+    /// Toggles between showing all albums and only the albums whose tracks aren't all matched
+    /// to exactly one file.
+    pub fn toggle_incomplete_albums(&self) -> Result<()> {
+        let hidden = self.hidden.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                s.call_on_name(
+                    ALBUM_FILE_TABLE,
+                    |table: &mut TableView<AlbumView, AudioFileColumn>| {
+                        let Ok(mut hidden) = hidden.lock() else {
+                            error!("Error locking hidden albums mutex!");
+                            return;
+                        };
+                        match hidden.take() {
+                            Some(views) => {
+                                for view in views {
+                                    table.insert_item(view);
+                                }
+                            }
+                            None => {
+                                let complete: Vec<AlbumId> = table
+                                    .borrow_items()
+                                    .iter()
+                                    .filter(|item| {
+                                        item.status.is_some_and(|status| status.is_complete())
+                                    })
+                                    .map(|item| item.id.album_id().clone())
+                                    .collect();
+                                let mut views = vec![];
+                                while let Some(index) =
+                                    table.index_of(|item| complete.contains(item.id.album_id()))
+                                {
+                                    if let Some(view) = table.remove_item(index) {
+                                        views.push(view);
+                                    }
+                                }
+                                *hidden = Some(views);
+                            }
+                        }
+                    },
+                );
+            }))
+            .map_err(|_| anyhow!("Error sending toggle incomplete albums callback to CbSink!"))
+    }
+
     // need to match add_album
     pub fn update_audio_file(&self, audio_file: &AudioFile, metadata: &Metadata) -> Result<()> {
         let audio_file = audio_file.clone();
@@ -107,21 +243,200 @@ impl Default for AlbumTable {
     }
 }
 
-fn album_view_dialog(s: &mut Cursive, audio_file: Option<AudioFile>, title: String) -> Result<()> {
-    let save = Button::new("Save", move |_| {
-        if let Some(audio_file) = &audio_file {
-            if let Err(e) =
-                UiEventService::instance().send(UiEvent::SaveAudioFile(audio_file.clone()))
-            {
-                error!("Error sending scan audio file event: {e}!");
-            }
+/// Removes the rows of the given album and its tracks from the table.
+///
+/// # Arguments
+///
+/// * `table` - The album table.
+/// * `album_id` - The ID of the album to remove the rows of.
+fn remove_album_rows(table: &mut TableView<AlbumView, AudioFileColumn>, album_id: &AlbumId) {
+    while let Some(index) = table.index_of(|item| item.id.album_id() == album_id) {
+        table.remove_item(index);
+    }
+}
+
+/// Opens an actions dialog for the given album or track row.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `album_view` - The album or track row to open the dialog for.
+/// * `audio_file` - The audio file matched to the track, if any.
+fn album_view_dialog(s: &mut Cursive, album_view: AlbumView, audio_file: Option<AudioFile>) {
+    let mut layout = LinearLayout::vertical();
+    match (&album_view.id, audio_file) {
+        (AlbumViewId::Album(album_id), _) => {
+            let unmatch_album_id = album_id.clone();
+            layout.add_child(Button::new("Unmatch All", move |s| {
+                if let Err(e) =
+                    UiEventService::instance().send(UiEvent::UnmatchAlbum(unmatch_album_id.clone()))
+                {
+                    error!("Error sending unmatch album event: {e}!");
+                }
+                s.pop_layer();
+            }));
+            let other_versions_album_id = album_id.clone();
+            layout.add_child(Button::new("Other Versions", move |s| {
+                if let Err(e) = UiEventService::instance()
+                    .send(UiEvent::OpenOtherVersions(other_versions_album_id.clone()))
+                {
+                    error!("Error sending open other versions event: {e}!");
+                }
+                s.pop_layer();
+            }));
+        }
+        (AlbumViewId::Track(_), Some(audio_file)) => {
+            let audio_file_id = audio_file.id.clone();
+            layout.add_child(Button::new("Save", move |_| {
+                if let Err(e) =
+                    UiEventService::instance().send(UiEvent::SaveAudioFile(audio_file.clone()))
+                {
+                    error!("Error sending save audio file event: {e}!");
+                }
+            }));
+            let unmatch_audio_file_id = audio_file_id.clone();
+            layout.add_child(Button::new("Unmatch", move |s| {
+                if let Err(e) = UiEventService::instance()
+                    .send(UiEvent::UnmatchAudioFile(unmatch_audio_file_id.clone()))
+                {
+                    error!("Error sending unmatch audio file event: {e}!");
+                }
+                s.pop_layer();
+            }));
+            layout.add_child(Button::new("Move to Track", move |s| {
+                s.pop_layer();
+                track_picker_dialog(s, audio_file_id.clone());
+            }));
+        }
+        (AlbumViewId::Track(_), None) => {}
+    }
+    layout.add_child(Button::new("Cancel", |s| {
+        s.pop_layer();
+    }));
+    let dialog = Dialog::around(layout).title(album_view.title);
+    s.add_layer(dialog);
+}
+
+/// Opens a dialog listing the tracks of the loaded albums, sending the one chosen to move the
+/// given file to. Choosing a track that has a file swaps the files.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `audio_file_id` - The ID of the audio file to move.
+fn track_picker_dialog(s: &mut Cursive, audio_file_id: AudioFileId) {
+    let mut tracks: Vec<(TrackId, String)> = s
+        .call_on_name(
+            ALBUM_FILE_TABLE,
+            |table: &mut TableView<AlbumView, AudioFileColumn>| {
+                let items = table.borrow_items();
+                let mut tracks: Vec<&AlbumView> = items
+                    .iter()
+                    .filter(|item| matches!(item.id, AlbumViewId::Track(_)))
+                    .collect();
+                tracks.sort_by_key(|item| {
+                    (
+                        item.id.album_id().value.clone(),
+                        item.disc_number,
+                        item.number,
+                    )
+                });
+                tracks
+                    .into_iter()
+                    .filter_map(|item| match &item.id {
+                        AlbumViewId::Album(_) => None,
+                        AlbumViewId::Track(track_id) => {
+                            let album_title = items
+                                .iter()
+                                .find(|album| {
+                                    album.id == AlbumViewId::Album(track_id.album_id.clone())
+                                })
+                                .map(|album| album.title.clone())
+                                .unwrap_or_default();
+                            Some((
+                                track_id.clone(),
+                                format!("{} / {}", album_title, item.title.trim()),
+                            ))
+                        }
+                    })
+                    .collect()
+            },
+        )
+        .unwrap_or_default();
+    if tracks.is_empty() {
+        s.add_layer(Dialog::info("No albums are loaded to move the file to."));
+        return;
+    }
+
+    let mut select_view = SelectView::new();
+    for (track_id, label) in tracks.drain(..) {
+        select_view.add_item(label, track_id);
+    }
+    select_view.set_on_submit(move |s, track_id: &TrackId| {
+        if let Err(e) = UiEventService::instance().send(UiEvent::MoveAudioFile(
+            audio_file_id.clone(),
+            track_id.clone(),
+        )) {
+            error!("Error sending move audio file event: {e}!");
         }
+        s.pop_layer();
     });
-    let cancel = Button::new("Cancel", |s| {
+    let dialog = Dialog::around(ScrollView::new(select_view))
+        .title("Move to Track")
+        .button("Cancel", |s| {
+            s.pop_layer();
+        });
+    s.add_layer(dialog);
+}
+
+/// Opens a dialog listing the given releases of an album's release group, sending the one
+/// chosen to switch the album to. The album's own release is marked.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `album_id` - The ID of the album to switch.
+/// * `versions` - The releases of the album's release group.
+fn other_versions_dialog(s: &mut Cursive, album_id: AlbumId, versions: Vec<ReleaseVersion>) {
+    if versions.len() < 2 {
+        s.add_layer(Dialog::info(
+            "No other versions of this release were found.",
+        ));
+        return;
+    }
+    let title = versions
+        .iter()
+        .find(|version| version.album.id == album_id)
+        .map(|version| version.album.title.clone())
+        .unwrap_or_default();
+    let mut select_view = SelectView::new();
+    for version in versions {
+        let album = &version.album;
+        let label = format!(
+            "{} {:<10}  {:<2}  {:<8}  {:>3} tracks  {} {}",
+            if album.id == album_id { "*" } else { " " },
+            album.date,
+            album.release_country,
+            version.formats,
+            version.track_count,
+            album.record_label.as_deref().unwrap_or_default(),
+            album.catalog_number.as_deref().unwrap_or_default()
+        );
+        select_view.add_item(label, album.id.clone());
+    }
+    select_view.set_on_submit(move |s, new_album_id: &AlbumId| {
+        if let Err(e) = UiEventService::instance().send(UiEvent::SwitchRelease(
+            album_id.clone(),
+            new_album_id.clone(),
+        )) {
+            error!("Error sending switch release event: {e}!");
+        }
         s.pop_layer();
     });
-    let layout = LinearLayout::vertical().child(save).child(cancel);
-    let dialog = Dialog::around(layout).title(title);
+    let dialog = Dialog::around(ScrollView::new(select_view))
+        .title(format!("Other Versions of {}", title))
+        .button("Cancel", |s| {
+            s.pop_layer();
+        });
     s.add_layer(dialog);
-    Ok(())
 }
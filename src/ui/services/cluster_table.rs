@@ -1,49 +1,82 @@
 use super::{CbSinkService, UiEventService};
 use crate::{
-    domain::models::{AudioFile, Metadata},
+    domain::models::{AudioFile, AudioFileId, Cluster, ClusterId, LookupResult, Metadata},
     infrastructure::TableViewExtensions,
     ui::{
         events::UiEvent,
-        models::{AudioFileColumn, AudioFileView, CLUSTER_FILE_TABLE},
+        models::{AudioFileColumn, ClusterView, ClusterViewId, CLUSTER_FILE_TABLE},
     },
 };
 use anyhow::{anyhow, Result};
 use cursive::{
-    views::{Button, Dialog, LinearLayout},
+    views::{Button, Dialog, LinearLayout, ScrollView, SelectView},
     Cursive,
 };
 use cursive_table_view::TableView;
 use log::error;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-pub struct ClusterTable {}
+/// The file rows of collapsed clusters, which aren't in the table while they're hidden.
+type CollapsedClusters = Arc<Mutex<HashMap<Option<ClusterId>, Vec<ClusterView>>>>;
+
+pub struct ClusterTable {
+    collapsed: CollapsedClusters,
+}
 
 impl ClusterTable {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            collapsed: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 
-    pub fn add_cluster_file(&self, audio_file: AudioFile, metadata: &Metadata) -> Result<()> {
-        let audio_file_view = AudioFileView::new(&audio_file, metadata);
+    /// Adds or moves the row of the given file to the given cluster, adding the cluster's row
+    /// if it isn't shown yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_file` - The audio file to add.
+    /// * `metadata` - The tags of the audio file.
+    /// * `cluster` - The cluster of the audio file, or none if it's unclustered.
+    pub fn add_cluster_file(
+        &self,
+        audio_file: AudioFile,
+        metadata: &Metadata,
+        cluster: Option<Cluster>,
+    ) -> Result<()> {
+        let file_view = ClusterView::for_audio_file(&audio_file, metadata);
+        let collapsed = self.collapsed.clone();
         CbSinkService::instance()?
             .send(Box::new(move |s: &mut Cursive| {
                 s.call_on_name(
                     CLUSTER_FILE_TABLE,
-                    |table: &mut TableView<AudioFileView, AudioFileColumn>| match table
-                        .index_of(|i| i.id == audio_file_view.id)
-                    {
-                        Some(index) => {
-                            table.remove_item(index);
-                            table.insert_item_at(index, audio_file_view.clone());
+                    |table: &mut TableView<ClusterView, AudioFileColumn>| {
+                        let Ok(mut collapsed) = collapsed.lock() else {
+                            error!("Error locking collapsed clusters mutex!");
+                            return;
+                        };
+                        let cluster_id = file_view.id.cluster_id().clone();
+                        remove_audio_file_rows(table, &mut collapsed, &audio_file.id);
+                        let cluster_row = ClusterViewId::Cluster(cluster_id.clone());
+                        if table.index_of(|item| item.id == cluster_row).is_none() {
+                            let is_expanded = !collapsed.contains_key(&cluster_id);
+                            table.insert_item(ClusterView::for_cluster(
+                                cluster.as_ref(),
+                                is_expanded,
+                            ));
                         }
-                        None => {
-                            table.insert_item(audio_file_view.clone());
-                            if table.len() == 1 {
-                                if let Err(e) = UiEventService::instance()
-                                    .send(UiEvent::SelectClusterFile(audio_file_view))
-                                {
-                                    error!("Error sending select cluster file event: {e}!");
-                                };
-                            }
+                        match collapsed.get_mut(&cluster_id) {
+                            Some(hidden) => hidden.push(file_view.clone()),
+                            None => table.insert_item(file_view.clone()),
+                        }
+                        remove_empty_cluster_rows(table, &collapsed);
+                        if table.len() == 2 {
+                            if let Err(e) = UiEventService::instance()
+                                .send(UiEvent::SelectClusterView(file_view))
+                            {
+                                error!("Error sending select cluster view event: {e}!");
+                            };
                         }
                     },
                 );
@@ -61,26 +94,106 @@ impl ClusterTable {
             .map_err(|_| anyhow!("Error sending open audio file dialog callback to CbSink!"))
     }
 
+    pub fn open_cluster_dialog(&self, cluster_view: &ClusterView) -> Result<()> {
+        let cluster_view = cluster_view.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                cluster_dialog(s, cluster_view);
+            }))
+            .map_err(|_| anyhow!("Error sending open cluster dialog callback to CbSink!"))
+    }
+
+    /// Opens a dialog listing the given lookup results, best first, for the user to choose the
+    /// release to match the looked up files to.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title of what was looked up.
+    /// * `audio_file_ids` - The IDs of the audio files that were looked up.
+    /// * `results` - The lookup results to choose from.
+    pub fn open_lookup_dialog(
+        &self,
+        title: &str,
+        audio_file_ids: Vec<AudioFileId>,
+        results: Vec<LookupResult>,
+    ) -> Result<()> {
+        let title = title.to_owned();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                lookup_dialog(s, title, audio_file_ids, results);
+            }))
+            .map_err(|_| anyhow!("Error sending open lookup dialog callback to CbSink!"))
+    }
+
     pub fn remove_cluster_file(&self, audio_file: &AudioFile) -> Result<()> {
         let path = audio_file.id.clone();
+        let collapsed = self.collapsed.clone();
         CbSinkService::instance()?
             .send(Box::new(move |s: &mut Cursive| {
                 s.call_on_name(
                     CLUSTER_FILE_TABLE,
-                    |table: &mut TableView<AudioFileView, AudioFileColumn>| {
-                        if let Some((index, _)) = table
-                            .borrow_items()
-                            .iter()
-                            .enumerate()
-                            .find(|(_, item)| item.id == path)
-                        {
-                            table.remove_item(index);
-                        }
+                    |table: &mut TableView<ClusterView, AudioFileColumn>| {
+                        let Ok(mut collapsed) = collapsed.lock() else {
+                            error!("Error locking collapsed clusters mutex!");
+                            return;
+                        };
+                        remove_audio_file_rows(table, &mut collapsed, &path);
+                        remove_empty_cluster_rows(table, &collapsed);
                     },
                 );
             }))
             .map_err(|_| anyhow!("Error senidng remove cluster file callback to CbSink!"))
     }
+
+    /// Shows the file rows of the given cluster if they're hidden, or hides them otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster_id` - The ID of the cluster, or none for the unclustered files.
+    pub fn toggle_cluster(&self, cluster_id: &Option<ClusterId>) -> Result<()> {
+        let cluster_id = cluster_id.clone();
+        let collapsed = self.collapsed.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                s.call_on_name(
+                    CLUSTER_FILE_TABLE,
+                    |table: &mut TableView<ClusterView, AudioFileColumn>| {
+                        let Ok(mut collapsed) = collapsed.lock() else {
+                            error!("Error locking collapsed clusters mutex!");
+                            return;
+                        };
+                        let is_expanded = match collapsed.remove(&cluster_id) {
+                            Some(hidden) => {
+                                for view in hidden {
+                                    table.insert_item(view);
+                                }
+                                true
+                            }
+                            None => {
+                                let mut hidden = vec![];
+                                while let Some(index) = table.index_of(|item| {
+                                    item.id.audio_file_id().is_some()
+                                        && *item.id.cluster_id() == cluster_id
+                                }) {
+                                    if let Some(view) = table.remove_item(index) {
+                                        hidden.push(view);
+                                    }
+                                }
+                                collapsed.insert(cluster_id.clone(), hidden);
+                                false
+                            }
+                        };
+                        let cluster_row = ClusterViewId::Cluster(cluster_id.clone());
+                        if let Some(index) = table.index_of(|item| item.id == cluster_row) {
+                            if let Some(item) = table.borrow_item_mut(index) {
+                                item.is_expanded = is_expanded;
+                            }
+                        }
+                    },
+                );
+            }))
+            .map_err(|_| anyhow!("Error sending toggle cluster callback to CbSink!"))
+    }
 }
 
 impl Default for ClusterTable {
@@ -89,6 +202,62 @@ impl Default for ClusterTable {
     }
 }
 
+/// Removes the row of the given file from the table, or from the hidden rows of a collapsed
+/// cluster.
+///
+/// # Arguments
+///
+/// * `table` - The cluster table.
+/// * `collapsed` - The hidden file rows of the collapsed clusters.
+/// * `audio_file_id` - The ID of the audio file to remove the row of.
+fn remove_audio_file_rows(
+    table: &mut TableView<ClusterView, AudioFileColumn>,
+    collapsed: &mut HashMap<Option<ClusterId>, Vec<ClusterView>>,
+    audio_file_id: &AudioFileId,
+) {
+    let is_file_row = |item: &ClusterView| item.id.audio_file_id() == Some(audio_file_id);
+    if let Some(index) = table.index_of(is_file_row) {
+        table.remove_item(index);
+    }
+    for hidden in collapsed.values_mut() {
+        hidden.retain(|item| !is_file_row(item));
+    }
+}
+
+/// Removes the rows of the clusters that no longer have any files.
+///
+/// # Arguments
+///
+/// * `table` - The cluster table.
+/// * `collapsed` - The hidden file rows of the collapsed clusters.
+fn remove_empty_cluster_rows(
+    table: &mut TableView<ClusterView, AudioFileColumn>,
+    collapsed: &HashMap<Option<ClusterId>, Vec<ClusterView>>,
+) {
+    let items = table.borrow_items();
+    let empty_cluster_ids: Vec<Option<ClusterId>> = items
+        .iter()
+        .filter_map(|item| match &item.id {
+            ClusterViewId::Cluster(cluster_id) => Some(cluster_id.clone()),
+            ClusterViewId::AudioFile(_, _) => None,
+        })
+        .filter(|cluster_id| {
+            !items
+                .iter()
+                .any(|item| matches!(&item.id, ClusterViewId::AudioFile(id, _) if id == cluster_id))
+                && collapsed
+                    .get(cluster_id)
+                    .is_none_or(|hidden| hidden.is_empty())
+        })
+        .collect();
+    for cluster_id in empty_cluster_ids {
+        let cluster_row = ClusterViewId::Cluster(cluster_id);
+        if let Some(index) = table.index_of(|item| item.id == cluster_row) {
+            table.remove_item(index);
+        }
+    }
+}
+
 /// Opens an audio file actions dialog for the given audio file view.
 ///
 /// # Arguments
@@ -96,18 +265,36 @@ impl Default for ClusterTable {
 /// * `s` - The Cursive to open the dialog with.
 /// * `view` - The audio file view to open the dialog for.
 fn audio_file_dialog(s: &mut Cursive, audio_file: AudioFile, title: String) {
-    let lookup = Button::new("Lookup", |_| {});
+    let audio_file_id = audio_file.id.clone();
+    let move_audio_file_id = audio_file.id.clone();
+    let lookup = Button::new("Lookup", move |s| {
+        if let Err(e) =
+            UiEventService::instance().send(UiEvent::LookupAudioFile(audio_file_id.clone()))
+        {
+            error!("Error sending lookup audio file event: {e}!");
+        }
+        s.pop_layer();
+    });
     let scan = Button::new("Scan", move |_| {
         if let Err(e) = UiEventService::instance().send(UiEvent::ScanAudioFile(audio_file.clone()))
         {
             error!("Error sending scan audio file event: {e}!");
         }
     });
+    let move_to_track = Button::new("Move to Track", move |s| {
+        if let Err(e) =
+            UiEventService::instance().send(UiEvent::OpenTrackPicker(move_audio_file_id.clone()))
+        {
+            error!("Error sending open track picker event: {e}!");
+        }
+        s.pop_layer();
+    });
     let save = Button::new("Save", |_| {});
     let remove = Button::new("Remove", |_| {});
     let layout = LinearLayout::vertical()
         .child(lookup)
         .child(scan)
+        .child(move_to_track)
         .child(save)
         .child(remove);
     let dialog = Dialog::around(layout)
@@ -118,3 +305,101 @@ fn audio_file_dialog(s: &mut Cursive, audio_file: AudioFile, title: String) {
         });
     s.add_layer(dialog);
 }
+
+/// Opens a cluster actions dialog for the given cluster view.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `cluster_view` - The cluster view to open the dialog for.
+fn cluster_dialog(s: &mut Cursive, cluster_view: ClusterView) {
+    let cluster_id = cluster_view.id.cluster_id().clone();
+    let toggle_label = match cluster_view.is_expanded {
+        true => "Collapse",
+        false => "Expand",
+    };
+    let toggle_cluster_id = cluster_id.clone();
+    let toggle = Button::new(toggle_label, move |s| {
+        if let Err(e) =
+            UiEventService::instance().send(UiEvent::ToggleCluster(toggle_cluster_id.clone()))
+        {
+            error!("Error sending toggle cluster event: {e}!");
+        }
+        s.pop_layer();
+    });
+    let mut layout = LinearLayout::vertical().child(toggle);
+    if let Some(cluster_id) = cluster_id {
+        let lookup_cluster_id = cluster_id.clone();
+        let lookup = Button::new("Lookup", move |s| {
+            if let Err(e) =
+                UiEventService::instance().send(UiEvent::LookupCluster(lookup_cluster_id.clone()))
+            {
+                error!("Error sending lookup cluster event: {e}!");
+            }
+            s.pop_layer();
+        });
+        let scan = Button::new("Scan", move |s| {
+            if let Err(e) =
+                UiEventService::instance().send(UiEvent::ScanCluster(cluster_id.clone()))
+            {
+                error!("Error sending scan cluster event: {e}!");
+            }
+            s.pop_layer();
+        });
+        layout = layout.child(lookup).child(scan);
+    }
+    let dialog = Dialog::around(layout)
+        .title(cluster_view.title)
+        .button("Cancel", |s| {
+            s.pop_layer();
+        });
+    s.add_layer(dialog);
+}
+
+/// Opens a dialog listing the given lookup results, sending the one chosen to be matched.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `title` - The title of what was looked up.
+/// * `audio_file_ids` - The IDs of the audio files that were looked up.
+/// * `results` - The lookup results to choose from.
+fn lookup_dialog(
+    s: &mut Cursive,
+    title: String,
+    audio_file_ids: Vec<AudioFileId>,
+    results: Vec<LookupResult>,
+) {
+    if results.is_empty() {
+        s.add_layer(Dialog::info(format!("No releases found for {}.", title)));
+        return;
+    }
+    let mut select_view = SelectView::new();
+    for result in results {
+        let album = &result.album;
+        let label = format!(
+            "{:>3.0}%  {} - {} ({}, {})",
+            result.score * 100.0,
+            album.title,
+            album.artist,
+            album.date,
+            album.release_country
+        );
+        select_view.add_item(label, result);
+    }
+    select_view.set_on_submit(move |s, result: &LookupResult| {
+        if let Err(e) = UiEventService::instance().send(UiEvent::MatchLookupResult(
+            audio_file_ids.clone(),
+            Box::new(result.clone()),
+        )) {
+            error!("Error sending match lookup result event: {e}!");
+        }
+        s.pop_layer();
+    });
+    let dialog = Dialog::around(ScrollView::new(select_view))
+        .title(format!("Lookup {}", title))
+        .button("Cancel", |s| {
+            s.pop_layer();
+        });
+    s.add_layer(dialog);
+}
@@ -2,9 +2,9 @@ use super::{CbSinkService, UiEventService};
 use crate::infrastructure::CursiveExtensions;
 use crate::ui::events::UiEvent;
 use crate::ui::models::{
-    AlbumView, AudioFileColumn, AudioFileView, TagFieldColumn, TagFieldView, ALBUM_FILE_TABLE,
-    CLUSTER_FILE_TABLE, HIDEABLE_BOTTOM_PANEL, HIDEABLE_LEFT_PANEL, HIDEABLE_RIGHT_PANEL,
-    METADATA_TABLE,
+    AlbumView, AudioFileColumn, ClusterView, ClusterViewId, TagFieldColumn, TagFieldView,
+    ALBUM_FILE_TABLE, CLUSTER_FILE_TABLE, HIDEABLE_BOTTOM_PANEL, HIDEABLE_LEFT_PANEL,
+    HIDEABLE_RIGHT_PANEL, METADATA_TABLE,
 };
 use anyhow::{anyhow, Result};
 use cursive::align::HAlign;
@@ -59,7 +59,7 @@ impl CursiveWrapper {
         root.add_global_callback(Event::CtrlChar('l'), |s| {
             s.call_on_name(
                 HIDEABLE_LEFT_PANEL,
-                |hideable: &mut TablePanel<AudioFileView, AudioFileColumn>| {
+                |hideable: &mut TablePanel<ClusterView, AudioFileColumn>| {
                     hideable.set_visible(!hideable.is_visible());
                 },
             );
@@ -67,7 +67,7 @@ impl CursiveWrapper {
         root.add_global_callback(Event::CtrlChar('r'), |s| {
             s.call_on_name(
                 HIDEABLE_RIGHT_PANEL,
-                |hideable: &mut TablePanel<AudioFileView, AudioFileColumn>| {
+                |hideable: &mut TablePanel<AlbumView, AudioFileColumn>| {
                     hideable.set_visible(!hideable.is_visible());
                 },
             );
@@ -81,18 +81,57 @@ impl CursiveWrapper {
     /// * `root` - The root to configure the menubar on.
     fn setup_menubar(root: &mut CursiveRunnable) {
         root.menubar()
-            .add_subtree("File", menu::Tree::new().leaf("Quit", Cursive::quit))
+            .add_subtree(
+                "File",
+                menu::Tree::new()
+                    .leaf("Load Release", |_| {
+                        if let Err(e) = UiEventService::instance().send(UiEvent::OpenLoadRelease) {
+                            error!("Error sending open load release event to UI: {e}!");
+                        }
+                    })
+                    .delimiter()
+                    .leaf("Quit", Cursive::quit),
+            )
             .add_subtree("Edit", menu::Tree::new())
             .add_subtree(
                 "View",
-                menu::Tree::new().leaf("Logs", |_| {
-                    if let Err(e) = UiEventService::instance().send(UiEvent::OpenLogs) {
-                        error!("Error sending open logs event to UI: {e}!");
+                menu::Tree::new()
+                    .leaf("Incomplete Albums Only", |_| {
+                        if let Err(e) =
+                            UiEventService::instance().send(UiEvent::ToggleIncompleteAlbums)
+                        {
+                            error!("Error sending toggle incomplete albums event to UI: {e}!");
+                        }
+                    })
+                    .leaf("Logs", |_| {
+                        if let Err(e) = UiEventService::instance().send(UiEvent::OpenLogs) {
+                            error!("Error sending open logs event to UI: {e}!");
+                        }
+                    }),
+            )
+            .add_subtree(
+                "Options",
+                menu::Tree::new().leaf("Release Preferences", |_| {
+                    if let Err(e) = UiEventService::instance().send(UiEvent::OpenReleasePreferences)
+                    {
+                        error!("Error sending open release preferences event to UI: {e}!");
                     }
                 }),
             )
-            .add_subtree("Options", menu::Tree::new())
-            .add_subtree("Tools", menu::Tree::new())
+            .add_subtree(
+                "Tools",
+                menu::Tree::new()
+                    .leaf("Find Duplicates", |_| {
+                        if let Err(e) = UiEventService::instance().send(UiEvent::FindDuplicates) {
+                            error!("Error sending find duplicates event to UI: {e}!");
+                        }
+                    })
+                    .leaf("Submit AcoustIDs", |_| {
+                        if let Err(e) = UiEventService::instance().send(UiEvent::SubmitAcoustIds) {
+                            error!("Error sending submit AcoustIDs event to UI: {e}!");
+                        }
+                    }),
+            )
             .add_subtree("Help", menu::Tree::new());
         root.set_autohide_menu(false);
     }
@@ -103,7 +142,7 @@ impl CursiveWrapper {
     ///
     /// * `root` - The root to add the widgets to.
     fn add_widgets(root: &mut CursiveRunnable) {
-        let cluster_file_table = TableView::<AudioFileView, AudioFileColumn>::new()
+        let cluster_file_table = TableView::<ClusterView, AudioFileColumn>::new()
             .column(
                 AudioFileColumn::Title,
                 AudioFileColumn::Title.as_str(),
@@ -119,20 +158,27 @@ impl CursiveWrapper {
                 AudioFileColumn::Length.as_str(),
                 |c| c.width(8).align(HAlign::Right),
             )
+            .column(
+                AudioFileColumn::Audio,
+                AudioFileColumn::Audio.as_str(),
+                |c| c,
+            )
             .on_select(|s: &mut Cursive, _row: usize, index: usize| {
                 if let Err(e) = (|| {
-                    let selected_audio_file = s.clone_audio_file_view(index)?;
-                    UiEventService::instance().send(UiEvent::SelectClusterFile(selected_audio_file))
+                    let selected_cluster_view = s.clone_cluster_view(index)?;
+                    UiEventService::instance()
+                        .send(UiEvent::SelectClusterView(selected_cluster_view))
                 })() {
-                    error!("Error sending select cluster file event: {e}!");
+                    error!("Error sending select cluster view event: {e}!");
                 }
             })
             .on_submit(|s: &mut Cursive, _row: usize, index: usize| {
                 if let Err(e) = (|| {
-                    let selected_audio_file = s.clone_audio_file_view(index)?;
-                    UiEventService::instance().send(UiEvent::SubmitClusterFile(selected_audio_file))
+                    let submitted_cluster_view = s.clone_cluster_view(index)?;
+                    UiEventService::instance()
+                        .send(UiEvent::SubmitClusterView(submitted_cluster_view))
                 })() {
-                    error!("Error sending submit cluster file event: {e}!");
+                    error!("Error sending submit cluster view event: {e}!");
                 }
             })
             .with_name(CLUSTER_FILE_TABLE);
@@ -153,6 +199,11 @@ impl CursiveWrapper {
                 AudioFileColumn::Length.as_str(),
                 |c| c.width(8).align(HAlign::Right),
             )
+            .column(
+                AudioFileColumn::Audio,
+                AudioFileColumn::Audio.as_str(),
+                |c| c,
+            )
             .on_select(|s: &mut Cursive, _row: usize, index: usize| {
                 if let Err(e) = (|| {
                     let selected_album_view = s.clone_album_view(index)?;
@@ -214,13 +265,65 @@ impl CursiveWrapper {
                     error!("Error sending open add file event: {e}!");
                 }
             }))
-            .child(Button::new("Cluster", Cursive::noop))
-            .child(Button::new("Lookup", Cursive::noop))
+            .child(Button::new("Cluster", |_| {
+                if let Err(e) = UiEventService::instance().send(UiEvent::ClusterFiles) {
+                    error!("Error sending cluster files event: {e}!");
+                }
+            }))
+            .child(Button::new("Lookup", |s| {
+                if let Err(e) = (|| {
+                    let index = s
+                        .call_on_name(
+                            CLUSTER_FILE_TABLE,
+                            |table: &mut TableView<ClusterView, AudioFileColumn>| table.item(),
+                        )
+                        .flatten()
+                        .ok_or_else(|| anyhow!("No file or cluster is selected to look up!"))?;
+                    let event = match s.clone_cluster_view(index)?.id {
+                        ClusterViewId::Cluster(Some(cluster_id)) => {
+                            UiEvent::LookupCluster(cluster_id)
+                        }
+                        ClusterViewId::Cluster(None) => {
+                            return Err(anyhow!("Cluster the files before looking them up!"))
+                        }
+                        ClusterViewId::AudioFile(_, audio_file_id) => {
+                            UiEvent::LookupAudioFile(audio_file_id)
+                        }
+                    };
+                    UiEventService::instance().send(event)
+                })() {
+                    error!("Error sending lookup event: {e}!");
+                }
+            }))
             .child(Button::new("Scan", Cursive::noop))
             .child(Button::new("Save", Cursive::noop))
             .child(Button::new("Info", Cursive::noop))
             .child(Button::new("Remove", Cursive::noop))
-            .child(Button::new("Lookup CD", Cursive::noop));
+            .child(Button::new("Lookup CD", |s| {
+                if let Err(e) = (|| {
+                    let index = s
+                        .call_on_name(
+                            CLUSTER_FILE_TABLE,
+                            |table: &mut TableView<ClusterView, AudioFileColumn>| table.item(),
+                        )
+                        .flatten()
+                        .ok_or_else(|| anyhow!("No file or cluster of a rip is selected!"))?;
+                    let event = match s.clone_cluster_view(index)?.id {
+                        ClusterViewId::Cluster(Some(cluster_id)) => {
+                            UiEvent::LookupClusterDisc(cluster_id)
+                        }
+                        ClusterViewId::Cluster(None) => {
+                            return Err(anyhow!("Select a file of the rip to look up its disc!"))
+                        }
+                        ClusterViewId::AudioFile(_, audio_file_id) => {
+                            UiEvent::LookupAudioFileDisc(audio_file_id)
+                        }
+                    };
+                    UiEventService::instance().send(event)
+                })() {
+                    error!("Error sending lookup disc event: {e}!");
+                }
+            }));
         let top_level_layout = LinearLayout::vertical()
             .child(file_panes)
             .child(hideable_bottom_panel)
@@ -1,19 +1,20 @@
 use super::{CbSinkService, UiEventService};
 use crate::{
     infrastructure::{
+        config::{ReleasePreferences, ReleaseWeights},
         file::{File, FileService, FileType},
         CursiveExtensions,
     },
     ui::{
         events::UiEvent,
-        models::{FileColumn, FileDialogType, FileView, FILE_TABLE},
+        models::{FileColumn, FileDialogType, FileView, FILE_TABLE, LOAD_RELEASE_INPUT},
     },
 };
 use anyhow::{anyhow, Result};
 use cursive::{
     traits::Nameable,
     view::Resizable,
-    views::{Dialog, ScrollView, TextView},
+    views::{Dialog, EditView, LinearLayout, ListView, ScrollView, TextView},
     Cursive,
 };
 use cursive_table_view::TableView;
@@ -60,6 +61,24 @@ impl Menu {
             .map_err(|_| anyhow!("Error sending open file dialog callback to CbSink!"))
     }
 
+    pub fn open_duplicates(&self, duplicates: &str) -> Result<()> {
+        let duplicates = duplicates.to_owned();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                new_duplicates_dialog(s, &duplicates);
+            }))
+            .map_err(|_| anyhow!("Error sending open duplicates callback to CbSink!"))
+    }
+
+    /// Opens a dialog to enter the MusicBrainz ID or URL of a release to load.
+    pub fn open_load_release_dialog(&self) -> Result<()> {
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                new_load_release_dialog(s);
+            }))
+            .map_err(|_| anyhow!("Error sending open load release dialog callback to CbSink!"))
+    }
+
     pub fn open_logs(&self, logs: &str) -> Result<()> {
         let logs = logs.to_owned();
         CbSinkService::instance()?
@@ -68,6 +87,20 @@ impl Menu {
             }))
             .map_err(|_| anyhow!("Error sending open logs callback to CbSink!"))
     }
+
+    pub fn open_release_preferences(
+        &self,
+        preferences: &ReleasePreferences,
+        weights: &ReleaseWeights,
+    ) -> Result<()> {
+        let preferences = preferences.clone();
+        let weights = weights.clone();
+        CbSinkService::instance()?
+            .send(Box::new(move |s: &mut Cursive| {
+                new_release_preferences_dialog(s, &preferences, &weights);
+            }))
+            .map_err(|_| anyhow!("Error sending open release preferences callback to CbSink!"))
+    }
 }
 
 impl Default for Menu {
@@ -76,6 +109,51 @@ impl Default for Menu {
     }
 }
 
+fn new_duplicates_dialog(s: &mut Cursive, duplicates: &String) {
+    let text_view = TextView::new(duplicates);
+    let dialog = Dialog::around(ScrollView::new(text_view))
+        .title("Duplicates")
+        .button("Close", |s| {
+            s.pop_layer();
+        });
+
+    s.add_layer(dialog);
+}
+
+/// Opens a dialog to enter the MusicBrainz ID or URL of a release, sending it to be loaded.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+fn new_load_release_dialog(s: &mut Cursive) {
+    let load = |s: &mut Cursive, input: &str| {
+        if let Err(e) = UiEventService::instance().send(UiEvent::LoadRelease(input.to_owned())) {
+            error!("Error sending load release event: {e}!");
+        }
+        s.pop_layer();
+    };
+    let edit_view = EditView::new()
+        .on_submit(load)
+        .with_name(LOAD_RELEASE_INPUT)
+        .min_width(48);
+    let dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(TextView::new("Release ID or musicbrainz.org URL:"))
+            .child(edit_view),
+    )
+    .title("Load Release")
+    .button("Load", move |s| {
+        let input = s
+            .call_on_name(LOAD_RELEASE_INPUT, |view: &mut EditView| view.get_content())
+            .unwrap_or_default();
+        load(s, &input);
+    })
+    .button("Cancel", |s| {
+        s.pop_layer();
+    });
+    s.add_layer(dialog);
+}
+
 fn new_logs_dialog(s: &mut Cursive, logs: &String) {
     let text_view = TextView::new(logs);
     let dialog = Dialog::around(ScrollView::new(text_view))
@@ -87,6 +165,100 @@ fn new_logs_dialog(s: &mut Cursive, logs: &String) {
     s.add_layer(dialog);
 }
 
+/// Opens a new dialog for editing the release preferences and their weights. Each preference
+/// is edited as a comma-separated list, most preferred first.
+///
+/// # Arguments
+///
+/// * `s` - The Cursive to open the dialog with.
+/// * `preferences` - The current release preferences.
+/// * `weights` - The current release weights.
+fn new_release_preferences_dialog(
+    s: &mut Cursive,
+    preferences: &ReleasePreferences,
+    weights: &ReleaseWeights,
+) {
+    let rows = [
+        (
+            "Countries",
+            &preferences.countries,
+            weights.preferred_country,
+        ),
+        ("Formats", &preferences.formats, weights.preferred_format),
+        ("Statuses", &preferences.statuses, weights.preferred_status),
+        ("Types", &preferences.types, weights.preferred_type),
+    ];
+    let mut list_view = ListView::new();
+    for (label, values, weight) in rows {
+        list_view.add_child(
+            label,
+            LinearLayout::horizontal()
+                .child(
+                    EditView::new()
+                        .content(values.join(", "))
+                        .with_name(label)
+                        .min_width(40),
+                )
+                .child(TextView::new(" Weight "))
+                .child(
+                    EditView::new()
+                        .content(weight.to_string())
+                        .with_name(format!("{label} Weight"))
+                        .fixed_width(6),
+                ),
+        );
+    }
+
+    let weights = weights.clone();
+    let dialog = Dialog::around(list_view)
+        .title("Release Preferences")
+        .button("Save", move |s| {
+            let list = |s: &mut Cursive, label: &str| -> Vec<String> {
+                s.call_on_name(label, |edit_view: &mut EditView| {
+                    edit_view
+                        .get_content()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+            };
+            let weight = |s: &mut Cursive, label: &str, default: f64| -> f64 {
+                s.call_on_name(&format!("{label} Weight"), |edit_view: &mut EditView| {
+                    edit_view.get_content().trim().parse::<f64>().ok()
+                })
+                .flatten()
+                .unwrap_or(default)
+            };
+            let preferences = ReleasePreferences {
+                countries: list(s, "Countries"),
+                formats: list(s, "Formats"),
+                statuses: list(s, "Statuses"),
+                types: list(s, "Types"),
+            };
+            let weights = ReleaseWeights {
+                preferred_country: weight(s, "Countries", weights.preferred_country),
+                preferred_format: weight(s, "Formats", weights.preferred_format),
+                preferred_status: weight(s, "Statuses", weights.preferred_status),
+                preferred_type: weight(s, "Types", weights.preferred_type),
+                ..weights.clone()
+            };
+            if let Err(e) = UiEventService::instance()
+                .send(UiEvent::ReleasePreferencesSaved(preferences, weights))
+            {
+                error!("Error sending release preferences saved event: {e}!");
+            }
+            s.pop_layer();
+        })
+        .button("Cancel", |s| {
+            s.pop_layer();
+        });
+
+    s.add_layer(dialog);
+}
+
 /// Opens a new file/folder selection dialog of the given type and calls the
 /// given callback with the chosen file/folder.
 ///